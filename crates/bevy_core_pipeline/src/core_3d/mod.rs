@@ -19,6 +19,8 @@ pub mod graph {
         Prepass,
         DeferredPrepass,
         CopyDeferredLightingId,
+        TransparentDepthPrepass,
+        LinearDepth,
         EndPrepasses,
         StartMainPass,
         MainOpaquePass,
@@ -77,9 +79,11 @@ use crate::{
         DEFERRED_PREPASS_FORMAT,
     },
     prepass::{
-        node::PrepassNode, AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass,
-        NormalPrepass, Opaque3dPrepass, ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT,
-        NORMAL_PREPASS_FORMAT,
+        linear_depth::LinearDepthNode,
+        node::{PrepassNode, TransparentDepthPrepassNode},
+        AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, LinearDepthPrepass, MotionVectorPrepass,
+        NormalPrepass, Opaque3dPrepass, Transparent3dPrepass, TransparentDepthPrepass,
+        ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
     },
     skybox::SkyboxPlugin,
     tonemapping::TonemappingNode,
@@ -110,6 +114,7 @@ impl Plugin for Core3dPlugin {
             .init_resource::<DrawFunctions<Transparent3d>>()
             .init_resource::<DrawFunctions<Opaque3dPrepass>>()
             .init_resource::<DrawFunctions<AlphaMask3dPrepass>>()
+            .init_resource::<DrawFunctions<Transparent3dPrepass>>()
             .init_resource::<DrawFunctions<Opaque3dDeferred>>()
             .init_resource::<DrawFunctions<AlphaMask3dDeferred>>()
             .add_systems(ExtractSchedule, extract_core_3d_camera_phases)
@@ -123,6 +128,7 @@ impl Plugin for Core3dPlugin {
                     sort_phase_system::<Transparent3d>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<Opaque3dPrepass>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<AlphaMask3dPrepass>.in_set(RenderSet::PhaseSort),
+                    sort_phase_system::<Transparent3dPrepass>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<Opaque3dDeferred>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<AlphaMask3dDeferred>.in_set(RenderSet::PhaseSort),
                     prepare_core_3d_depth_textures.in_set(RenderSet::PrepareResources),
@@ -142,6 +148,11 @@ impl Plugin for Core3dPlugin {
                 Core3d,
                 Node3d::CopyDeferredLightingId,
             )
+            .add_render_graph_node::<ViewNodeRunner<TransparentDepthPrepassNode>>(
+                Core3d,
+                Node3d::TransparentDepthPrepass,
+            )
+            .add_render_graph_node::<ViewNodeRunner<LinearDepthNode>>(Core3d, Node3d::LinearDepth)
             .add_render_graph_node::<EmptyNode>(Core3d, Node3d::EndPrepasses)
             .add_render_graph_node::<EmptyNode>(Core3d, Node3d::StartMainPass)
             .add_render_graph_node::<ViewNodeRunner<MainOpaquePass3dNode>>(
@@ -166,6 +177,8 @@ impl Plugin for Core3dPlugin {
                     Node3d::Prepass,
                     Node3d::DeferredPrepass,
                     Node3d::CopyDeferredLightingId,
+                    Node3d::TransparentDepthPrepass,
+                    Node3d::LinearDepth,
                     Node3d::EndPrepasses,
                     Node3d::StartMainPass,
                     Node3d::MainOpaquePass,
@@ -464,13 +477,23 @@ pub fn extract_camera_prepass_phase(
                 Has<NormalPrepass>,
                 Has<MotionVectorPrepass>,
                 Has<DeferredPrepass>,
+                Has<TransparentDepthPrepass>,
+                Has<LinearDepthPrepass>,
             ),
             With<Camera3d>,
         >,
     >,
 ) {
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        cameras_3d.iter()
+    for (
+        entity,
+        camera,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+        transparent_depth_prepass,
+        linear_depth_prepass,
+    ) in cameras_3d.iter()
     {
         if camera.is_active {
             let mut entity = commands.get_or_spawn(entity);
@@ -489,9 +512,19 @@ pub fn extract_camera_prepass_phase(
                 ));
             }
 
+            if depth_prepass && transparent_depth_prepass {
+                entity.insert((
+                    RenderPhase::<Transparent3dPrepass>::default(),
+                    TransparentDepthPrepass,
+                ));
+            }
+
             if depth_prepass {
                 entity.insert(DepthPrepass);
             }
+            if depth_prepass && linear_depth_prepass {
+                entity.insert(LinearDepthPrepass);
+            }
             if normal_prepass {
                 entity.insert(NormalPrepass);
             }
@@ -511,7 +544,13 @@ pub fn prepare_core_3d_depth_textures(
     msaa: Res<Msaa>,
     render_device: Res<RenderDevice>,
     views_3d: Query<
-        (Entity, &ExtractedCamera, Option<&DepthPrepass>, &Camera3d),
+        (
+            Entity,
+            &ExtractedCamera,
+            Has<DepthPrepass>,
+            Has<TransparentDepthPrepass>,
+            &Camera3d,
+        ),
         (
             With<RenderPhase<Opaque3d>>,
             With<RenderPhase<AlphaMask3d>>,
@@ -521,10 +560,10 @@ pub fn prepare_core_3d_depth_textures(
     >,
 ) {
     let mut render_target_usage = HashMap::default();
-    for (_, camera, depth_prepass, camera_3d) in &views_3d {
+    for (_, camera, depth_prepass, transparent_depth_prepass, camera_3d) in &views_3d {
         // Default usage required to write to the depth texture
         let mut usage: TextureUsages = camera_3d.depth_texture_usages.into();
-        if depth_prepass.is_some() {
+        if depth_prepass || transparent_depth_prepass {
             // Required to read the output of the prepass
             usage |= TextureUsages::COPY_SRC;
         }
@@ -535,7 +574,7 @@ pub fn prepare_core_3d_depth_textures(
     }
 
     let mut textures = HashMap::default();
-    for (entity, camera, _, camera_3d) in &views_3d {
+    for (entity, camera, _, _, camera_3d) in &views_3d {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
         };
@@ -699,6 +738,7 @@ pub fn prepare_prepass_textures(
             Has<NormalPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<TransparentDepthPrepass>,
         ),
         Or<(
             With<RenderPhase<Opaque3dPrepass>>,
@@ -713,8 +753,16 @@ pub fn prepare_prepass_textures(
     let mut deferred_textures = HashMap::default();
     let mut deferred_lighting_id_textures = HashMap::default();
     let mut motion_vectors_textures = HashMap::default();
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        &views_3d
+    let mut transparent_depth_textures = HashMap::default();
+    for (
+        entity,
+        camera,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+        transparent_depth_prepass,
+    ) in &views_3d
     {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
@@ -835,6 +883,29 @@ pub fn prepare_prepass_textures(
                 .clone()
         });
 
+        let cached_transparent_depth_texture = transparent_depth_prepass.then(|| {
+            transparent_depth_textures
+                .entry(camera.target.clone())
+                .or_insert_with(|| {
+                    texture_cache.get(
+                        &render_device,
+                        TextureDescriptor {
+                            label: Some("prepass_transparent_depth_texture"),
+                            size,
+                            mip_level_count: 1,
+                            sample_count: msaa.samples(),
+                            dimension: TextureDimension::D2,
+                            format: CORE_3D_DEPTH_FORMAT,
+                            usage: TextureUsages::COPY_DST
+                                | TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                            view_formats: &[],
+                        },
+                    )
+                })
+                .clone()
+        });
+
         commands.entity(entity).insert(ViewPrepassTextures {
             depth: cached_depth_texture
                 .map(|t| ColorAttachment::new(t, None, Some(LegacyColor::BLACK))),
@@ -849,6 +920,8 @@ pub fn prepare_prepass_textures(
                 .map(|t| ColorAttachment::new(t, None, Some(LegacyColor::BLACK))),
             deferred_lighting_pass_id: cached_deferred_lighting_pass_id_texture
                 .map(|t| ColorAttachment::new(t, None, Some(LegacyColor::BLACK))),
+            transparent_depth: cached_transparent_depth_texture
+                .map(|t| ColorAttachment::new(t, None, None)),
             size,
         });
     }
@@ -16,7 +16,7 @@ mod taa;
 pub mod tonemapping;
 pub mod upscaling;
 
-pub use skybox::Skybox;
+pub use skybox::{Skybox, SkyboxFog};
 
 /// Experimental features that are not yet finished. Please report any issues you encounter!
 pub mod experimental {
@@ -46,7 +46,10 @@ use crate::{
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
     msaa_writeback::MsaaWritebackPlugin,
-    prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
+    prepass::{
+        linear_depth::LinearDepthPlugin, DeferredPrepass, DepthPrepass, LinearDepthPrepass,
+        MotionVectorPrepass, NormalPrepass, TransparentDepthPrepass,
+    },
     tonemapping::TonemappingPlugin,
     upscaling::UpscalingPlugin,
 };
@@ -70,10 +73,13 @@ impl Plugin for CorePipelinePlugin {
             .register_type::<NormalPrepass>()
             .register_type::<MotionVectorPrepass>()
             .register_type::<DeferredPrepass>()
+            .register_type::<TransparentDepthPrepass>()
+            .register_type::<LinearDepthPrepass>()
             .add_plugins((
                 Core2dPlugin,
                 Core3dPlugin,
                 CopyDeferredLightingIdPlugin,
+                LinearDepthPlugin,
                 BlitPlugin,
                 MsaaWritebackPlugin,
                 TonemappingPlugin,
@@ -0,0 +1,282 @@
+//! Converts the depth written by the prepass into linear view space depth.
+//!
+//! See [`LinearDepthPrepass`] for how to enable it.
+
+use crate::{
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prepass::{DepthPrepass, LinearDepthPrepass, ViewPrepassTextures},
+};
+use bevy_app::prelude::*;
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::UVec2;
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        binding_types::{texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{CachedTexture, TextureCache},
+    view::{Msaa, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Render, RenderApp, RenderSet,
+};
+
+/// The format of [`ViewLinearDepthTexture`].
+pub const LINEAR_DEPTH_FORMAT: TextureFormat = TextureFormat::R32Float;
+
+pub const LINEAR_DEPTH_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8190436372175541953);
+
+pub struct LinearDepthPlugin;
+
+impl Plugin for LinearDepthPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LINEAR_DEPTH_SHADER_HANDLE,
+            "linear_depth.wgsl",
+            Shader::from_wgsl
+        );
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<LinearDepthPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_linear_depth_pipelines.in_set(RenderSet::Prepare),
+                    prepare_linear_depth_textures.in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<LinearDepthPipeline>();
+    }
+}
+
+/// Writes the linear depth of the prepass into [`ViewLinearDepthTexture`].
+#[derive(Default)]
+pub struct LinearDepthNode;
+
+impl ViewNode for LinearDepthNode {
+    type ViewQuery = (
+        &'static ViewPrepassTextures,
+        &'static ViewUniformOffset,
+        &'static ViewLinearDepthTexture,
+        &'static LinearDepthPipelineId,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_prepass_textures, view_uniform_offset, linear_depth_texture, pipeline_id): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let linear_depth_pipeline = world.resource::<LinearDepthPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let view_uniforms = world.resource::<ViewUniforms>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+        let Some(depth_view) = view_prepass_textures.depth_view() else {
+            return Ok(());
+        };
+        let Some(view_uniforms) = view_uniforms.uniforms.binding() else {
+            return Ok(());
+        };
+
+        let layout = if view_prepass_textures
+            .depth
+            .as_ref()
+            .is_some_and(|depth| depth.texture.texture.sample_count() > 1)
+        {
+            &linear_depth_pipeline.layout_multisampled
+        } else {
+            &linear_depth_pipeline.layout
+        };
+
+        let bind_group = render_context.render_device().create_bind_group(
+            "linear_depth_bind_group",
+            layout,
+            &BindGroupEntries::sequential((depth_view, view_uniforms)),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("linear_depth_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &linear_depth_texture.texture.default_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Default::default()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[view_uniform_offset.offset]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+pub struct LinearDepthPipeline {
+    layout: BindGroupLayout,
+    layout_multisampled: BindGroupLayout,
+}
+
+impl FromWorld for LinearDepthPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let layout = render_device.create_bind_group_layout(
+            "linear_depth_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (texture_depth_2d(), uniform_buffer::<ViewUniform>(true)),
+            ),
+        );
+        let layout_multisampled = render_device.create_bind_group_layout(
+            "linear_depth_multisampled_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_depth_2d_multisampled(),
+                    uniform_buffer::<ViewUniform>(true),
+                ),
+            ),
+        );
+
+        Self {
+            layout,
+            layout_multisampled,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct LinearDepthPipelineKey {
+    multisampled: bool,
+}
+
+impl SpecializedRenderPipeline for LinearDepthPipeline {
+    type Key = LinearDepthPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, shader_defs) = if key.multisampled {
+            (
+                self.layout_multisampled.clone(),
+                vec!["MULTISAMPLED".into()],
+            )
+        } else {
+            (self.layout.clone(), vec![])
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("linear_depth_pipeline".into()),
+            layout: vec![layout],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: LINEAR_DEPTH_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: LINEAR_DEPTH_FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct LinearDepthPipelineId(pub CachedRenderPipelineId);
+
+fn prepare_linear_depth_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<LinearDepthPipeline>>,
+    pipeline: Res<LinearDepthPipeline>,
+    msaa: Res<Msaa>,
+    views: Query<Entity, (With<LinearDepthPrepass>, With<DepthPrepass>)>,
+) {
+    for entity in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            LinearDepthPipelineKey {
+                multisampled: msaa.samples() > 1,
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(LinearDepthPipelineId(pipeline_id));
+    }
+}
+
+/// The linear view space depth of a view, written after the prepasses.
+///
+/// Exists only if [`LinearDepthPrepass`] and [`DepthPrepass`] are added to the camera.
+/// The texture uses [`LINEAR_DEPTH_FORMAT`] and can be bound as a texture in post processing shaders.
+#[derive(Component)]
+pub struct ViewLinearDepthTexture {
+    pub texture: CachedTexture,
+}
+
+fn prepare_linear_depth_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    views: Query<(Entity, &ExtractedCamera), (With<LinearDepthPrepass>, With<DepthPrepass>)>,
+) {
+    for (entity, camera) in &views {
+        if let Some(UVec2 {
+            x: width,
+            y: height,
+        }) = camera.physical_target_size
+        {
+            let texture_descriptor = TextureDescriptor {
+                label: Some("linear_depth_texture"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: LINEAR_DEPTH_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            };
+            let texture = texture_cache.get(&render_device, texture_descriptor);
+            commands
+                .entity(entity)
+                .insert(ViewLinearDepthTexture { texture });
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+
+#ifdef MULTISAMPLED
+@group(0) @binding(0) var depth_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(0) var depth_texture: texture_depth_2d;
+#endif
+@group(0) @binding(1) var<uniform> view: View;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) f32 {
+    // Only the first sample is used when the prepass depth is multisampled.
+    let depth = textureLoad(depth_texture, vec2<i32>(in.position.xy), 0);
+
+    // Unproject the NDC depth into view space. This works for both perspective and
+    // orthographic projections. View space looks down -Z, so negate to get a positive distance.
+    let view_position = view.inverse_projection * vec4(0.0, 0.0, depth, 1.0);
+    return -view_position.z / view_position.w;
+}
@@ -1,13 +1,16 @@
 //! Run a prepass before the main pass to generate depth, normals, and/or motion vectors textures, sometimes called a thin g-buffer.
 //! These textures are useful for various screen-space effects and reducing overdraw in the main pass.
 //!
-//! The prepass only runs for opaque meshes or meshes with an alpha mask. Transparent meshes are ignored.
+//! The prepass only runs for opaque meshes or meshes with an alpha mask. Transparent meshes are ignored,
+//! unless the camera has a [`TransparentDepthPrepass`], in which case transparent meshes whose material
+//! opts in record their depth into a separate texture.
 //!
 //! To enable the prepass, you need to add a prepass component to a [`crate::prelude::Camera3d`].
 //!
 //! [`DepthPrepass`]
 //! [`NormalPrepass`]
 //! [`MotionVectorPrepass`]
+//! [`LinearDepthPrepass`]
 //!
 //! The textures are automatically added to the default mesh view bindings. You can also get the raw textures
 //! by querying the [`ViewPrepassTextures`] component on any camera with a prepass component.
@@ -25,6 +28,7 @@
 //!
 //! Currently only works for 3D.
 
+pub mod linear_depth;
 pub mod node;
 
 use std::{cmp::Reverse, ops::Range};
@@ -56,6 +60,27 @@ pub struct NormalPrepass;
 #[derive(Component, Default, Reflect)]
 pub struct MotionVectorPrepass;
 
+/// If added to a [`crate::prelude::Camera3d`] along with a [`DepthPrepass`], then the depth of transparent
+/// meshes whose material opts in is recorded into [`ViewPrepassTextures::transparent_depth`].
+///
+/// The texture starts out as a copy of the opaque depth, so it holds the depth of the nearest surface,
+/// transparent or not. Comparing it with the opaque depth tells where a transparent surface lies in
+/// front of the opaque scene and how far in front it is, e.g. for refraction or distortion effects
+/// behind glass.
+#[derive(Component, Default, Reflect)]
+pub struct TransparentDepthPrepass;
+
+/// If added to a [`crate::prelude::Camera3d`] along with a [`DepthPrepass`], then the prepass depth is
+/// converted to linear view space depth and written to a
+/// [`ViewLinearDepthTexture`](linear_depth::ViewLinearDepthTexture).
+///
+/// Each texel holds the distance from the camera plane along the view direction, in world units,
+/// which is usually what post processing effects want instead of the non-linear depth buffer.
+/// Texels where nothing was drawn hold the far plane distance, which is infinite for the
+/// default perspective projection. With MSAA, only the first sample of each pixel is used.
+#[derive(Component, Default, Reflect)]
+pub struct LinearDepthPrepass;
+
 /// If added to a [`crate::prelude::Camera3d`] then deferred materials will be rendered to the deferred gbuffer texture and will be available to subsequent passes.
 /// Note the default deferred lighting plugin also requires `DepthPrepass` to work correctly.
 #[derive(Component, Default, Reflect)]
@@ -81,6 +106,9 @@ pub struct ViewPrepassTextures {
     /// A texture that specifies the deferred lighting pass id for a material.
     /// Exists only if [`DeferredPrepass`] is added to the `ViewTarget`
     pub deferred_lighting_pass_id: Option<ColorAttachment>,
+    /// The depth of the nearest surface, including transparent ones that opted in.
+    /// Exists only if [`TransparentDepthPrepass`] and [`DepthPrepass`] are added to the `ViewTarget`
+    pub transparent_depth: Option<ColorAttachment>,
     /// The size of the textures.
     pub size: Extent3d,
 }
@@ -103,6 +131,12 @@ impl ViewPrepassTextures {
     pub fn deferred_view(&self) -> Option<&TextureView> {
         self.deferred.as_ref().map(|t| &t.texture.default_view)
     }
+
+    pub fn transparent_depth_view(&self) -> Option<&TextureView> {
+        self.transparent_depth
+            .as_ref()
+            .map(|t| &t.texture.default_view)
+    }
 }
 
 /// Opaque phase of the 3D prepass.
@@ -237,3 +271,73 @@ impl CachedRenderPipelinePhaseItem for AlphaMask3dPrepass {
         self.pipeline_id
     }
 }
+
+/// Transparent phase of the 3D prepass.
+///
+/// Sorted front-to-back by the z-distance in front of the camera.
+///
+/// Used to record the depth of meshes with a transparent material that opts in, see
+/// [`TransparentDepthPrepass`].
+pub struct Transparent3dPrepass {
+    pub distance: f32,
+    pub entity: Entity,
+    pub pipeline_id: CachedRenderPipelineId,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub dynamic_offset: Option<NonMaxU32>,
+}
+
+impl PhaseItem for Transparent3dPrepass {
+    // NOTE: Values increase towards the camera. These items only write depth, so unlike the
+    // transparent main pass their order doesn't change the result. Front-to-back ordering, which
+    // means a descending sort, lets the fewest fragments pass the depth test.
+    type SortKey = Reverse<FloatOrd>;
+
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        Reverse(FloatOrd(self.distance))
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // Key negated to match reversed SortKey ordering
+        radsort::sort_by_key(items, |item| -item.distance);
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn dynamic_offset(&self) -> Option<NonMaxU32> {
+        self.dynamic_offset
+    }
+
+    #[inline]
+    fn dynamic_offset_mut(&mut self) -> &mut Option<NonMaxU32> {
+        &mut self.dynamic_offset
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Transparent3dPrepass {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline_id
+    }
+}
@@ -4,14 +4,19 @@ use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_phase::{RenderPhase, TrackedRenderPass},
-    render_resource::{CommandEncoderDescriptor, RenderPassDescriptor, StoreOp},
+    render_resource::{
+        CommandEncoderDescriptor, LoadOp, Operations, RenderPassDepthStencilAttachment,
+        RenderPassDescriptor, StoreOp,
+    },
     renderer::RenderContext,
     view::ViewDepthTexture,
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 
-use super::{AlphaMask3dPrepass, DeferredPrepass, Opaque3dPrepass, ViewPrepassTextures};
+use super::{
+    AlphaMask3dPrepass, DeferredPrepass, Opaque3dPrepass, Transparent3dPrepass, ViewPrepassTextures,
+};
 
 /// Render node used by the prepass.
 ///
@@ -121,3 +126,70 @@ impl ViewNode for PrepassNode {
         Ok(())
     }
 }
+
+/// Render node that records the depth of transparent meshes into
+/// [`ViewPrepassTextures::transparent_depth`].
+///
+/// By default, inserted after the other prepasses, once the opaque depth is complete.
+#[derive(Default)]
+pub struct TransparentDepthPrepassNode;
+
+impl ViewNode for TransparentDepthPrepassNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static RenderPhase<Transparent3dPrepass>,
+        &'static ViewDepthTexture,
+        &'static ViewPrepassTextures,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, transparent_prepass_phase, view_depth_texture, view_prepass_textures): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(transparent_depth_texture) = &view_prepass_textures.transparent_depth else {
+            return Ok(());
+        };
+
+        #[cfg(feature = "trace")]
+        let _transparent_depth_prepass_span = info_span!("transparent_depth_prepass").entered();
+
+        // Start from the opaque depth, so that transparent surfaces hidden behind opaque ones
+        // are rejected by the depth test.
+        render_context.command_encoder().copy_texture_to_texture(
+            view_depth_texture.texture.as_image_copy(),
+            transparent_depth_texture.texture.texture.as_image_copy(),
+            view_prepass_textures.size,
+        );
+
+        if transparent_prepass_phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let view_entity = graph.view_entity();
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("transparent_depth_prepass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: &transparent_depth_texture.texture.default_view,
+                depth_ops: Some(Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+        transparent_prepass_phase.render(&mut render_pass, world, view_entity);
+
+        Ok(())
+    }
+}
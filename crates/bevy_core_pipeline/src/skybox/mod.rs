@@ -6,8 +6,10 @@ use bevy_ecs::{
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut, Resource},
 };
+use bevy_math::Vec4;
 use bevy_render::{
     camera::Exposure,
+    color::LegacyColor,
     extract_component::{
         ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
         UniformComponentPlugin,
@@ -67,7 +69,8 @@ impl Plugin for SkyboxPlugin {
 /// Adds a skybox to a 3D camera, based on a cubemap texture.
 ///
 /// Note that this component does not (currently) affect the scene's lighting.
-/// To do so, use `EnvironmentMapLight` alongside this component.
+/// To do so, use `EnvironmentMapLight` alongside this component, or
+/// `SkyboxAmbientLight` to replace the ambient light with the light of the skybox.
 ///
 /// See also <https://en.wikipedia.org/wiki/Skybox_(video_games)>.
 #[derive(Component, Clone)]
@@ -79,12 +82,36 @@ pub struct Skybox {
     pub brightness: f32,
 }
 
+/// Blends the [`Skybox`] of a camera towards a fog color near the horizon, so
+/// that distance fog fades into the sky instead of ending in a hard line.
+///
+/// The skybox is fully covered at and below the horizon when `blend` is `1.0`,
+/// and the fog fades out towards the zenith.
+///
+/// `bevy_pbr` keeps this component in sync with the camera's `FogSettings`
+/// when `FogSettings::affects_skybox` is set.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SkyboxFog {
+    /// The color the skybox is blended towards.
+    ///
+    /// The alpha channel scales the blend, like it does for the fog itself.
+    pub color: LegacyColor,
+    /// How strongly the fog covers the skybox, from `0.0` (not at all) to `1.0`.
+    pub blend: f32,
+}
+
 impl ExtractComponent for Skybox {
-    type QueryData = (&'static Self, Option<&'static Exposure>);
+    type QueryData = (
+        &'static Self,
+        Option<&'static Exposure>,
+        Option<&'static SkyboxFog>,
+    );
     type QueryFilter = ();
     type Out = (Self, SkyboxUniforms);
 
-    fn extract_component((skybox, exposure): QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+    fn extract_component(
+        (skybox, exposure, fog): QueryItem<'_, Self::QueryData>,
+    ) -> Option<Self::Out> {
         let exposure = exposure
             .map(|e| e.exposure())
             .unwrap_or_else(|| Exposure::default().exposure());
@@ -92,13 +119,9 @@ impl ExtractComponent for Skybox {
         Some((
             skybox.clone(),
             SkyboxUniforms {
+                fog_color: fog.map_or(Vec4::ZERO, |fog| fog.color.as_linear_rgba_f32().into()),
                 brightness: skybox.brightness * exposure,
-                #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-                _wasm_padding_8b: 0,
-                #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-                _wasm_padding_12b: 0,
-                #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-                _wasm_padding_16b: 0,
+                fog_blend: fog.map_or(0.0, |fog| fog.blend),
             },
         ))
     }
@@ -107,13 +130,9 @@ impl ExtractComponent for Skybox {
 // TODO: Replace with a push constant once WebGPU gets support for that
 #[derive(Component, ShaderType, Clone)]
 pub struct SkyboxUniforms {
+    fog_color: Vec4,
     brightness: f32,
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    _wasm_padding_8b: u32,
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    _wasm_padding_12b: u32,
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    _wasm_padding_16b: u32,
+    fog_blend: f32,
 }
 
 #[derive(Resource)]
@@ -2,12 +2,9 @@
 #import bevy_pbr::utils::coords_to_viewport_uv
 
 struct SkyboxUniforms {
+	fog_color: vec4<f32>,
 	brightness: f32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-	_wasm_padding_8b: u32,
-	_wasm_padding_12b: u32,
-	_wasm_padding_16b: u32,
-#endif
+	fog_blend: f32,
 }
 
 @group(0) @binding(0) var skybox: texture_cube<f32>;
@@ -72,5 +69,9 @@ fn skybox_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let ray_direction = coords_to_ray_direction(in.position.xy, view.viewport);
 
     // Cube maps are left-handed so we negate the z coordinate.
-    return textureSample(skybox, skybox_sampler, ray_direction * vec3(1.0, 1.0, -1.0)) * uniforms.brightness;
+    let color = textureSample(skybox, skybox_sampler, ray_direction * vec3(1.0, 1.0, -1.0)) * uniforms.brightness;
+
+    // Fully fogged at and below the horizon, fading out towards the zenith.
+    let fog_amount = uniforms.fog_blend * uniforms.fog_color.a * (1.0 - max(ray_direction.y, 0.0));
+    return vec4(mix(color.rgb, uniforms.fog_color.rgb, fog_amount), color.a);
 }
@@ -1,13 +1,13 @@
 //! Additional [`Gizmos`] Functions -- Arcs
 //!
-//! Includes the implementation of [`Gizmos::arc_2d`],
-//! and assorted support items.
+//! Includes the implementation of [`Gizmos::arc_2d`], [`Gizmos::arc_wedge_2d`],
+//! [`Gizmos::gauge_arc_2d`] and [`Gizmos::capsule_2d`], and assorted support items.
 
 use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
 use crate::prelude::{GizmoConfigGroup, Gizmos};
-use bevy_math::{Quat, Vec2, Vec3};
+use bevy_math::{Mat2, Quat, Vec2, Vec3};
 use bevy_render::color::LegacyColor;
-use std::f32::consts::TAU;
+use std::f32::consts::{PI, TAU};
 
 // === 2D ===
 
@@ -96,6 +96,364 @@ impl<T: GizmoConfigGroup> Drop for Arc2dBuilder<'_, '_, '_, T> {
     }
 }
 
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a capsule in 2D, made of two semicircular caps joined by two straight sides.
+    ///
+    /// This should be called for each frame the capsule needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the capsule.
+    /// - `angle` sets the counterclockwise rotation of the capsule in radians. With an angle of
+    /// `0.` the straight sides are parallel to `Vec2::Y`.
+    /// - `radius` sets the radius of the caps, and thus half the width of the capsule.
+    /// - `length` sets the distance between the centers of the two caps, i.e. the length of
+    /// the straight sides.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::PI;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.capsule_2d(Vec2::ZERO, 0., 1., 2., LegacyColor::GREEN);
+    ///
+    ///     // Each cap has 16 line-segments by default.
+    ///     // You may want to increase this for larger capsules.
+    ///     gizmos
+    ///         .capsule_2d(Vec2::ZERO, PI / 4., 5., 10., LegacyColor::RED)
+    ///         .segments(32);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn capsule_2d(
+        &mut self,
+        position: Vec2,
+        angle: f32,
+        radius: f32,
+        length: f32,
+        color: LegacyColor,
+    ) -> Capsule2dBuilder<'_, 'w, 's, T> {
+        Capsule2dBuilder {
+            gizmos: self,
+            position,
+            angle,
+            radius,
+            half_length: length / 2.,
+            color,
+            segments: segments_from_angle(PI),
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::capsule_2d`].
+pub struct Capsule2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    angle: f32,
+    radius: f32,
+    half_length: f32,
+    color: LegacyColor,
+    segments: usize,
+}
+
+impl<T: GizmoConfigGroup> Capsule2dBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments for each cap of this capsule.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Capsule2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let rotation = Mat2::from_angle(self.angle);
+        let cap_offset = Vec2::Y * self.half_length;
+
+        // Both caps are drawn clockwise, so the last vertex of each cap is connected to the
+        // first vertex of the other one by a straight side. Repeating the very first vertex
+        // closes the outline.
+        let top_cap = arc_2d_inner(0., PI, self.radius, self.segments).map(|p| p + cap_offset);
+        let bottom_cap = arc_2d_inner(PI, PI, self.radius, self.segments).map(|p| p - cap_offset);
+        let first = Vec2::new(-self.radius, self.half_length);
+
+        let positions = top_cap
+            .chain(bottom_cap)
+            .chain(std::iter::once(first))
+            .map(|p| rotation * p + self.position);
+        self.gizmos.linestrip_2d(positions, self.color);
+    }
+}
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a thick arc in 2D, i.e. the outline of the part of a ring between two angles.
+    ///
+    /// The wedge is made of an outer and an inner arc, joined by straight lines at both ends.
+    /// This is the ring counterpart to a pie slice and is useful for radial progress or cooldown
+    /// indicators.
+    ///
+    /// This should be called for each frame the wedge needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the ring.
+    /// - `inner_radius` and `outer_radius` set the radii of the inner and outer arcs.
+    /// - `start_angle` sets the clockwise angle in radians between `Vec2::Y` and the vector from
+    ///   `position` to the start of the wedge.
+    /// - `sweep_angle` sets how far the wedge extends clockwise from `start_angle`, in radians.
+    ///   Negative values extend it counterclockwise. A sweep of zero draws nothing, and a sweep of
+    ///   `TAU` or more draws a full annulus made of two circles.
+    ///
+    /// # Builder methods
+    /// - The number of line-segments of each arc can be adjusted with the `.segments(...)` method.
+    /// - `.filled()` additionally draws the triangulation of the wedge, which shades its area with
+    ///   a hatch of lines.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::TAU;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let progress = 0.3;
+    ///     gizmos.arc_wedge_2d(Vec2::ZERO, 4., 5., 0., TAU, LegacyColor::GRAY);
+    ///     gizmos
+    ///         .arc_wedge_2d(Vec2::ZERO, 4., 5., 0., progress * TAU, LegacyColor::GREEN)
+    ///         .filled();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn arc_wedge_2d(
+        &mut self,
+        position: Vec2,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        color: LegacyColor,
+    ) -> ArcWedge2dBuilder<'_, 'w, 's, T> {
+        ArcWedge2dBuilder {
+            gizmos: self,
+            position,
+            inner_radius,
+            outer_radius,
+            start_angle,
+            sweep_angle,
+            color,
+            segments: None,
+            filled: false,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::arc_wedge_2d`].
+pub struct ArcWedge2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+    color: LegacyColor,
+    segments: Option<usize>,
+    filled: bool,
+}
+
+impl<T: GizmoConfigGroup> ArcWedge2dBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments for each arc of this wedge.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments.replace(segments);
+        self
+    }
+
+    /// Also draw the triangulation of the wedge, shading its area.
+    pub fn filled(mut self) -> Self {
+        self.filled = true;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for ArcWedge2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled || self.sweep_angle == 0. {
+            return;
+        }
+
+        let sweep_angle = self.sweep_angle.clamp(-TAU, TAU);
+        let full_ring = sweep_angle.abs() == TAU;
+        let segments = self
+            .segments
+            .unwrap_or_else(|| segments_from_angle(sweep_angle))
+            .max(1);
+
+        let direction_angle = self.start_angle + sweep_angle / 2.;
+        let arc = |radius| {
+            arc_2d_inner(direction_angle, sweep_angle, radius, segments)
+                .map(|p| p + self.position)
+                .collect::<Vec<_>>()
+        };
+        let outer = arc(self.outer_radius);
+        let inner = arc(self.inner_radius);
+
+        if self.filled {
+            // Split each segment into two triangles, sharing the spoke between the inner and
+            // outer vertex. The spokes at both ends are part of the outline.
+            for i in 0..segments {
+                if i > 0 {
+                    self.gizmos.line_2d(inner[i], outer[i], self.color);
+                }
+                self.gizmos.line_2d(inner[i], outer[i + 1], self.color);
+            }
+        }
+
+        if full_ring {
+            self.gizmos.linestrip_2d(outer, self.color);
+            self.gizmos.linestrip_2d(inner, self.color);
+        } else {
+            // Walk along the outer arc, then back along the inner arc, and close the outline.
+            let first = outer[0];
+            let positions = outer
+                .into_iter()
+                .chain(inner.into_iter().rev())
+                .chain(std::iter::once(first));
+            self.gizmos.linestrip_2d(positions, self.color);
+        }
+    }
+}
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw the arc of a gauge in 2D, sweeping from `start_angle` towards `end_angle` in
+    /// proportion to `value`.
+    ///
+    /// A `value` of `0.` draws nothing and a `value` of `1.` draws the whole arc from
+    /// `start_angle` to `end_angle`. Values outside of that range are clamped.
+    ///
+    /// This should be called for each frame the gauge needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the gauge.
+    /// - `radius` sets the distance from `position` to the arc.
+    /// - `start_angle` and `end_angle` set the clockwise angles in radians between `Vec2::Y` and
+    ///   the vectors from `position` to both ends of the full gauge. An `end_angle` smaller than
+    ///   `start_angle` makes the gauge sweep counterclockwise.
+    /// - `value` sets the filled fraction of the gauge.
+    ///
+    /// # Builder methods
+    /// - The number of line-segments of the full arc can be adjusted with the `.segments(...)`
+    ///   method. The swept arc uses the matching fraction of them.
+    /// - `.background(color)` also draws the full arc of the gauge, behind the swept part.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::PI;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let speed = 0.6;
+    ///     gizmos
+    ///         .gauge_arc_2d(Vec2::ZERO, 5., -0.75 * PI, 0.75 * PI, speed, LegacyColor::ORANGE)
+    ///         .background(LegacyColor::DARK_GRAY);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn gauge_arc_2d(
+        &mut self,
+        position: Vec2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        value: f32,
+        color: LegacyColor,
+    ) -> GaugeArc2dBuilder<'_, 'w, 's, T> {
+        GaugeArc2dBuilder {
+            gizmos: self,
+            position,
+            radius,
+            start_angle,
+            end_angle,
+            value,
+            color,
+            background: None,
+            segments: None,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::gauge_arc_2d`].
+pub struct GaugeArc2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    value: f32,
+    color: LegacyColor,
+    background: Option<LegacyColor>,
+    segments: Option<usize>,
+}
+
+impl<T: GizmoConfigGroup> GaugeArc2dBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments of the full arc of this gauge.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments.replace(segments);
+        self
+    }
+
+    /// Also draw the full arc of the gauge in `color`, e.g. a dim version of the gauge color.
+    pub fn background(mut self, color: LegacyColor) -> Self {
+        self.background = Some(color);
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for GaugeArc2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let full_angle = self.end_angle - self.start_angle;
+        let full_segments = self
+            .segments
+            .unwrap_or_else(|| segments_from_angle(full_angle))
+            .max(1);
+
+        if let Some(background) = self.background {
+            let positions = arc_2d_inner(
+                self.start_angle + full_angle / 2.,
+                full_angle,
+                self.radius,
+                full_segments,
+            )
+            .map(|p| p + self.position);
+            self.gizmos.linestrip_2d(positions, background);
+        }
+
+        let value = self.value.clamp(0., 1.);
+        if value == 0. || value.is_nan() {
+            return;
+        }
+        let sweep_angle = full_angle * value;
+        let segments = ((full_segments as f32 * value).ceil() as usize).max(1);
+        let positions = arc_2d_inner(
+            self.start_angle + sweep_angle / 2.,
+            sweep_angle,
+            self.radius,
+            segments,
+        )
+        .map(|p| p + self.position);
+        self.gizmos.linestrip_2d(positions, self.color);
+    }
+}
+
 fn arc_2d_inner(
     direction_angle: f32,
     arc_angle: f32,
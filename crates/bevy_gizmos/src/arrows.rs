@@ -35,6 +35,26 @@ impl<T: GizmoConfigGroup> ArrowBuilder<'_, '_, '_, T> {
     pub fn with_tip_length(&mut self, length: f32) {
         self.tip_length = length;
     }
+
+    /// Set the length of the tips to `length`, like [`with_tip_length`](Self::with_tip_length),
+    /// but can be chained with the other builder methods.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .arrow_2d(Vec2::ZERO, Vec2::new(10., 0.), LegacyColor::GREEN)
+    ///         .tip_length(0.5);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn tip_length(mut self, length: f32) -> Self {
+        self.tip_length = length;
+        self
+    }
 }
 
 impl<T: GizmoConfigGroup> Drop for ArrowBuilder<'_, '_, '_, T> {
@@ -47,7 +67,10 @@ impl<T: GizmoConfigGroup> Drop for ArrowBuilder<'_, '_, '_, T> {
         self.gizmos.line(self.start, self.end, self.color);
         // now the hard part is to draw the head in a sensible way
         // put us in a coordinate system where the arrow is pointing towards +x and ends at the origin
-        let pointing = (self.end - self.start).normalize();
+        let Some(pointing) = (self.end - self.start).try_normalize() else {
+            // a zero length arrow has no direction to point the tips in
+            return;
+        };
         let rotation = Quat::from_rotation_arc(Vec3::X, pointing);
         let tips = [
             Vec3::new(-1., 1., 0.),
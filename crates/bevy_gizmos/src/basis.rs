@@ -0,0 +1,73 @@
+//! Additional [`Gizmos`] Functions -- Basis
+//!
+//! Includes the implementation of [`Gizmos::basis`].
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_math::{Quat, Vec3};
+use bevy_render::color::LegacyColor;
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a coordinate basis: one line per axis, from `position` along the X, Y and Z axes of
+    /// `rotation`, each scaled by the matching component of `scale`.
+    ///
+    /// A small arc goes from the X axis to the Y axis, ending in a tip pointing at the Y axis.
+    /// Curling the fingers of your right hand along the arc, your thumb points along Z for a
+    /// right-handed basis and away from it for a left-handed one. A negative component of `scale`
+    /// flips its axis, so a tangent basis can be drawn with the bitangent sign applied to Y.
+    ///
+    /// This should be called for each frame the basis needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the origin of the basis.
+    /// - `rotation` orients the basis.
+    /// - `scale` sets the length of each axis, and flips it if negative.
+    /// - `colors` sets the colors of the X, Y and Z axes. The arc uses the color of the Z axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.basis(
+    ///         Vec3::ZERO,
+    ///         Quat::IDENTITY,
+    ///         Vec3::ONE,
+    ///         [LegacyColor::RED, LegacyColor::GREEN, LegacyColor::BLUE],
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn basis(&mut self, position: Vec3, rotation: Quat, scale: Vec3, colors: [LegacyColor; 3]) {
+        if !self.enabled {
+            return;
+        }
+
+        let axes = [
+            rotation * Vec3::X * scale.x,
+            rotation * Vec3::Y * scale.y,
+            rotation * Vec3::Z * scale.z,
+        ];
+        for (axis, color) in axes.into_iter().zip(colors) {
+            self.line(position, position + axis, color);
+        }
+
+        // The handedness arc, at a fraction of the shortest of the X and Y axes.
+        let radius = 0.25 * axes[0].length().min(axes[1].length());
+        let x_dir = axes[0].normalize_or_zero();
+        let y_dir = axes[1].normalize_or_zero();
+        if radius <= 0.0 || x_dir == Vec3::ZERO || y_dir == Vec3::ZERO {
+            return;
+        }
+        let end = position + y_dir * radius;
+        self.short_arc_3d_between(position, position + x_dir * radius, end, colors[2]);
+
+        // At the end of the arc, its direction of travel is along -X.
+        let tip_length = 0.3 * radius;
+        let back = x_dir * 0.5_f32.sqrt();
+        let side = y_dir * 0.5_f32.sqrt();
+        for tip in [back + side, back - side] {
+            self.line(end, end + tip * tip_length, colors[2]);
+        }
+    }
+}
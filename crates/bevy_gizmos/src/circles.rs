@@ -1,7 +1,49 @@
 //! Additional [`Gizmos`] Functions -- Circles
 //!
-//! Includes the implementation of [`Gizmos::circle`] and [`Gizmos::circle_2d`],
-//! and assorted support items.
+//! Includes the implementation of [`Gizmos::circle`], [`Gizmos::circle_2d`],
+//! [`Gizmos::circle_arc`], [`Gizmos::circle_screen`], [`Gizmos::range_rings_2d`] and
+//! [`Gizmos::ellipse_from_foci`], and assorted support items.
+//!
+//! Like every other [`Gizmos`] method, circles are drawn into the [`GizmoConfigGroup`] of the
+//! [`Gizmos`] they were called on, and respect that group's [`GizmoConfig`](crate::config::GizmoConfig).
+//! A circle drawn into a disabled group is discarded.
+//!
+//! # Custom config groups
+//!
+//! Circles can be kept in their own group, so they can be toggled and configured independently
+//! of other gizmos:
+//!
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_ecs::system::ResMut;
+//! # use bevy_gizmos::prelude::*;
+//! # use bevy_math::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! # use bevy_render::prelude::*;
+//! // Define a config group. It may hold additional settings of its own.
+//! #[derive(Default, Reflect, GizmoConfigGroup)]
+//! struct PhysicsGizmos;
+//!
+//! // Draw circles into that group by naming it in the `Gizmos` parameter.
+//! fn draw_colliders(mut gizmos: Gizmos<PhysicsGizmos>) {
+//!     gizmos.circle(Vec3::ZERO, Direction3d::Y, 1., LegacyColor::GREEN);
+//!     gizmos.circle_2d(Vec2::ZERO, 2., LegacyColor::RED).segments(64);
+//! }
+//!
+//! // Toggle the group, or change its line width, without affecting other gizmos.
+//! fn toggle_physics_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+//!     let (config, _) = config_store.config_mut::<PhysicsGizmos>();
+//!     config.enabled = !config.enabled;
+//!     config.line_width = 4.;
+//! }
+//! # bevy_ecs::system::assert_is_system(draw_colliders);
+//! # bevy_ecs::system::assert_is_system(toggle_physics_gizmos);
+//!
+//! // The group must be registered before it can be used.
+//! # fn build(app: &mut App) {
+//! app.init_gizmo_group::<PhysicsGizmos>();
+//! # }
+//! ```
 
 use crate::prelude::{GizmoConfigGroup, Gizmos};
 use bevy_math::Mat2;
@@ -12,13 +54,52 @@ use std::f32::consts::TAU;
 pub(crate) const DEFAULT_CIRCLE_SEGMENTS: usize = 32;
 
 fn ellipse_inner(half_size: Vec2, segments: usize) -> impl Iterator<Item = Vec2> {
+    ellipse_arc_inner(half_size, 0., TAU, segments)
+}
+
+/// Returns the points of the part of an ellipse starting at `start_angle` and spanning
+/// `sweep_angle`, with the angles measured clockwise from `Vec2::Y`.
+fn ellipse_arc_inner(
+    half_size: Vec2,
+    start_angle: f32,
+    sweep_angle: f32,
+    segments: usize,
+) -> impl Iterator<Item = Vec2> {
     (0..segments + 1).map(move |i| {
-        let angle = i as f32 * TAU / segments as f32;
+        let angle = start_angle + i as f32 * sweep_angle / segments as f32;
         let (x, y) = angle.sin_cos();
         Vec2::new(x, y) * half_size
     })
 }
 
+/// Tick marks drawn around an ellipse, see [`EllipseBuilder::ticks`].
+#[derive(Clone, Copy, Default)]
+struct EllipseTicks {
+    count: usize,
+    length: f32,
+    major_every: usize,
+    major_length: f32,
+}
+
+impl EllipseTicks {
+    /// Returns the start and end of each tick, relative to the center of the ellipse.
+    ///
+    /// Ticks start at the same angles as the outline's vertices and point towards the center.
+    fn lines(self, half_size: Vec2) -> impl Iterator<Item = (Vec2, Vec2)> {
+        (0..self.count).map(move |i| {
+            let angle = i as f32 * TAU / self.count as f32;
+            let (x, y) = angle.sin_cos();
+            let outer = Vec2::new(x, y) * half_size;
+            let length = if self.major_every > 0 && i % self.major_every == 0 {
+                self.major_length
+            } else {
+                self.length
+            };
+            (outer, outer - outer.normalize_or_zero() * length)
+        })
+    }
+}
+
 impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     /// Draw an ellipse in 3D at `position` with the flat side facing `normal`.
     ///
@@ -48,13 +129,78 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         half_size: Vec2,
         color: LegacyColor,
     ) -> EllipseBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
         EllipseBuilder {
             gizmos: self,
             position,
             rotation,
             half_size,
             color,
-            segments: DEFAULT_CIRCLE_SEGMENTS,
+            segments,
+            closed: true,
+            dashes: None,
+            z_bias: 0.,
+            ticks: EllipseTicks::default(),
+        }
+    }
+
+    /// Draw an ellipse in 3D from its two focal points and its semi-major axis, as is common
+    /// when visualizing orbits.
+    ///
+    /// The major axis runs through `focus_a` and `focus_b`, and the ellipse lies in the plane
+    /// obtained by rotating the XY plane so that its X axis lines up with the major axis. Use
+    /// [`Gizmos::ellipse`] directly to draw it in a specific plane.
+    ///
+    /// If `semi_major` is smaller than half the distance between the foci, the ellipse
+    /// degenerates into the line segment along the major axis.
+    ///
+    /// This should be called for each frame the ellipse needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // An orbit around a body at the origin, with an eccentricity of 0.5.
+    ///     gizmos.ellipse_from_foci(Vec3::ZERO, Vec3::new(4., 0., 0.), 4., LegacyColor::GREEN);
+    ///
+    ///     gizmos
+    ///         .ellipse_from_foci(Vec3::ZERO, Vec3::new(0., 2., 2.), 10., LegacyColor::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn ellipse_from_foci(
+        &mut self,
+        focus_a: Vec3,
+        focus_b: Vec3,
+        semi_major: f32,
+        color: LegacyColor,
+    ) -> EllipseBuilder<'_, 'w, 's, T> {
+        let focal_axis = focus_b - focus_a;
+        // The distance from the center to either focus.
+        let linear_eccentricity = focal_axis.length() * 0.5;
+        let semi_minor = (semi_major * semi_major - linear_eccentricity * linear_eccentricity)
+            .max(0.)
+            .sqrt();
+        let rotation = focal_axis
+            .try_normalize()
+            .map_or(Quat::IDENTITY, |axis| Quat::from_rotation_arc(Vec3::X, axis));
+
+        let segments = self.config.circle_segments;
+        EllipseBuilder {
+            gizmos: self,
+            position: (focus_a + focus_b) * 0.5,
+            rotation,
+            half_size: Vec2::new(semi_major, semi_minor),
+            color,
+            segments,
+            closed: true,
+            dashes: None,
+            z_bias: 0.,
+            ticks: EllipseTicks::default(),
         }
     }
 
@@ -86,13 +232,17 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         half_size: Vec2,
         color: LegacyColor,
     ) -> Ellipse2dBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
         Ellipse2dBuilder {
             gizmos: self,
             position,
             rotation: Mat2::from_angle(angle),
             half_size,
             color,
-            segments: DEFAULT_CIRCLE_SEGMENTS,
+            segments,
+            closed: true,
+            dashes: None,
+            ticks: EllipseTicks::default(),
         }
     }
 
@@ -113,6 +263,11 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     ///     gizmos
     ///         .circle(Vec3::ZERO, Direction3d::Z, 5., LegacyColor::RED)
     ///         .segments(64);
+    ///
+    ///     // A dashed circle, to tell it apart from overlapping ones.
+    ///     gizmos
+    ///         .circle(Vec3::ZERO, Direction3d::Z, 5., LegacyColor::BLUE)
+    ///         .dashed(0.5, 0.25);
     /// }
     /// # bevy_ecs::system::assert_is_system(system);
     /// ```
@@ -124,13 +279,66 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         radius: f32,
         color: LegacyColor,
     ) -> EllipseBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
         EllipseBuilder {
             gizmos: self,
             position,
             rotation: Quat::from_rotation_arc(Vec3::Z, *normal),
             half_size: Vec2::splat(radius),
             color,
-            segments: DEFAULT_CIRCLE_SEGMENTS,
+            segments,
+            closed: true,
+            dashes: None,
+            z_bias: 0.,
+            ticks: EllipseTicks::default(),
+        }
+    }
+
+    /// Draw the part of a circle in 3D at `position`, with the flat side facing `normal`, that
+    /// starts at `start_angle` and spans `sweep_angle`, e.g. for the outline of a field of view
+    /// or a turning radius.
+    ///
+    /// Angles are in radians and measured like the points of [`Gizmos::circle`], starting from
+    /// where that circle starts. A negative `sweep_angle` draws the arc in the opposite
+    /// direction, and one beyond a full turn draws the full circle.
+    ///
+    /// This should be called for each frame the arc needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::FRAC_PI_2;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.circle_arc(Vec3::ZERO, Direction3d::Y, 1., 0., FRAC_PI_2, LegacyColor::GREEN);
+    ///
+    ///     // By default, arcs have as many segments as the same part of a circle.
+    ///     gizmos
+    ///         .circle_arc(Vec3::ZERO, Direction3d::Y, 5., -FRAC_PI_2, -FRAC_PI_2, LegacyColor::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn circle_arc(
+        &mut self,
+        position: Vec3,
+        normal: Direction3d,
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        color: LegacyColor,
+    ) -> CircleArcBuilder<'_, 'w, 's, T> {
+        CircleArcBuilder {
+            gizmos: self,
+            position,
+            rotation: Quat::from_rotation_arc(Vec3::Z, *normal),
+            radius,
+            start_angle,
+            sweep_angle: sweep_angle.clamp(-TAU, TAU),
+            color,
+            segments: None,
         }
     }
 
@@ -151,6 +359,12 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     ///     gizmos
     ///         .circle_2d(Vec2::ZERO, 5., LegacyColor::RED)
     ///         .segments(64);
+    ///
+    ///     // A dial with a tick every 30 degrees and a longer one every 90 degrees.
+    ///     gizmos
+    ///         .circle_2d(Vec2::ZERO, 10., LegacyColor::WHITE)
+    ///         .ticks(12, 0.5)
+    ///         .major_every(3, 1.5);
     /// }
     /// # bevy_ecs::system::assert_is_system(system);
     /// ```
@@ -161,13 +375,100 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         radius: f32,
         color: LegacyColor,
     ) -> Ellipse2dBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
         Ellipse2dBuilder {
             gizmos: self,
             position,
             rotation: Mat2::IDENTITY,
             half_size: Vec2::splat(radius),
             color,
-            segments: DEFAULT_CIRCLE_SEGMENTS,
+            segments,
+            closed: true,
+            dashes: None,
+            ticks: EllipseTicks::default(),
+        }
+    }
+
+    /// Draw a circle in screen space, independently of the camera.
+    ///
+    /// `position` and `radius` are in physical pixels, with `position` relative to the
+    /// top-left corner of the viewport. The circle is drawn on top of everything else.
+    ///
+    /// This should be called for each frame the circle needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.circle_screen(Vec2::new(100., 100.), 20., LegacyColor::GREEN);
+    ///
+    ///     // Circles have 32 line-segments by default.
+    ///     // You may want to increase this for larger circles.
+    ///     gizmos
+    ///         .circle_screen(Vec2::new(300., 200.), 150., LegacyColor::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn circle_screen(
+        &mut self,
+        position: Vec2,
+        radius: f32,
+        color: LegacyColor,
+    ) -> CircleScreenBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
+        CircleScreenBuilder {
+            gizmos: self,
+            position,
+            radius,
+            color,
+            segments,
+            closed: true,
+        }
+    }
+
+    /// Draw concentric circles in 2D around `position`, one for each of the `radii`, e.g. to
+    /// show the ranges of a unit.
+    ///
+    /// Use [`RangeRings2dBuilder::positions_at_bearing`] to get points on the rings to attach
+    /// labels to.
+    ///
+    /// This should be called for each frame the rings need to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.range_rings_2d(Vec2::ZERO, &[10., 20., 30.], LegacyColor::GREEN);
+    ///
+    ///     // The positions where the rings cross the bearing pointing up and to the right.
+    ///     let label_positions = gizmos
+    ///         .range_rings_2d(Vec2::ZERO, &[50., 100.], LegacyColor::RED)
+    ///         .segments(64)
+    ///         .positions_at_bearing(std::f32::consts::FRAC_PI_4);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn range_rings_2d<'a>(
+        &'a mut self,
+        position: Vec2,
+        radii: &'a [f32],
+        color: LegacyColor,
+    ) -> RangeRings2dBuilder<'a, 'w, 's, T> {
+        let segments = self.config.circle_segments;
+        RangeRings2dBuilder {
+            gizmos: self,
+            position,
+            radii,
+            color,
+            segments,
+            closed: true,
         }
     }
 }
@@ -180,6 +481,10 @@ pub struct EllipseBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
     half_size: Vec2,
     color: LegacyColor,
     segments: usize,
+    closed: bool,
+    dashes: Option<(f32, f32)>,
+    z_bias: f32,
+    ticks: EllipseTicks,
 }
 
 impl<T: GizmoConfigGroup> EllipseBuilder<'_, '_, '_, T> {
@@ -188,6 +493,58 @@ impl<T: GizmoConfigGroup> EllipseBuilder<'_, '_, '_, T> {
         self.segments = segments;
         self
     }
+
+    /// Whether to join the seam where this ellipse starts and ends like its other points, when
+    /// [`GizmoConfig::line_joints`](crate::config::GizmoConfig::line_joints) is set.
+    ///
+    /// Defaults to `true`. Has no effect without line joints.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Draw this ellipse as a dashed line, alternating between `dash_len` long dashes and
+    /// `gap_len` long gaps, both in world units.
+    ///
+    /// The dashes run continuously around the ellipse. A `gap_len` of zero draws a solid line.
+    pub fn dashed(mut self, dash_len: f32, gap_len: f32) -> Self {
+        self.dashes = Some((dash_len, gap_len));
+        self
+    }
+
+    /// Bias the depth of this ellipse by `z_bias`, added to
+    /// [`GizmoConfig::depth_bias`](crate::config::GizmoConfig::depth_bias) and on the same scale.
+    ///
+    /// Use a small negative value to pull an ellipse drawn on a surface, like a circle on the
+    /// ground, towards the camera so it doesn't z-fight with that surface, without biasing the
+    /// other gizmos of the group. The ellipse itself isn't moved, so it keeps its position from
+    /// every side and at every distance.
+    ///
+    /// Defaults to `0.0`.
+    pub fn z_bias(mut self, z_bias: f32) -> Self {
+        self.z_bias = z_bias;
+        self
+    }
+
+    /// Draw `count` tick marks of the given `length` at evenly spaced angles around this
+    /// ellipse, e.g. for dials, gauges or compasses.
+    ///
+    /// The first tick is at the top of the ellipse and ticks point towards its center. No ticks
+    /// are drawn unless this is called.
+    pub fn ticks(mut self, count: usize, length: f32) -> Self {
+        self.ticks.count = count;
+        self.ticks.length = length;
+        self
+    }
+
+    /// Make every `n`th tick, starting with the first, a major tick of the given `length`.
+    ///
+    /// Only has an effect together with [`ticks`](Self::ticks). Pass `0` to disable major ticks.
+    pub fn major_every(mut self, n: usize, length: f32) -> Self {
+        self.ticks.major_every = n;
+        self.ticks.major_length = length;
+        self
+    }
 }
 
 impl<T: GizmoConfigGroup> Drop for EllipseBuilder<'_, '_, '_, T> {
@@ -196,10 +553,75 @@ impl<T: GizmoConfigGroup> Drop for EllipseBuilder<'_, '_, '_, T> {
             return;
         }
 
-        let positions = ellipse_inner(self.half_size, self.segments)
-            .map(|vec2| self.rotation * vec2.extend(0.))
-            .map(|vec3| vec3 + self.position);
-        self.gizmos.linestrip(positions, self.color);
+        self.gizmos.with_depth_bias(self.z_bias, |gizmos| {
+            let positions = ellipse_inner(self.half_size, self.segments)
+                .map(|vec2| self.rotation * vec2.extend(0.))
+                .map(|vec3| vec3 + self.position);
+            if let Some((dash_len, gap_len)) = self.dashes {
+                gizmos.linestrip_dashed(positions, dash_len, gap_len, self.color);
+            } else if self.closed {
+                gizmos.linestrip_closed(positions, self.color, false);
+            } else {
+                gizmos.linestrip(positions, self.color);
+            }
+
+            for (start, end) in self.ticks.lines(self.half_size) {
+                gizmos.line(
+                    self.position + self.rotation * start.extend(0.),
+                    self.position + self.rotation * end.extend(0.),
+                    self.color,
+                );
+            }
+        });
+    }
+}
+
+/// A builder returned by [`Gizmos::circle_arc`].
+pub struct CircleArcBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+    color: LegacyColor,
+    segments: Option<usize>,
+}
+
+impl<T: GizmoConfigGroup> CircleArcBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments for this arc.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = Some(segments);
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for CircleArcBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let segments = self
+            .segments
+            .unwrap_or_else(|| {
+                let circle_segments = self.gizmos.config.circle_segments as f32;
+                ((self.sweep_angle.abs() / TAU) * circle_segments).ceil() as usize
+            })
+            .max(1);
+        let positions = ellipse_arc_inner(
+            Vec2::splat(self.radius),
+            self.start_angle,
+            self.sweep_angle,
+            segments,
+        )
+        .map(|vec2| self.rotation * vec2.extend(0.))
+        .map(|vec3| vec3 + self.position);
+        if self.sweep_angle.abs() == TAU {
+            self.gizmos.linestrip_closed(positions, self.color, false);
+        } else {
+            self.gizmos.linestrip(positions, self.color);
+        }
     }
 }
 
@@ -211,6 +633,9 @@ pub struct Ellipse2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
     half_size: Vec2,
     color: LegacyColor,
     segments: usize,
+    closed: bool,
+    dashes: Option<(f32, f32)>,
+    ticks: EllipseTicks,
 }
 
 impl<T: GizmoConfigGroup> Ellipse2dBuilder<'_, '_, '_, T> {
@@ -219,6 +644,44 @@ impl<T: GizmoConfigGroup> Ellipse2dBuilder<'_, '_, '_, T> {
         self.segments = segments;
         self
     }
+
+    /// Whether to join the seam where this ellipse starts and ends like its other points, when
+    /// [`GizmoConfig::line_joints`](crate::config::GizmoConfig::line_joints) is set.
+    ///
+    /// Defaults to `true`. Has no effect without line joints.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Draw this ellipse as a dashed line, alternating between `dash_len` long dashes and
+    /// `gap_len` long gaps, both in world units.
+    ///
+    /// The dashes run continuously around the ellipse. A `gap_len` of zero draws a solid line.
+    pub fn dashed(mut self, dash_len: f32, gap_len: f32) -> Self {
+        self.dashes = Some((dash_len, gap_len));
+        self
+    }
+
+    /// Draw `count` tick marks of the given `length` at evenly spaced angles around this
+    /// ellipse, e.g. for dials, gauges or compasses.
+    ///
+    /// The first tick is at the top of the ellipse and ticks point towards its center. No ticks
+    /// are drawn unless this is called.
+    pub fn ticks(mut self, count: usize, length: f32) -> Self {
+        self.ticks.count = count;
+        self.ticks.length = length;
+        self
+    }
+
+    /// Make every `n`th tick, starting with the first, a major tick of the given `length`.
+    ///
+    /// Only has an effect together with [`ticks`](Self::ticks). Pass `0` to disable major ticks.
+    pub fn major_every(mut self, n: usize, length: f32) -> Self {
+        self.ticks.major_every = n;
+        self.ticks.major_length = length;
+        self
+    }
 }
 
 impl<T: GizmoConfigGroup> Drop for Ellipse2dBuilder<'_, '_, '_, T> {
@@ -230,6 +693,130 @@ impl<T: GizmoConfigGroup> Drop for Ellipse2dBuilder<'_, '_, '_, T> {
         let positions = ellipse_inner(self.half_size, self.segments)
             .map(|vec2| self.rotation * vec2)
             .map(|vec2| vec2 + self.position);
-        self.gizmos.linestrip_2d(positions, self.color);
+        if let Some((dash_len, gap_len)) = self.dashes {
+            self.gizmos.linestrip_dashed(
+                positions.map(|vec2| vec2.extend(0.)),
+                dash_len,
+                gap_len,
+                self.color,
+            );
+        } else if self.closed {
+            self.gizmos
+                .linestrip_closed(positions.map(|vec2| vec2.extend(0.)), self.color, false);
+        } else {
+            self.gizmos.linestrip_2d(positions, self.color);
+        }
+
+        for (start, end) in self.ticks.lines(self.half_size) {
+            self.gizmos.line_2d(
+                self.position + self.rotation * start,
+                self.position + self.rotation * end,
+                self.color,
+            );
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::circle_screen`].
+pub struct CircleScreenBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    radius: f32,
+    color: LegacyColor,
+    segments: usize,
+    closed: bool,
+}
+
+impl<T: GizmoConfigGroup> CircleScreenBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments for this circle.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Whether to join the seam where this circle starts and ends like its other points, when
+    /// [`GizmoConfig::line_joints`](crate::config::GizmoConfig::line_joints) is set.
+    ///
+    /// Defaults to `true`. Has no effect without line joints.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for CircleScreenBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let positions =
+            ellipse_inner(Vec2::splat(self.radius), self.segments).map(|vec2| vec2 + self.position);
+        if self.closed {
+            self.gizmos
+                .linestrip_closed(positions.map(|vec2| vec2.extend(0.)), self.color, true);
+        } else {
+            self.gizmos.linestrip_screen(positions, self.color);
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::range_rings_2d`].
+pub struct RangeRings2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    radii: &'a [f32],
+    color: LegacyColor,
+    segments: usize,
+    closed: bool,
+}
+
+impl<T: GizmoConfigGroup> RangeRings2dBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments for each ring.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Whether to join the seam where this ring starts and ends like its other points, when
+    /// [`GizmoConfig::line_joints`](crate::config::GizmoConfig::line_joints) is set.
+    ///
+    /// Defaults to `true`. Has no effect without line joints.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Returns the position on each ring at the given `bearing`, in the order of the radii.
+    ///
+    /// The bearing is an angle in radians, measured clockwise from `Vec2::Y`.
+    pub fn positions_at_bearing(&self, bearing: f32) -> Vec<Vec2> {
+        let (x, y) = bearing.sin_cos();
+        self.radii
+            .iter()
+            .map(|radius| self.position + Vec2::new(x, y) * *radius)
+            .collect()
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for RangeRings2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        for radius in self.radii {
+            let positions =
+                ellipse_inner(Vec2::splat(*radius), self.segments).map(|vec2| vec2 + self.position);
+            if self.closed {
+                self.gizmos.linestrip_closed(
+                    positions.map(|vec2| vec2.extend(0.)),
+                    self.color,
+                    false,
+                );
+            } else {
+                self.gizmos.linestrip_2d(positions, self.color);
+            }
+        }
     }
 }
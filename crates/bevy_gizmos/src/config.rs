@@ -1,6 +1,7 @@
 //! A module for the [`GizmoConfig<T>`] [`Resource`].
 
 use crate as bevy_gizmos;
+use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
 pub use bevy_gizmos_macros::GizmoConfigGroup;
 
 use bevy_ecs::{component::Component, reflect::ReflectResource, system::Resource};
@@ -99,6 +100,20 @@ impl GizmoConfigStore {
     }
 }
 
+/// How the segments of gizmo line strips are joined together, see [`GizmoConfig::line_joints`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum GizmoLineJoint {
+    /// Segments are drawn independently, leaving small gaps on the outside of turns at large
+    /// line widths.
+    #[default]
+    None,
+    /// The edges of consecutive segments are extended until they meet, giving sharp corners.
+    ///
+    /// Very sharp turns, where the segments meet at less than about 30°, are left unjoined, as
+    /// their corners would stick far out.
+    Miter,
+}
+
 /// A struct that stores configuration for gizmos.
 #[derive(Clone, Reflect)]
 pub struct GizmoConfig {
@@ -109,6 +124,7 @@ pub struct GizmoConfig {
     /// Line width specified in pixels.
     ///
     /// If `line_perspective` is `true` then this is the size in pixels at the camera's near plane.
+    /// If `line_width_world_space` is `true` then this is the size in world units instead.
     ///
     /// Defaults to `2.0`.
     pub line_width: f32,
@@ -118,6 +134,18 @@ pub struct GizmoConfig {
     ///
     /// Defaults to `false`.
     pub line_perspective: bool,
+    /// Interpret `line_width` in world units rather than pixels, so that lines have a constant
+    /// thickness in the world and get thinner with distance, like real geometry.
+    ///
+    /// Takes precedence over `line_perspective`. Has no effect on gizmos drawn in screen space.
+    ///
+    /// Defaults to `false`.
+    pub line_width_world_space: bool,
+    /// How the segments of line strips, like circles and [`Gizmos::linestrip`](crate::gizmos::Gizmos::linestrip),
+    /// are joined together. Joints matter most at large line widths.
+    ///
+    /// Defaults to [`GizmoLineJoint::None`].
+    pub line_joints: GizmoLineJoint,
     /// How closer to the camera than real geometry the line should be.
     ///
     /// In 2D this setting has no effect and is effectively always -1.
@@ -135,6 +163,11 @@ pub struct GizmoConfig {
     ///
     /// Gizmos will only be rendered to cameras with intersecting layers.
     pub render_layers: RenderLayers,
+    /// The number of line-segments that circles, ellipses and spheres are drawn with, and that
+    /// spirals and helices are drawn with per turn, unless set on the gizmo itself, e.g. with [`EllipseBuilder::segments`](crate::circles::EllipseBuilder::segments).
+    ///
+    /// Raise this if large circles look faceted. Defaults to `32`.
+    pub circle_segments: usize,
 }
 
 impl Default for GizmoConfig {
@@ -143,8 +176,11 @@ impl Default for GizmoConfig {
             enabled: true,
             line_width: 2.,
             line_perspective: false,
+            line_width_world_space: false,
+            line_joints: GizmoLineJoint::None,
             depth_bias: 0.,
             render_layers: Default::default(),
+            circle_segments: DEFAULT_CIRCLE_SEGMENTS,
         }
     }
 }
@@ -2,15 +2,14 @@
 
 use std::{iter, marker::PhantomData};
 
-use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
 use bevy_ecs::{
     component::Tick,
     system::{Deferred, ReadOnlySystemParam, Res, Resource, SystemBuffer, SystemMeta, SystemParam},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
-use bevy_math::{primitives::Direction3d, Mat2, Quat, Vec2, Vec3};
-use bevy_render::color::LegacyColor;
-use bevy_transform::TransformPoint;
+use bevy_math::{Mat2, Quat, Vec2, Vec3};
+use bevy_render::{camera::CameraProjection, color::LegacyColor};
+use bevy_transform::{components::GlobalTransform, TransformPoint};
 
 use crate::{
     config::GizmoConfigGroup,
@@ -18,6 +17,8 @@ use crate::{
     prelude::GizmoConfig,
 };
 
+pub use crate::sphere::SphereBuilder;
+
 type PositionItem = [f32; 3];
 type ColorItem = [f32; 4];
 
@@ -25,8 +26,16 @@ type ColorItem = [f32; 4];
 pub(crate) struct GizmoStorage<T: GizmoConfigGroup> {
     pub list_positions: Vec<PositionItem>,
     pub list_colors: Vec<ColorItem>,
+    /// The depth bias of each list position. May be shorter than `list_positions`, in which case
+    /// the remaining positions have no bias.
+    pub list_depth_biases: Vec<f32>,
     pub strip_positions: Vec<PositionItem>,
     pub strip_colors: Vec<ColorItem>,
+    /// The depth bias of each strip position. May be shorter than `strip_positions`, in which
+    /// case the remaining positions have no bias.
+    pub strip_depth_biases: Vec<f32>,
+    pub screen_strip_positions: Vec<PositionItem>,
+    pub screen_strip_colors: Vec<ColorItem>,
     marker: PhantomData<T>,
 }
 
@@ -105,21 +114,52 @@ where
 struct GizmoBuffer<T: GizmoConfigGroup> {
     list_positions: Vec<PositionItem>,
     list_colors: Vec<ColorItem>,
+    list_depth_biases: Vec<f32>,
     strip_positions: Vec<PositionItem>,
     strip_colors: Vec<ColorItem>,
+    strip_depth_biases: Vec<f32>,
+    screen_strip_positions: Vec<PositionItem>,
+    screen_strip_colors: Vec<ColorItem>,
     marker: PhantomData<T>,
 }
 
 impl<T: GizmoConfigGroup> SystemBuffer for GizmoBuffer<T> {
     fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
         let mut storage = world.resource_mut::<GizmoStorage<T>>();
+        let storage = &mut *storage;
+        append_depth_biases(
+            &mut storage.list_depth_biases,
+            storage.list_positions.len(),
+            &mut self.list_depth_biases,
+        );
+        append_depth_biases(
+            &mut storage.strip_depth_biases,
+            storage.strip_positions.len(),
+            &mut self.strip_depth_biases,
+        );
         storage.list_positions.append(&mut self.list_positions);
         storage.list_colors.append(&mut self.list_colors);
         storage.strip_positions.append(&mut self.strip_positions);
         storage.strip_colors.append(&mut self.strip_colors);
+        storage
+            .screen_strip_positions
+            .append(&mut self.screen_strip_positions);
+        storage
+            .screen_strip_colors
+            .append(&mut self.screen_strip_colors);
     }
 }
 
+/// Appends the depth biases of positions about to be appended after the first `len` ones,
+/// padding the positions in between with no bias.
+fn append_depth_biases(depth_biases: &mut Vec<f32>, len: usize, appended: &mut Vec<f32>) {
+    if appended.is_empty() {
+        return;
+    }
+    depth_biases.resize(len, 0.);
+    depth_biases.append(appended);
+}
+
 impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     /// Draw a line in 3D from `start` to `end`.
     ///
@@ -250,6 +290,46 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         self.buffer.strip_colors.push([f32::NAN; 4]);
     }
 
+    /// Draw a dashed line in 3D made of straight segments between the points.
+    ///
+    /// The line alternates between `dash_len` long dashes and `gap_len` long gaps, both in world
+    /// units. The pattern carries over from one segment to the next, so dashes stay evenly
+    /// spaced around corners. If `gap_len` is zero, this draws a solid [`Gizmos::linestrip`].
+    /// At most 65536 dashes are drawn per call; the rest of a longer line is left out.
+    ///
+    /// This should be called for each frame the line needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.linestrip_dashed([Vec3::ZERO, Vec3::X, Vec3::Y], 0.1, 0.05, LegacyColor::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn linestrip_dashed(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        dash_len: f32,
+        gap_len: f32,
+        color: LegacyColor,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        if gap_len <= 0. {
+            self.linestrip(positions, color);
+            return;
+        }
+        for_each_dash(positions, dash_len, gap_len, |start, end| {
+            self.extend_list_positions([start, end]);
+            self.add_list_color(color, 2);
+        });
+    }
+
     /// Draw a line in 3D made of straight segments between the points, with a color gradient.
     ///
     /// This should be called for each frame the lines need to be rendered.
@@ -294,9 +374,13 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         strip_colors.push([f32::NAN; 4]);
     }
 
-    /// Draw a wireframe sphere in 3D made out of 3 circles around the axes.
+    /// Draw a line in 3D made of straight segments between the points, with one color per point.
+    ///
+    /// Unlike [`Gizmos::linestrip_gradient`], colors are not interpolated: each segment is drawn
+    /// in the color of its start point. If there are fewer colors than positions, the last color
+    /// is reused for the remaining points. Nothing is drawn without any color.
     ///
-    /// This should be called for each frame the sphere needs to be rendered.
+    /// This should be called for each frame the line needs to be rendered.
     ///
     /// # Example
     /// ```
@@ -304,31 +388,32 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     /// # use bevy_render::prelude::*;
     /// # use bevy_math::prelude::*;
     /// fn system(mut gizmos: Gizmos) {
-    ///     gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1., LegacyColor::BLACK);
-    ///
-    ///     // Each circle has 32 line-segments by default.
-    ///     // You may want to increase this for larger spheres.
-    ///     gizmos
-    ///         .sphere(Vec3::ZERO, Quat::IDENTITY, 5., LegacyColor::BLACK)
-    ///         .circle_segments(64);
+    ///     gizmos.linestrip_colored(
+    ///         [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z],
+    ///         [LegacyColor::GREEN, LegacyColor::RED],
+    ///     );
     /// }
     /// # bevy_ecs::system::assert_is_system(system);
     /// ```
     #[inline]
-    pub fn sphere(
+    pub fn linestrip_colored(
         &mut self,
-        position: Vec3,
-        rotation: Quat,
-        radius: f32,
-        color: LegacyColor,
-    ) -> SphereBuilder<'_, 'w, 's, T> {
-        SphereBuilder {
-            gizmos: self,
-            position,
-            rotation,
-            radius,
-            color,
-            circle_segments: DEFAULT_CIRCLE_SEGMENTS,
+        positions: impl IntoIterator<Item = Vec3>,
+        colors: impl IntoIterator<Item = LegacyColor>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let mut positions = positions.into_iter();
+        let mut colors = colors.into_iter();
+        let (Some(mut start), Some(mut color)) = (positions.next(), colors.next()) else {
+            return;
+        };
+        for end in positions {
+            self.extend_list_positions([start, end]);
+            self.add_list_color(color, 2);
+            start = end;
+            color = colors.next().unwrap_or(color);
         }
     }
 
@@ -393,6 +478,61 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         self.add_list_color(color, 6);
     }
 
+    /// Draw the view frustum of a camera in 3D.
+    ///
+    /// The near and far planes are drawn as rectangles, joined by the four side edges. The
+    /// corners are found by unprojecting through the inverse of the projection matrix, and the
+    /// far plane is placed at [`CameraProjection::far`], so infinite perspective projections
+    /// are drawn up to their culling distance.
+    ///
+    /// This should be called for each frame the frustum needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// fn system(mut gizmos: Gizmos, cameras: Query<(&GlobalTransform, &Projection)>) {
+    ///     for (transform, projection) in &cameras {
+    ///         gizmos.frustum(transform, projection, LegacyColor::YELLOW);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn frustum(
+        &mut self,
+        camera_transform: &GlobalTransform,
+        projection: &impl CameraProjection,
+        color: LegacyColor,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let inverse_projection = projection.get_projection_matrix().inverse();
+        let far = projection.far();
+        // Bevy uses reverse-z, so the near plane is at a depth of 1. Each side edge is extended
+        // from two points on it, which works whether the edges converge or are parallel.
+        let side_edge = |x: f32, y: f32| {
+            let near = inverse_projection.project_point3(Vec3::new(x, y, 1.0));
+            let middle = inverse_projection.project_point3(Vec3::new(x, y, 0.5));
+            let far = near + (middle - near) * ((-far - near.z) / (middle.z - near.z));
+            (
+                camera_transform.transform_point(near),
+                camera_transform.transform_point(far),
+            )
+        };
+        let (tln, tlf) = side_edge(-1.0, 1.0);
+        let (trn, trf) = side_edge(1.0, 1.0);
+        let (brn, brf) = side_edge(1.0, -1.0);
+        let (bln, blf) = side_edge(-1.0, -1.0);
+
+        self.linestrip([tln, trn, brn, bln, tln], color);
+        self.linestrip([tlf, trf, brf, blf, tlf], color);
+        self.extend_list_positions([tln, tlf, trn, trf, brn, brf, bln, blf]);
+        self.add_list_color(color, 8);
+    }
+
     /// Draw a line in 2D from `start` to `end`.
     ///
     /// This should be called for each frame the line needs to be rendered.
@@ -498,6 +638,37 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         );
     }
 
+    /// Draw a line in 2D made of straight segments between the points, with one color per point.
+    ///
+    /// Each segment is drawn in the color of its start point, see [`Gizmos::linestrip_colored`].
+    ///
+    /// This should be called for each frame the line needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.linestrip_colored_2d(
+    ///         [Vec2::ZERO, Vec2::X, Vec2::Y],
+    ///         [LegacyColor::GREEN, LegacyColor::RED, LegacyColor::BLUE],
+    ///     );
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn linestrip_colored_2d(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec2>,
+        colors: impl IntoIterator<Item = LegacyColor>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.linestrip_colored(positions.into_iter().map(|vec2| vec2.extend(0.)), colors);
+    }
+
     /// Draw a line in 2D from `start` to `start + vector`.
     ///
     /// This should be called for each frame the line needs to be rendered.
@@ -602,41 +773,156 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
                 .chain(iter::once([f32::NAN; 3])),
         );
     }
-}
 
-/// A builder returned by [`Gizmos::sphere`].
-pub struct SphereBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-    position: Vec3,
-    rotation: Quat,
-    radius: f32,
-    color: LegacyColor,
-    circle_segments: usize,
-}
+    /// Draws the world space lines added by `f` with `depth_bias` added to
+    /// [`GizmoConfig::depth_bias`].
+    pub(crate) fn with_depth_bias(&mut self, depth_bias: f32, f: impl FnOnce(&mut Self)) {
+        if depth_bias == 0. {
+            f(self);
+            return;
+        }
+        let list_start = self.buffer.list_positions.len();
+        let strip_start = self.buffer.strip_positions.len();
+        f(self);
+        let buffer = &mut *self.buffer;
+        for (depth_biases, start, len) in [
+            (
+                &mut buffer.list_depth_biases,
+                list_start,
+                buffer.list_positions.len(),
+            ),
+            (
+                &mut buffer.strip_depth_biases,
+                strip_start,
+                buffer.strip_positions.len(),
+            ),
+        ] {
+            if len > start {
+                depth_biases.resize(start, 0.);
+                depth_biases.resize(len, depth_bias);
+            }
+        }
+    }
 
-impl<T: GizmoConfigGroup> SphereBuilder<'_, '_, '_, T> {
-    /// Set the number of line-segments per circle for this sphere.
-    pub fn circle_segments(mut self, segments: usize) -> Self {
-        self.circle_segments = segments;
-        self
+    /// Draw a line made of straight segments between points given in pixels, relative to the
+    /// top-left corner of the viewport.
+    #[inline]
+    pub(crate) fn linestrip_screen(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec2>,
+        color: LegacyColor,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.buffer.screen_strip_positions.extend(
+            positions
+                .into_iter()
+                .map(|vec2| vec2.extend(0.).to_array())
+                .chain(iter::once([f32::NAN; 3])),
+        );
+        let len = self.buffer.screen_strip_positions.len();
+        self.buffer
+            .screen_strip_colors
+            .resize(len - 1, color.as_linear_rgba_f32());
+        self.buffer.screen_strip_colors.push([f32::NAN; 4]);
     }
-}
 
-impl<T: GizmoConfigGroup> Drop for SphereBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
+    /// Draw a closed line strip, whose last point is its first one, in 3D or, if `screen_space`,
+    /// in viewport pixels.
+    ///
+    /// Unlike [`Gizmos::linestrip`], the seam is joined like the other points when
+    /// [`GizmoConfig::line_joints`] is set: the points on either side of the seam are added as
+    /// neighbors of its joint, with a NaN color so that no segment is drawn to them.
+    pub(crate) fn linestrip_closed(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        color: LegacyColor,
+        screen_space: bool,
+    ) {
+        if !self.enabled {
             return;
         }
-        for axis in Vec3::AXES {
-            self.gizmos
-                .circle(
-                    self.position,
-                    Direction3d::new_unchecked(self.rotation * axis),
-                    self.radius,
-                    self.color,
-                )
-                .segments(self.circle_segments);
+        let points: Vec<_> = positions.into_iter().map(|vec3| vec3.to_array()).collect();
+        let buffer = &mut *self.buffer;
+        let (strip_positions, strip_colors) = if screen_space {
+            (
+                &mut buffer.screen_strip_positions,
+                &mut buffer.screen_strip_colors,
+            )
+        } else {
+            (&mut buffer.strip_positions, &mut buffer.strip_colors)
+        };
+
+        let color = color.as_linear_rgba_f32();
+        let closed = points.len() >= 3;
+        if closed {
+            strip_positions.push(points[points.len() - 2]);
+            strip_colors.push([f32::NAN; 4]);
+        }
+        strip_positions.extend_from_slice(&points);
+        strip_colors.resize(strip_colors.len() + points.len(), color);
+        if closed {
+            strip_positions.push(points[1]);
+            strip_colors.push([f32::NAN; 4]);
+        }
+        strip_positions.push([f32::NAN; 3]);
+        strip_colors.push([f32::NAN; 4]);
+    }
+}
+
+/// The maximum number of dashes [`Gizmos::linestrip_dashed`] draws per call, so that tiny dashes
+/// on a long line can't flood the gizmo buffers. The rest of the line is left out.
+const MAX_DASHES: u32 = 1 << 16;
+
+/// Calls `f` with the start and end of each dash along the line strip through `positions`.
+///
+/// Dash `k` covers `[k * period, k * period + dash_len]` of the length along the whole strip, so
+/// the boundaries are computed from the dash index rather than accumulated, which would stall on
+/// float rounding.
+fn for_each_dash(
+    positions: impl IntoIterator<Item = Vec3>,
+    dash_len: f32,
+    gap_len: f32,
+    mut f: impl FnMut(Vec3, Vec3),
+) {
+    let dash_len = dash_len.max(0.);
+    let period = dash_len + gap_len;
+    if dash_len == 0. || !period.is_finite() {
+        return;
+    }
+
+    let mut positions = positions.into_iter();
+    let Some(mut start) = positions.next() else {
+        return;
+    };
+    // The length along the strip at `start`.
+    let mut offset = 0.;
+    let mut dash_count = 0;
+    for end in positions {
+        let length = start.distance(end);
+        let segment_end = offset + length;
+        if length > 0. {
+            let mut k = (offset / period) as u32;
+            while dash_count < MAX_DASHES {
+                let dash_start = k as f32 * period;
+                if dash_start >= segment_end {
+                    break;
+                }
+                let from = dash_start.max(offset);
+                let to = (dash_start + dash_len).min(segment_end);
+                if to > from {
+                    f(
+                        start.lerp(end, (from - offset) / length),
+                        start.lerp(end, (to - offset) / length),
+                    );
+                    dash_count += 1;
+                }
+                k += 1;
+            }
         }
+        offset = segment_end;
+        start = end;
     }
 }
 
@@ -648,3 +934,59 @@ fn rect_inner(size: Vec2) -> [Vec2; 4] {
     let br = Vec2::new(half_size.x, -half_size.y);
     [tl, tr, br, bl]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashes(positions: &[Vec3], dash_len: f32, gap_len: f32) -> Vec<(Vec3, Vec3)> {
+        let mut dashes = Vec::new();
+        for_each_dash(
+            positions.iter().copied(),
+            dash_len,
+            gap_len,
+            |start, end| {
+                dashes.push((start, end));
+            },
+        );
+        dashes
+    }
+
+    #[test]
+    fn dashes_carry_over_corners() {
+        let dashes = dashes(&[Vec3::ZERO, Vec3::X, Vec3::Y], 0.1, 0.05);
+        // Dashes cover `dash_len / period` of the strip, minus at most the one cut off at its end.
+        let total: f32 = dashes.iter().map(|(start, end)| start.distance(*end)).sum();
+        let expected = (1. + 2f32.sqrt()) * 0.1 / 0.15;
+        assert!((total - expected).abs() < 0.1, "{total} != {expected}");
+        assert!(dashes
+            .iter()
+            .all(|(start, end)| start.distance(*end) <= 0.1 + 1e-5));
+    }
+
+    #[test]
+    fn dashes_terminate() {
+        let circle: Vec<Vec3> = (0..=32)
+            .map(|i| {
+                let angle = i as f32 / 32. * std::f32::consts::TAU;
+                Vec3::new(angle.cos(), angle.sin(), 0.)
+            })
+            .collect();
+        for (positions, dash_len, gap_len) in [
+            (vec![Vec3::ZERO, Vec3::X, Vec3::Y], 0.1, 0.05),
+            (circle, 0.1, 0.05),
+            (vec![Vec3::ZERO, Vec3::X * 100.], 0.01, 0.01),
+            (vec![Vec3::ZERO, Vec3::X * 5000.], 0.3, 0.2),
+        ] {
+            let dashes = dashes(&positions, dash_len, gap_len);
+            assert!(!dashes.is_empty());
+            assert!(dashes.len() <= MAX_DASHES as usize);
+        }
+    }
+
+    #[test]
+    fn dashes_are_capped() {
+        let dashes = dashes(&[Vec3::ZERO, Vec3::X * 1e6], 0.001, 0.001);
+        assert_eq!(dashes.len(), MAX_DASHES as usize);
+    }
+}
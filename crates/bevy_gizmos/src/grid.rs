@@ -0,0 +1,201 @@
+//! Additional [`Gizmos`] Functions -- Grids
+//!
+//! Includes the implementation of [`Gizmos::grid`] and [`Gizmos::grid_2d`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_math::{Mat2, Quat, UVec2, Vec2, Vec3};
+use bevy_render::color::LegacyColor;
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a grid in 3D, e.g. for an editor-like reference plane.
+    ///
+    /// The grid has `cell_count` cells of `cell_size` each, lies in the XY plane rotated by
+    /// `rotation` and is centered on `position`. Nothing is drawn if `cell_count` is zero along
+    /// either axis.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     // A ground plane of 10x10 cells of 1x1.
+    ///     gizmos.grid(
+    ///         Vec3::ZERO,
+    ///         Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+    ///         UVec2::splat(10),
+    ///         Vec2::ONE,
+    ///         LegacyColor::GRAY,
+    ///     );
+    ///
+    ///     // Only the inner lines of the grid.
+    ///     gizmos
+    ///         .grid(Vec3::Y, Quat::IDENTITY, UVec2::new(4, 2), Vec2::splat(0.5), LegacyColor::WHITE)
+    ///         .outer_edges(false);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn grid(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        cell_count: UVec2,
+        cell_size: Vec2,
+        color: LegacyColor,
+    ) -> GridBuilder<'_, 'w, 's, T> {
+        GridBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            cell_count,
+            cell_size,
+            color,
+            outer_edges: true,
+        }
+    }
+
+    /// Draw a grid in 2D.
+    ///
+    /// The grid has `cell_count` cells of `cell_size` each, is rotated by `rotation` radians and
+    /// is centered on `position`. Nothing is drawn if `cell_count` is zero along either axis.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.grid_2d(Vec2::ZERO, 0., UVec2::new(16, 9), Vec2::splat(40.), LegacyColor::GRAY);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn grid_2d(
+        &mut self,
+        position: Vec2,
+        rotation: f32,
+        cell_count: UVec2,
+        cell_size: Vec2,
+        color: LegacyColor,
+    ) -> Grid2dBuilder<'_, 'w, 's, T> {
+        Grid2dBuilder {
+            gizmos: self,
+            position,
+            rotation: Mat2::from_angle(rotation),
+            cell_count,
+            cell_size,
+            color,
+            outer_edges: true,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::grid`].
+pub struct GridBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    cell_count: UVec2,
+    cell_size: Vec2,
+    color: LegacyColor,
+    outer_edges: bool,
+}
+
+impl<T: GizmoConfigGroup> GridBuilder<'_, '_, '_, T> {
+    /// Whether to draw the lines along the boundary of this grid.
+    ///
+    /// Defaults to `true`. Skip them when the grid sits inside a frame that is drawn separately.
+    pub fn outer_edges(mut self, outer_edges: bool) -> Self {
+        self.outer_edges = outer_edges;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for GridBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        // a grid without cells along one axis has no area to cover
+        if self.cell_count.x == 0 || self.cell_count.y == 0 {
+            return;
+        }
+
+        for (start, end) in grid_inner(self.cell_count, self.cell_size, self.outer_edges) {
+            self.gizmos.line(
+                self.position + self.rotation * start.extend(0.),
+                self.position + self.rotation * end.extend(0.),
+                self.color,
+            );
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::grid_2d`].
+pub struct Grid2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    rotation: Mat2,
+    cell_count: UVec2,
+    cell_size: Vec2,
+    color: LegacyColor,
+    outer_edges: bool,
+}
+
+impl<T: GizmoConfigGroup> Grid2dBuilder<'_, '_, '_, T> {
+    /// Whether to draw the lines along the boundary of this grid.
+    ///
+    /// Defaults to `true`. Skip them when the grid sits inside a frame that is drawn separately.
+    pub fn outer_edges(mut self, outer_edges: bool) -> Self {
+        self.outer_edges = outer_edges;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Grid2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        // a grid without cells along one axis has no area to cover
+        if self.cell_count.x == 0 || self.cell_count.y == 0 {
+            return;
+        }
+
+        for (start, end) in grid_inner(self.cell_count, self.cell_size, self.outer_edges) {
+            self.gizmos.line_2d(
+                self.position + self.rotation * start,
+                self.position + self.rotation * end,
+                self.color,
+            );
+        }
+    }
+}
+
+/// Yields the start and end points of the lines of a grid centered on the origin of the XY plane.
+///
+/// `cell_count` must not be zero along either axis.
+fn grid_inner(
+    cell_count: UVec2,
+    cell_size: Vec2,
+    outer_edges: bool,
+) -> impl Iterator<Item = (Vec2, Vec2)> {
+    let half_size = cell_count.as_vec2() * cell_size / 2.;
+    let skip = u32::from(!outer_edges);
+
+    // lines parallel to the y axis, spaced along the x axis
+    let vertical = (skip..=cell_count.x - skip).map(move |i| {
+        let x = i as f32 * cell_size.x - half_size.x;
+        (Vec2::new(x, -half_size.y), Vec2::new(x, half_size.y))
+    });
+    // lines parallel to the x axis, spaced along the y axis
+    let horizontal = (skip..=cell_count.y - skip).map(move |i| {
+        let y = i as f32 * cell_size.y - half_size.y;
+        (Vec2::new(-half_size.x, y), Vec2::new(half_size.x, y))
+    });
+    vertical.chain(horizontal)
+}
@@ -27,10 +27,14 @@ pub enum GizmoRenderSystem {
 pub mod aabb;
 pub mod arcs;
 pub mod arrows;
+pub mod basis;
 pub mod circles;
 pub mod config;
 pub mod gizmos;
+pub mod grid;
 pub mod primitives;
+pub mod sphere;
+pub mod spirals;
 
 #[cfg(feature = "bevy_sprite")]
 mod pipeline_2d;
@@ -42,10 +46,13 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         aabb::{AabbGizmoConfigGroup, ShowAabbGizmo},
-        config::{DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore},
+        config::{
+            DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
+            GizmoLineJoint,
+        },
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
-        AppGizmoBuilder,
+        AppGizmoBuilder, GizmoStatistics,
     };
 }
 
@@ -62,6 +69,8 @@ use bevy_ecs::{
         Commands, Res, ResMut, Resource, SystemParamItem,
     },
 };
+#[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+use bevy_math::Vec3;
 use bevy_reflect::TypePath;
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
@@ -79,7 +88,8 @@ use bevy_render::{
 };
 use bevy_utils::TypeIdMap;
 use config::{
-    DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoMeshConfig,
+    DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoLineJoint,
+    GizmoMeshConfig,
 };
 use gizmos::GizmoStorage;
 use std::{any::TypeId, mem};
@@ -103,6 +113,8 @@ impl Plugin for GizmoPlugin {
             .init_asset::<LineGizmo>()
             .add_plugins(RenderAssetPlugin::<LineGizmo>::default())
             .init_resource::<LineGizmoHandles>()
+            .init_resource::<GizmoStatistics>()
+            .add_systems(Last, reset_gizmo_statistics.before(UpdateGizmoMeshes))
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>()
             .add_plugins(AabbGizmoPlugin);
@@ -164,7 +176,7 @@ impl AppGizmoBuilder for App {
         }
 
         self.init_resource::<GizmoStorage<T>>()
-            .add_systems(Last, update_gizmo_meshes::<T>);
+            .add_systems(Last, update_gizmo_meshes::<T>.in_set(UpdateGizmoMeshes));
 
         self.world
             .get_resource_or_insert_with::<GizmoConfigStore>(Default::default)
@@ -193,7 +205,7 @@ impl AppGizmoBuilder for App {
         }
 
         self.init_resource::<GizmoStorage<T>>()
-            .add_systems(Last, update_gizmo_meshes::<T>);
+            .add_systems(Last, update_gizmo_meshes::<T>.in_set(UpdateGizmoMeshes));
 
         let Ok(render_app) = self.get_sub_app_mut(RenderApp) else {
             return self;
@@ -205,57 +217,149 @@ impl AppGizmoBuilder for App {
     }
 }
 
+/// Label for the systems moving the gizmos drawn this frame into their [`LineGizmo`] assets.
+#[derive(SystemSet, Clone, Debug, Hash, PartialEq, Eq)]
+struct UpdateGizmoMeshes;
+
+/// Statistics about the gizmos drawn in the last frame, across all enabled [`GizmoConfigGroup`]s.
+///
+/// Updated in [`Last`], so systems running earlier in the frame see the previous frame's counts.
+/// Useful to catch systems accidentally drawing far more gizmos than intended.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GizmoStatistics {
+    /// The number of vertices uploaded for gizmo lines.
+    pub vertices: usize,
+    /// The number of line segments drawn.
+    pub line_segments: usize,
+    /// The number of gizmo batches submitted. Each batch is drawn with one draw call per camera
+    /// that renders it.
+    pub batches: usize,
+}
+
+impl GizmoStatistics {
+    fn add(&mut self, positions: &[[f32; 3]], colors: &[[f32; 4]], strip: bool) {
+        if positions.is_empty() {
+            return;
+        }
+        self.vertices += positions.len();
+        self.line_segments += if strip {
+            // Separate strips are delimited by NaN positions, and the neighbors of the joints of
+            // closed strips have NaN colors.
+            positions
+                .windows(2)
+                .zip(colors.windows(2))
+                .filter(|(pair, colors)| {
+                    !pair[0][0].is_nan()
+                        && !pair[1][0].is_nan()
+                        && !colors[0][0].is_nan()
+                        && !colors[1][0].is_nan()
+                })
+                .count()
+        } else {
+            positions.len() / 2
+        };
+        self.batches += 1;
+    }
+}
+
+fn reset_gizmo_statistics(mut statistics: ResMut<GizmoStatistics>) {
+    *statistics = GizmoStatistics::default();
+}
+
 #[derive(Resource, Default)]
 struct LineGizmoHandles {
     list: TypeIdMap<Handle<LineGizmo>>,
     strip: TypeIdMap<Handle<LineGizmo>>,
+    /// Line strips positioned in viewport pixels rather than world space.
+    screen_strip: TypeIdMap<Handle<LineGizmo>>,
 }
 
 fn update_gizmo_meshes<T: GizmoConfigGroup>(
     mut line_gizmos: ResMut<Assets<LineGizmo>>,
     mut handles: ResMut<LineGizmoHandles>,
     mut storage: ResMut<GizmoStorage<T>>,
+    mut statistics: ResMut<GizmoStatistics>,
+    config: Res<GizmoConfigStore>,
 ) {
-    if storage.list_positions.is_empty() {
-        handles.list.remove(&TypeId::of::<T>());
-    } else if let Some(handle) = handles.list.get(&TypeId::of::<T>()) {
-        let list = line_gizmos.get_mut(handle).unwrap();
-
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
-    } else {
-        let mut list = LineGizmo {
-            strip: false,
-            ..Default::default()
-        };
+    let storage = &mut *storage;
+    if config.config::<T>().0.enabled {
+        statistics.add(&storage.list_positions, &storage.list_colors, false);
+        statistics.add(&storage.strip_positions, &storage.strip_colors, true);
+        statistics.add(
+            &storage.screen_strip_positions,
+            &storage.screen_strip_colors,
+            true,
+        );
+    }
+    update_line_gizmo::<T>(
+        &mut line_gizmos,
+        &mut handles.list,
+        &mut storage.list_positions,
+        &mut storage.list_colors,
+        &mut storage.list_depth_biases,
+        false,
+    );
+    update_line_gizmo::<T>(
+        &mut line_gizmos,
+        &mut handles.strip,
+        &mut storage.strip_positions,
+        &mut storage.strip_colors,
+        &mut storage.strip_depth_biases,
+        true,
+    );
+    update_line_gizmo::<T>(
+        &mut line_gizmos,
+        &mut handles.screen_strip,
+        &mut storage.screen_strip_positions,
+        &mut storage.screen_strip_colors,
+        &mut Vec::new(),
+        true,
+    );
+}
 
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
+/// Moves the lines drawn this frame into the [`LineGizmo`] asset of group `T` in `handles`,
+/// creating the asset if needed, or removing it if nothing was drawn.
+fn update_line_gizmo<T: GizmoConfigGroup>(
+    line_gizmos: &mut Assets<LineGizmo>,
+    handles: &mut TypeIdMap<Handle<LineGizmo>>,
+    positions: &mut Vec<[f32; 3]>,
+    colors: &mut Vec<[f32; 4]>,
+    depth_biases: &mut Vec<f32>,
+    strip: bool,
+) {
+    if positions.is_empty() {
+        handles.remove(&TypeId::of::<T>());
+        depth_biases.clear();
+        return;
+    }
 
-        handles
-            .list
-            .insert(TypeId::of::<T>(), line_gizmos.add(list));
+    if strip {
+        // Line strips are drawn with the points before and after each segment, so that its
+        // joints can be computed. Start with a delimiter so the first segment has no previous
+        // point.
+        positions.insert(0, [f32::NAN; 3]);
+        colors.insert(0, [f32::NAN; 4]);
+        if !depth_biases.is_empty() {
+            depth_biases.insert(0, 0.);
+        }
     }
+    depth_biases.resize(positions.len(), 0.);
 
-    if storage.strip_positions.is_empty() {
-        handles.strip.remove(&TypeId::of::<T>());
-    } else if let Some(handle) = handles.strip.get(&TypeId::of::<T>()) {
-        let strip = line_gizmos.get_mut(handle).unwrap();
+    if let Some(handle) = handles.get(&TypeId::of::<T>()) {
+        let line_gizmo = line_gizmos.get_mut(handle).unwrap();
 
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
+        line_gizmo.positions = mem::take(positions);
+        line_gizmo.colors = mem::take(colors);
+        line_gizmo.depth_biases = mem::take(depth_biases);
     } else {
-        let mut strip = LineGizmo {
-            strip: true,
-            ..Default::default()
+        let line_gizmo = LineGizmo {
+            positions: mem::take(positions),
+            colors: mem::take(colors),
+            depth_biases: mem::take(depth_biases),
+            strip,
         };
 
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
-
-        handles
-            .strip
-            .insert(TypeId::of::<T>(), line_gizmos.add(strip));
+        handles.insert(TypeId::of::<T>(), line_gizmos.add(line_gizmo));
     }
 }
 
@@ -270,7 +374,11 @@ fn extract_gizmo_data<T: GizmoConfigGroup>(
         return;
     }
 
-    for map in [&handles.list, &handles.strip].into_iter() {
+    for (map, screen_space) in [
+        (&handles.list, false),
+        (&handles.strip, false),
+        (&handles.screen_strip, true),
+    ] {
         let Some(handle) = map.get(&TypeId::of::<T>()) else {
             continue;
         };
@@ -278,8 +386,14 @@ fn extract_gizmo_data<T: GizmoConfigGroup>(
             LineGizmoUniform {
                 line_width: config.line_width,
                 depth_bias: config.depth_bias,
-                #[cfg(feature = "webgl")]
-                _padding: Default::default(),
+                screen_space: screen_space as u32,
+                world_space_width: (config.line_width_world_space && !screen_space) as u32,
+                joints: match config.line_joints {
+                    GizmoLineJoint::None => 0,
+                    GizmoLineJoint::Miter => 1,
+                },
+                #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+                _webgl2_padding: Vec3::ZERO,
             },
             (*handle).clone_weak(),
             GizmoMeshConfig::from(config),
@@ -291,15 +405,23 @@ fn extract_gizmo_data<T: GizmoConfigGroup>(
 struct LineGizmoUniform {
     line_width: f32,
     depth_bias: f32,
+    /// Nonzero if the positions are in viewport pixels rather than world space.
+    screen_space: u32,
+    /// Nonzero if `line_width` is in world units rather than pixels.
+    world_space_width: u32,
+    /// The [`GizmoLineJoint`] of line strips: `0` for none, `1` for miter.
+    joints: u32,
     /// WebGL2 structs must be 16 byte aligned.
-    #[cfg(feature = "webgl")]
-    _padding: bevy_math::Vec2,
+    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+    _webgl2_padding: Vec3,
 }
 
 #[derive(Asset, Debug, Default, Clone, TypePath)]
 struct LineGizmo {
     positions: Vec<[f32; 3]>,
     colors: Vec<[f32; 4]>,
+    /// Added to the [`GizmoConfig::depth_bias`] of each position.
+    depth_biases: Vec<f32>,
     /// Whether this gizmo's topology is a line-strip or line-list
     strip: bool,
 }
@@ -308,6 +430,7 @@ struct LineGizmo {
 struct GpuLineGizmo {
     position_buffer: Buffer,
     color_buffer: Buffer,
+    depth_bias_buffer: Buffer,
     vertex_count: u32,
     strip: bool,
 }
@@ -338,9 +461,16 @@ impl RenderAsset for LineGizmo {
             contents: color_buffer_data,
         });
 
+        let depth_bias_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            usage: BufferUsages::VERTEX,
+            label: Some("LineGizmo Depth Bias Buffer"),
+            contents: cast_slice(&self.depth_biases),
+        });
+
         Ok(GpuLineGizmo {
             position_buffer,
             color_buffer,
+            depth_bias_buffer,
             vertex_count: self.positions.len() as u32,
             strip: self.strip,
         })
@@ -421,25 +551,58 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
             return RenderCommandResult::Failure;
         };
 
-        if line_gizmo.vertex_count < 2 {
-            return RenderCommandResult::Success;
-        }
-
         let instances = if line_gizmo.strip {
+            // Each segment also reads the points before and after it, which are delimiters at the
+            // start and end of the buffer.
+            if line_gizmo.vertex_count < 4 {
+                return RenderCommandResult::Success;
+            }
             let item_size = VertexFormat::Float32x3.size();
-            let buffer_size = line_gizmo.position_buffer.size() - item_size;
-            pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..buffer_size));
-            pass.set_vertex_buffer(1, line_gizmo.position_buffer.slice(item_size..));
+            let buffer_size = line_gizmo.position_buffer.size() - 3 * item_size;
+            for (slot, offset) in [(4, 0), (0, 1), (1, 2), (5, 3)] {
+                let start = offset * item_size;
+                pass.set_vertex_buffer(
+                    slot,
+                    line_gizmo.position_buffer.slice(start..start + buffer_size),
+                );
+            }
 
             let item_size = VertexFormat::Float32x4.size();
-            let buffer_size = line_gizmo.color_buffer.size() - item_size;
-            pass.set_vertex_buffer(2, line_gizmo.color_buffer.slice(..buffer_size));
-            pass.set_vertex_buffer(3, line_gizmo.color_buffer.slice(item_size..));
-
-            u32::max(line_gizmo.vertex_count, 1) - 1
+            let buffer_size = line_gizmo.color_buffer.size() - 3 * item_size;
+            pass.set_vertex_buffer(
+                2,
+                line_gizmo
+                    .color_buffer
+                    .slice(item_size..item_size + buffer_size),
+            );
+            pass.set_vertex_buffer(
+                3,
+                line_gizmo
+                    .color_buffer
+                    .slice(2 * item_size..2 * item_size + buffer_size),
+            );
+
+            let item_size = VertexFormat::Float32.size();
+            let buffer_size = line_gizmo.depth_bias_buffer.size() - 3 * item_size;
+            for (slot, offset) in [(6, 1), (7, 2)] {
+                let start = offset * item_size;
+                pass.set_vertex_buffer(
+                    slot,
+                    line_gizmo
+                        .depth_bias_buffer
+                        .slice(start..start + buffer_size),
+                );
+            }
+
+            line_gizmo.vertex_count - 3
         } else {
+            if line_gizmo.vertex_count < 2 {
+                return RenderCommandResult::Success;
+            }
+
             pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..));
             pass.set_vertex_buffer(1, line_gizmo.color_buffer.slice(..));
+            pass.set_vertex_buffer(2, line_gizmo.depth_bias_buffer.slice(..));
 
             line_gizmo.vertex_count / 2
         };
@@ -472,18 +635,32 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
         }],
     };
 
+    let mut depth_bias_layout = VertexBufferLayout {
+        array_stride: Float32.size(),
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![VertexAttribute {
+            format: Float32,
+            offset: 0,
+            shader_location: 6,
+        }],
+    };
+
     if strip {
+        let with_location = |layout: &VertexBufferLayout, shader_location| {
+            let mut layout = layout.clone();
+            layout.attributes[0].shader_location = shader_location;
+            layout
+        };
         vec![
             position_layout.clone(),
-            {
-                position_layout.attributes[0].shader_location = 1;
-                position_layout
-            },
+            with_location(&position_layout, 1),
             color_layout.clone(),
-            {
-                color_layout.attributes[0].shader_location = 3;
-                color_layout
-            },
+            with_location(&color_layout, 3),
+            // The points before and after the segment.
+            with_location(&position_layout, 4),
+            with_location(&position_layout, 5),
+            depth_bias_layout.clone(),
+            with_location(&depth_bias_layout, 7),
         ]
     } else {
         position_layout.array_stride *= 2;
@@ -500,6 +677,13 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
             shader_location: 3,
         });
 
-        vec![position_layout, color_layout]
+        depth_bias_layout.array_stride *= 2;
+        depth_bias_layout.attributes.push(VertexAttribute {
+            format: Float32,
+            offset: Float32.size(),
+            shader_location: 7,
+        });
+
+        vec![position_layout, color_layout, depth_bias_layout]
     }
 }
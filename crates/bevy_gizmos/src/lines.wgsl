@@ -7,9 +7,15 @@
 struct LineGizmoUniform {
     line_width: f32,
     depth_bias: f32,
+    // Nonzero if the positions are in viewport pixels rather than world space.
+    screen_space: u32,
+    // Nonzero if `line_width` is in world units rather than pixels.
+    world_space_width: u32,
+    // 0 for no joints between the segments of line strips, 1 for miter joints.
+    joints: u32,
 #ifdef SIXTEEN_BYTE_ALIGNMENT
     // WebGL2 structs must be 16 byte aligned.
-    _padding: vec2<f32>,
+    _webgl2_padding: vec3<f32>,
 #endif
 }
 
@@ -20,6 +26,14 @@ struct VertexInput {
     @location(1) position_b: vec3<f32>,
     @location(2) color_a: vec4<f32>,
     @location(3) color_b: vec4<f32>,
+#ifdef LINE_STRIP
+    // The points before `position_a` and after `position_b` in the strip.
+    @location(4) position_prev: vec3<f32>,
+    @location(5) position_next: vec3<f32>,
+#endif
+    // Added to `line_gizmo.depth_bias` at each end of the segment.
+    @location(6) depth_bias_a: f32,
+    @location(7) depth_bias_b: f32,
     @builtin(vertex_index) index: u32,
 };
 
@@ -29,6 +43,8 @@ struct VertexOutput {
 };
 
 const EPSILON: f32 = 4.88e-04;
+// Miter joints longer than this, relative to the line width, are left unjoined.
+const MITER_LIMIT: f32 = 4.;
 
 @vertex
 fn vertex(vertex: VertexInput) -> VertexOutput {
@@ -42,9 +58,22 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     );
     let position = positions[vertex.index];
 
+    // Line strips are delimited by NaN positions and colors. Points that only serve as the
+    // neighbors of a joint have a NaN color: don't draw the segments that touch them.
+    if is_nan(vertex.color_a.x) || is_nan(vertex.color_b.x) {
+        return VertexOutput(vec4(0.), vec4(0.));
+    }
+
     // algorithm based on https://wwwtyro.net/2019/11/18/instanced-lines.html
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
+    var clip_a: vec4<f32>;
+    var clip_b: vec4<f32>;
+    if line_gizmo.screen_space != 0u {
+        clip_a = screen_to_clip(vertex.position_a.xy);
+        clip_b = screen_to_clip(vertex.position_b.xy);
+    } else {
+        clip_a = view.view_proj * vec4(vertex.position_a, 1.);
+        clip_b = view.view_proj * vec4(vertex.position_b, 1.);
+    }
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_b);
@@ -64,9 +93,14 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     var line_width = line_gizmo.line_width;
     var alpha = 1.;
 
+    if line_gizmo.world_space_width != 0u {
+        // Convert the width from world units to pixels at the depth of this vertex.
+        line_width *= 0.5 * view.projection[1][1] * resolution.y / clip.w;
+    } else {
 #ifdef PERSPECTIVE
-    line_width /= clip.w;
+        line_width /= clip.w;
 #endif
+    }
 
     // Line thinness fade from https://acegikmo.com/shapes/docs/#anti-aliasing
     if line_width > 0.0 && line_width < 1. {
@@ -74,12 +108,35 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
         line_width = 1.;
     }
 
-    let offset = line_width * (position.x * x_basis + position.y * y_basis);
+    var y_offset = y_basis;
+#ifdef LINE_STRIP
+    if line_gizmo.joints != 0u {
+        // Move the corners at each end of the segment to the joint with the neighboring segment.
+        if position.z == 0. {
+            let prev = project_to_screen(vertex.position_prev, resolution);
+            if prev.z != 0. {
+                y_offset = miter_offset(screen_a - prev.xy, screen_b - screen_a, y_basis);
+            }
+        } else {
+            let next = project_to_screen(vertex.position_next, resolution);
+            if next.z != 0. {
+                y_offset = miter_offset(screen_b - screen_a, next.xy - screen_b, y_basis);
+            }
+        }
+    }
+#endif
+
+    let offset = line_width * (position.x * x_basis + position.y * y_offset);
     let screen = mix(screen_a, screen_b, position.z) + offset;
 
+    let depth_bias = clamp(
+        line_gizmo.depth_bias + mix(vertex.depth_bias_a, vertex.depth_bias_b, position.z),
+        -1.,
+        1.
+    );
     var depth: f32;
-    if line_gizmo.depth_bias >= 0. {
-        depth = clip.z * (1. - line_gizmo.depth_bias);
+    if depth_bias >= 0. {
+        depth = clip.z * (1. - depth_bias);
     } else {
         // depth * (clip.w / depth)^-depth_bias. So that when -depth_bias is 1.0, this is equal to clip.w
         // and when equal to 0.0, it is exactly equal to depth.
@@ -88,7 +145,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
         // of this value means nothing can be in front of this
         // The reason this uses an exponential function is that it makes it much easier for the
         // user to chose a value that is convenient for them
-        depth = clip.z * exp2(-line_gizmo.depth_bias * log2(clip.w / clip.z - EPSILON));
+        depth = clip.z * exp2(-depth_bias * log2(clip.w / clip.z - EPSILON));
     }
 
     var clip_position = vec4(clip.w * ((2. * screen) / resolution - 1.), depth, clip.w);
@@ -96,6 +153,61 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     return VertexOutput(clip_position, color);
 }
 
+// Converts a position in pixels, relative to the top-left corner of the viewport, to clip space
+// on the near plane, so that it is drawn on top of everything else.
+fn screen_to_clip(screen: vec2<f32>) -> vec4<f32> {
+    let ndc = screen / view.viewport.zw * vec2(2., -2.) + vec2(-1., 1.);
+    return vec4(ndc, 1., 1.);
+}
+
+#ifdef LINE_STRIP
+// Returns the position in pixels of the neighbor point of a joint in `xy`, and in `z` whether it
+// can be used: it isn't a strip delimiter nor behind the camera.
+fn project_to_screen(position: vec3<f32>, resolution: vec2<f32>) -> vec3<f32> {
+    if is_nan(position.x) {
+        return vec3(0.);
+    }
+    var clip: vec4<f32>;
+    if line_gizmo.screen_space != 0u {
+        clip = screen_to_clip(position.xy);
+    } else {
+        clip = view.view_proj * vec4(position, 1.);
+    }
+    if clip.w <= 0. {
+        return vec3(0.);
+    }
+    return vec3(resolution * (0.5 * clip.xy / clip.w + 0.5), 1.);
+}
+
+// Returns the offset, per unit of line width, of the corners of a segment at its joint with
+// another segment, given the direction of the segment before the joint and after it.
+//
+// The offset lies along the bisector of the joint and is lengthened so that both segments keep
+// their width. Joints that are too sharp, or that join degenerate segments, fall back to
+// `y_basis`, the offset without a joint.
+fn miter_offset(before: vec2<f32>, after: vec2<f32>, y_basis: vec2<f32>) -> vec2<f32> {
+    if dot(before, before) < EPSILON || dot(after, after) < EPSILON {
+        return y_basis;
+    }
+    let tangent = normalize(before) + normalize(after);
+    if dot(tangent, tangent) < EPSILON {
+        return y_basis;
+    }
+    let direction = normalize(tangent);
+    let miter = vec2(direction.y, -direction.x);
+    let scale = 1. / dot(miter, y_basis);
+    if scale <= 0. || scale > MITER_LIMIT {
+        return y_basis;
+    }
+    return miter * scale;
+}
+#endif
+
+// NaN checks on the bits, as comparisons with NaN may be optimized away.
+fn is_nan(value: f32) -> bool {
+    return (bitcast<u32>(value) & 0x7fffffffu) > 0x7f800000u;
+}
+
 fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
     // Move a if a is behind the near plane and b is in front. 
     if a.z > a.w && b.z <= b.w {
@@ -89,11 +89,15 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             TextureFormat::bevy_default()
         };
 
-        let shader_defs = vec![
-            #[cfg(feature = "webgl")]
+        let mut shader_defs = vec![
+            #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
             "SIXTEEN_BYTE_ALIGNMENT".into(),
         ];
 
+        if key.strip {
+            shader_defs.push("LINE_STRIP".into());
+        }
+
         let layout = vec![
             self.mesh_pipeline.view_layout.clone(),
             self.uniform_layout.clone(),
@@ -0,0 +1,96 @@
+//! Additional [`Gizmos`] Functions -- Spheres
+//!
+//! Includes the implementation of [`Gizmos::sphere`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_math::{primitives::Direction3d, Quat, Vec3};
+use bevy_render::color::LegacyColor;
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a wireframe sphere in 3D made out of 3 circles around the axes, e.g. for bounding
+    /// spheres or the range of a point light.
+    ///
+    /// The circles lie in the XY, XZ and YZ planes, rotated by `rotation`.
+    ///
+    /// This should be called for each frame the sphere needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1., LegacyColor::BLACK);
+    ///
+    ///     // Each circle has 32 line-segments by default.
+    ///     // You may want to increase this for larger spheres.
+    ///     gizmos
+    ///         .sphere(Vec3::ZERO, Quat::IDENTITY, 5., LegacyColor::BLACK)
+    ///         .circle_segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn sphere(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        radius: f32,
+        color: LegacyColor,
+    ) -> SphereBuilder<'_, 'w, 's, T> {
+        let circle_segments = self.config.circle_segments;
+        SphereBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            radius,
+            color,
+            circle_segments,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::sphere`].
+pub struct SphereBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    radius: f32,
+    color: LegacyColor,
+    circle_segments: usize,
+}
+
+impl<T: GizmoConfigGroup> SphereBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments per circle for this sphere.
+    pub fn circle_segments(mut self, segments: usize) -> Self {
+        self.circle_segments = segments;
+        self
+    }
+
+    /// Set the number of line-segments per circle for this sphere.
+    ///
+    /// Same as [`circle_segments`](Self::circle_segments), for consistency with the other
+    /// circle-based builders.
+    pub fn segments(self, segments: usize) -> Self {
+        self.circle_segments(segments)
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for SphereBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        for axis in Vec3::AXES {
+            self.gizmos
+                .circle(
+                    self.position,
+                    Direction3d::new_unchecked(self.rotation * axis),
+                    self.radius,
+                    self.color,
+                )
+                .segments(self.circle_segments);
+        }
+    }
+}
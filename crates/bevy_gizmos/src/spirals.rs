@@ -0,0 +1,193 @@
+//! Additional [`Gizmos`] Functions -- Spirals
+//!
+//! Includes the implementation of [`Gizmos::spiral_2d`] and [`Gizmos::helix`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_render::color::LegacyColor;
+use std::f32::consts::TAU;
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw an Archimedean spiral in 2D, whose radius grows linearly from `start_radius` to
+    /// `end_radius` over `turns` full turns.
+    ///
+    /// This should be called for each frame the spiral needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the spiral.
+    /// - `start_radius` sets the distance from `position` to the start of the spiral, which lies
+    ///   in the direction of `Vec2::Y`.
+    /// - `end_radius` sets the distance from `position` to the end of the spiral.
+    /// - `turns` sets the number of clockwise turns. Negative values turn counterclockwise.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.spiral_2d(Vec2::ZERO, 0., 5., 3., LegacyColor::GREEN);
+    ///
+    ///     // Spirals have 32 line-segments per turn by default.
+    ///     // You may want to increase this for larger spirals.
+    ///     gizmos
+    ///         .spiral_2d(Vec2::ZERO, 10., 20., 2., LegacyColor::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn spiral_2d(
+        &mut self,
+        position: Vec2,
+        start_radius: f32,
+        end_radius: f32,
+        turns: f32,
+        color: LegacyColor,
+    ) -> Spiral2dBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
+        Spiral2dBuilder {
+            gizmos: self,
+            position,
+            start_radius,
+            end_radius,
+            turns,
+            color,
+            segments,
+        }
+    }
+
+    /// Draw a helix in 3D, such as a coil spring, winding `turns` times around its axis.
+    ///
+    /// This should be called for each frame the helix needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the bottom of the helix.
+    /// - `rotation` defines the orientation of the helix. With `Quat::IDENTITY` its axis is
+    ///   `Vec3::Y`, and it starts in the direction of `Vec3::Z`.
+    /// - `radius` sets the distance from the axis to the helix.
+    /// - `height` sets the length of the helix along its axis.
+    /// - `turns` sets the number of turns. Positive values wind counterclockwise when looking
+    ///   down the axis, like a right-handed thread.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.helix(Vec3::ZERO, Quat::IDENTITY, 0.5, 2., 8., LegacyColor::GREEN);
+    ///
+    ///     // Helices have 32 line-segments per turn by default.
+    ///     // You may want to increase this for larger helices.
+    ///     gizmos
+    ///         .helix(Vec3::X, Quat::IDENTITY, 2., 2., 4., LegacyColor::RED)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn helix(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        radius: f32,
+        height: f32,
+        turns: f32,
+        color: LegacyColor,
+    ) -> HelixBuilder<'_, 'w, 's, T> {
+        let segments = self.config.circle_segments;
+        HelixBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            radius,
+            height,
+            turns,
+            color,
+            segments,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::spiral_2d`].
+pub struct Spiral2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    start_radius: f32,
+    end_radius: f32,
+    turns: f32,
+    color: LegacyColor,
+    segments: usize,
+}
+
+impl<T: GizmoConfigGroup> Spiral2dBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments per full turn of this spiral.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Spiral2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let positions = spiral_inner(self.turns, self.segments).map(|(t, angle)| {
+            let radius = self.start_radius + (self.end_radius - self.start_radius) * t;
+            let (x, y) = angle.sin_cos();
+            self.position + Vec2::new(x, y) * radius
+        });
+        self.gizmos.linestrip_2d(positions, self.color);
+    }
+}
+
+/// A builder returned by [`Gizmos::helix`].
+pub struct HelixBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    radius: f32,
+    height: f32,
+    turns: f32,
+    color: LegacyColor,
+    segments: usize,
+}
+
+impl<T: GizmoConfigGroup> HelixBuilder<'_, '_, '_, T> {
+    /// Set the number of line-segments per full turn of this helix.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for HelixBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let positions = spiral_inner(self.turns, self.segments).map(|(t, angle)| {
+            let (sin, cos) = angle.sin_cos();
+            let local = Vec3::new(sin * self.radius, t * self.height, cos * self.radius);
+            self.position + self.rotation * local
+        });
+        self.gizmos.linestrip(positions, self.color);
+    }
+}
+
+/// Yields the progress in `[0, 1]` and the angle of each point along a curve winding `turns`
+/// times, with `segments_per_turn` line-segments per full turn.
+///
+/// The points are evaluated directly from the progress, so the curve has no seams between turns.
+fn spiral_inner(turns: f32, segments_per_turn: usize) -> impl Iterator<Item = (f32, f32)> {
+    let segments = ((turns.abs() * segments_per_turn as f32).ceil() as usize).max(1);
+    (0..segments + 1).map(move |i| {
+        let t = i as f32 / segments as f32;
+        (t, t * turns * TAU)
+    })
+}
@@ -11,8 +11,8 @@ use bevy_hierarchy::{BuildWorldChildren, WorldChildBuilder};
 use bevy_log::{error, info_span, warn};
 use bevy_math::{Affine2, Mat4, Vec3};
 use bevy_pbr::{
-    DirectionalLight, DirectionalLightBundle, PbrBundle, PointLight, PointLightBundle, SpotLight,
-    SpotLightBundle, StandardMaterial, MAX_JOINTS,
+    DirectionalLight, DirectionalLightBundle, GltfPbrParameters, PbrBundle, PbrWorkflow,
+    PointLight, PointLightBundle, SpotLight, SpotLightBundle, StandardMaterial, MAX_JOINTS,
 };
 use bevy_render::{
     alpha::AlphaMode,
@@ -917,43 +917,58 @@ fn load_material(
 
         let ior = material.ior().unwrap_or(1.5);
 
-        StandardMaterial {
-            base_color: LegacyColor::rgba_linear(color[0], color[1], color[2], color[3]),
+        let mut standard_material = StandardMaterial::from(GltfPbrParameters {
+            base_color_factor: color,
             base_color_texture,
-            perceptual_roughness: pbr.roughness_factor(),
-            metallic: pbr.metallic_factor(),
+            metallic_factor: pbr.metallic_factor(),
+            roughness_factor: pbr.roughness_factor(),
             metallic_roughness_texture,
-            normal_map_texture,
-            double_sided: material.double_sided(),
-            cull_mode: if material.double_sided() {
-                None
-            } else if is_scale_inverted {
-                Some(Face::Front)
-            } else {
-                Some(Face::Back)
-            },
+            normal_texture: normal_map_texture,
             occlusion_texture,
-            emissive: LegacyColor::rgb_linear(emissive[0], emissive[1], emissive[2])
-                * material.emissive_strength().unwrap_or(1.0),
+            emissive_factor: emissive,
             emissive_texture,
-            specular_transmission,
+            emissive_strength: material.emissive_strength().unwrap_or(1.0),
+            alpha_mode: alpha_mode(material),
+            double_sided: material.double_sided(),
+            unlit: material.unlit(),
+            uv_transform,
+            transmission_factor: specular_transmission,
             #[cfg(feature = "pbr_transmission_textures")]
-            specular_transmission_texture,
-            thickness,
+            transmission_texture: specular_transmission_texture,
+            thickness_factor: thickness,
             #[cfg(feature = "pbr_transmission_textures")]
             thickness_texture,
-            ior,
             attenuation_distance,
-            attenuation_color: LegacyColor::rgb_linear(
-                attenuation_color[0],
-                attenuation_color[1],
-                attenuation_color[2],
-            ),
-            unlit: material.unlit(),
-            alpha_mode: alpha_mode(material),
-            uv_transform,
-            ..Default::default()
+            attenuation_color,
+            ior,
+        });
+        if let Some(specular_glossiness) = material.pbr_specular_glossiness() {
+            // The diffuse color takes the place of the base color in this workflow.
+            standard_material.workflow = PbrWorkflow::SpecularGlossiness;
+            standard_material.base_color =
+                LegacyColor::rgba_linear_from_array(specular_glossiness.diffuse_factor());
+            standard_material.base_color_texture =
+                specular_glossiness.diffuse_texture().map(|info| {
+                    // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
+                    texture_handle(load_context, &info.texture())
+                });
+            standard_material.specular_color =
+                LegacyColor::rgb_linear_from_array(specular_glossiness.specular_factor());
+            standard_material.glossiness = specular_glossiness.glossiness_factor();
+            #[cfg(feature = "pbr_specular_glossiness_textures")]
+            {
+                standard_material.specular_glossiness_texture = specular_glossiness
+                    .specular_glossiness_texture()
+                    .map(|info| {
+                        // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
+                        texture_handle(load_context, &info.texture())
+                    });
+            }
+        }
+        if is_scale_inverted && !material.double_sided() {
+            standard_material.cull_mode = Some(Face::Front);
         }
+        standard_material
     })
 }
 
@@ -0,0 +1,48 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::extract_resource::ExtractResource;
+
+/// Selects a debug visualization for the PBR lighting of all 3D views.
+///
+/// Each visualization is enabled with a shader def, so the default of
+/// [`PbrDebugVisualization::None`] compiles them all out and costs nothing.
+///
+/// Changing the visualization re-specializes the pipelines of all materials, so it is meant for
+/// debugging rather than for toggling every frame.
+///
+/// ```
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_pbr::{PbrDebugSettings, PbrDebugVisualization};
+/// fn show_cascades(mut settings: ResMut<PbrDebugSettings>) {
+///     settings.visualization = PbrDebugVisualization::ShadowCascades;
+/// }
+/// # bevy_ecs::system::assert_is_system(show_cascades);
+/// ```
+#[derive(Resource, Clone, Debug, Default, ExtractResource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct PbrDebugSettings {
+    /// The active visualization. Defaults to [`PbrDebugVisualization::None`].
+    pub visualization: PbrDebugVisualization,
+}
+
+/// A debug visualization for the PBR lighting, see [`PbrDebugSettings`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum PbrDebugVisualization {
+    /// Render normally.
+    #[default]
+    None,
+    /// Tint the light of each directional light by the shadow cascade the fragment falls into.
+    ShadowCascades,
+    /// Overlay the depth slices of the clusters used to assign point and spot lights.
+    ClusterZSlices,
+    /// Overlay the number of point and spot lights in the cluster of each fragment, from green
+    /// for none to red for many.
+    ClusterLightComplexity,
+    /// Overlay a random color per cluster, to show which cluster each fragment belongs to.
+    ClusterCoherency,
+    /// Only show the light received directly from light sources.
+    DirectLight,
+    /// Only show the indirect light, from ambient light, light probes and lightmaps.
+    IndirectLight,
+}
@@ -1,6 +1,7 @@
 use crate::{
     graph::NodePbr, irradiance_volume::IrradianceVolume, prelude::EnvironmentMapLight,
-    MeshPipeline, MeshViewBindGroup, RenderViewLightProbes, ScreenSpaceAmbientOcclusionSettings,
+    skybox_ambient::SKYBOX_AMBIENT_IS_USABLE, LightingStrategy, MeshPipeline, MeshViewBindGroup,
+    PbrDebugSettings, RenderViewLightProbes, ScreenSpaceAmbientOcclusionSettings,
     ViewLightProbesUniformOffset,
 };
 use bevy_app::prelude::*;
@@ -15,6 +16,7 @@ use bevy_core_pipeline::{
 };
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
+    camera::TemporalJitter,
     extract_component::{
         ComponentUniforms, ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin,
     },
@@ -28,7 +30,9 @@ use bevy_render::{
 };
 
 use crate::{
-    MeshPipelineKey, ShadowFilteringMethod, ViewFogUniformOffset, ViewLightsUniformOffset,
+    FogSettings, MeshPipelineKey, NoShadowFilteringJitter, PointLightShadowFilteringMethod,
+    PointLightShadowMap, PointLightShadowMapMode, ShadowFilteringMethod, ShadowFilteringQuality,
+    ViewFogUniformOffset, ViewLightsUniformOffset,
 };
 
 pub struct DeferredPbrLightingPlugin;
@@ -248,6 +252,10 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
         #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
         shader_defs.push("WEBGL2".into());
 
+        if SKYBOX_AMBIENT_IS_USABLE {
+            shader_defs.push("SKYBOX_AMBIENT_IS_USABLE".into());
+        }
+
         if key.contains(MeshPipelineKey::TONEMAP_IN_SHADER) {
             shader_defs.push("TONEMAP_IN_SHADER".into());
 
@@ -289,6 +297,10 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
             shader_defs.push("IRRADIANCE_VOLUME".into());
         }
 
+        if key.contains(MeshPipelineKey::FOG_COLOR_GRADIENT) {
+            shader_defs.push("FOG_COLOR_GRADIENT".into());
+        }
+
         if key.contains(MeshPipelineKey::NORMAL_PREPASS) {
             shader_defs.push("NORMAL_PREPASS".into());
         }
@@ -314,6 +326,36 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
             shader_defs.push("SHADOW_FILTER_METHOD_JIMENEZ_14".into());
         }
 
+        if key.contains(MeshPipelineKey::POINT_LIGHT_SHADOW_FILTER_PCF) {
+            shader_defs.push("POINT_LIGHT_SHADOW_FILTER_PCF".into());
+        }
+
+        if key.contains(MeshPipelineKey::SHADOW_FILTER_JITTER) {
+            shader_defs.push("SHADOW_FILTER_JITTER".into());
+        }
+
+        if key.contains(MeshPipelineKey::SIMPLE_FORWARD_LIGHTING) {
+            shader_defs.push("SIMPLE_FORWARD_LIGHTING".into());
+        }
+
+        if key.contains(MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS) {
+            shader_defs.push("DUAL_PARABOLOID_POINT_SHADOWS".into());
+        }
+
+        if let Some(pbr_debug_shader_def) = key.pbr_debug_shader_def() {
+            shader_defs.push(pbr_debug_shader_def.into());
+        }
+
+        let shadow_filter_quality =
+            key.intersection(MeshPipelineKey::SHADOW_FILTER_QUALITY_RESERVED_BITS);
+        if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_LOW {
+            shader_defs.push("SHADOW_FILTER_QUALITY_LOW".into());
+        } else if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_MEDIUM {
+            shader_defs.push("SHADOW_FILTER_QUALITY_MEDIUM".into());
+        } else if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_HIGH {
+            shader_defs.push("SHADOW_FILTER_QUALITY_HIGH".into());
+        }
+
         #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
         shader_defs.push("SIXTEEN_BYTE_ALIGNMENT".into());
 
@@ -405,7 +447,12 @@ pub fn prepare_deferred_lighting_pipelines(
             &ExtractedView,
             Option<&Tonemapping>,
             Option<&DebandDither>,
-            Option<&ShadowFilteringMethod>,
+            (
+                Option<&ShadowFilteringMethod>,
+                Option<&ShadowFilteringQuality>,
+                Option<&PointLightShadowFilteringMethod>,
+                Has<NoShadowFilteringJitter>,
+            ),
             Has<ScreenSpaceAmbientOcclusionSettings>,
             (
                 Has<NormalPrepass>,
@@ -414,20 +461,31 @@ pub fn prepare_deferred_lighting_pipelines(
             ),
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
+            Has<TemporalJitter>,
+            Option<&FogSettings>,
         ),
         With<DeferredPrepass>,
     >,
+    point_light_shadow_map: Res<PointLightShadowMap>,
+    (pbr_debug_settings, lighting_strategy): (Res<PbrDebugSettings>, Res<LightingStrategy>),
 ) {
     for (
         entity,
         view,
         tonemapping,
         dither,
-        shadow_filter_method,
+        (
+            shadow_filter_method,
+            shadow_filter_quality,
+            point_light_shadow_filter_method,
+            no_shadow_filter_jitter,
+        ),
         ssao,
         (normal_prepass, depth_prepass, motion_vector_prepass),
         has_environment_maps,
         has_irradiance_volumes,
+        temporal_jitter,
+        fog,
     ) in &views
     {
         let mut view_key = MeshPipelineKey::from_hdr(view.hdr);
@@ -485,6 +543,10 @@ pub fn prepare_deferred_lighting_pipelines(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if fog.is_some_and(|fog| fog.color_gradient.is_some()) {
+            view_key |= MeshPipelineKey::FOG_COLOR_GRADIENT;
+        }
+
         match shadow_filter_method.unwrap_or(&ShadowFilteringMethod::default()) {
             ShadowFilteringMethod::Hardware2x2 => {
                 view_key |= MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2;
@@ -497,6 +559,36 @@ pub fn prepare_deferred_lighting_pipelines(
             }
         }
 
+        match shadow_filter_quality.unwrap_or(&ShadowFilteringQuality::default()) {
+            ShadowFilteringQuality::Low => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_LOW;
+            }
+            ShadowFilteringQuality::Medium => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_MEDIUM;
+            }
+            ShadowFilteringQuality::High => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_HIGH;
+            }
+        }
+
+        if let Some(PointLightShadowFilteringMethod::Pcf) = point_light_shadow_filter_method {
+            view_key |= MeshPipelineKey::POINT_LIGHT_SHADOW_FILTER_PCF;
+        }
+
+        if temporal_jitter && !no_shadow_filter_jitter {
+            view_key |= MeshPipelineKey::SHADOW_FILTER_JITTER;
+        }
+
+        if point_light_shadow_map.mode == PointLightShadowMapMode::DualParaboloid {
+            view_key |= MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS;
+        }
+
+        view_key |= MeshPipelineKey::from_pbr_debug_visualization(pbr_debug_settings.visualization);
+
+        if *lighting_strategy == LightingStrategy::SimpleForward {
+            view_key |= MeshPipelineKey::SIMPLE_FORWARD_LIGHTING;
+        }
+
         let pipeline_id =
             pipelines.specialize(&pipeline_cache, &deferred_lighting_layout, view_key);
 
@@ -2,19 +2,31 @@
 
 #import bevy_pbr::{
     mesh_types::MESH_FLAGS_SHADOW_RECEIVER_BIT,
-    pbr_types::{STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT, STANDARD_MATERIAL_FLAGS_UNLIT_BIT},
+    pbr_types::{
+        STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT, STANDARD_MATERIAL_FLAGS_UNLIT_BIT,
+        STANDARD_MATERIAL_FLAGS_UNCLAMPED_ROUGHNESS_BIT, STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT,
+        STANDARD_MATERIAL_FLAGS_NO_ENV_SPECULAR_BIT, STANDARD_MATERIAL_FLAGS_NO_SPECULAR_BIT,
+    },
 }
 
 // Maximum of 8 bits available
 const DEFERRED_FLAGS_UNLIT_BIT: u32                 = 1u;
 const DEFERRED_FLAGS_FOG_ENABLED_BIT: u32           = 2u;
 const DEFERRED_MESH_FLAGS_SHADOW_RECEIVER_BIT: u32  = 4u;
+const DEFERRED_FLAGS_UNCLAMPED_ROUGHNESS_BIT: u32   = 8u;
+const DEFERRED_FLAGS_NO_ENV_DIFFUSE_BIT: u32        = 16u;
+const DEFERRED_FLAGS_NO_ENV_SPECULAR_BIT: u32       = 32u;
+const DEFERRED_FLAGS_NO_SPECULAR_BIT: u32           = 64u;
 
 fn deferred_flags_from_mesh_material_flags(mesh_flags: u32, mat_flags: u32) -> u32 {
     var flags = 0u;
     flags |= u32((mesh_flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u) * DEFERRED_MESH_FLAGS_SHADOW_RECEIVER_BIT;
     flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) * DEFERRED_FLAGS_FOG_ENABLED_BIT;
     flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) != 0u) * DEFERRED_FLAGS_UNLIT_BIT;
+    flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_UNCLAMPED_ROUGHNESS_BIT) != 0u) * DEFERRED_FLAGS_UNCLAMPED_ROUGHNESS_BIT;
+    flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT) != 0u) * DEFERRED_FLAGS_NO_ENV_DIFFUSE_BIT;
+    flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_NO_ENV_SPECULAR_BIT) != 0u) * DEFERRED_FLAGS_NO_ENV_SPECULAR_BIT;
+    flags |= u32((mat_flags & STANDARD_MATERIAL_FLAGS_NO_SPECULAR_BIT) != 0u) * DEFERRED_FLAGS_NO_SPECULAR_BIT;
     return flags;
 }
 
@@ -24,6 +36,10 @@ fn mesh_material_flags_from_deferred_flags(deferred_flags: u32) -> vec2<u32> {
     mesh_flags |= u32((deferred_flags & DEFERRED_MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u) * MESH_FLAGS_SHADOW_RECEIVER_BIT;
     mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_FOG_ENABLED_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT;
     mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_UNLIT_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_UNLIT_BIT;
+    mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_UNCLAMPED_ROUGHNESS_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_UNCLAMPED_ROUGHNESS_BIT;
+    mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_NO_ENV_DIFFUSE_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT;
+    mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_NO_ENV_SPECULAR_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_NO_ENV_SPECULAR_BIT;
+    mat_flags |= u32((deferred_flags & DEFERRED_FLAGS_NO_SPECULAR_BIT) != 0u) * STANDARD_MATERIAL_FLAGS_NO_SPECULAR_BIT;
     return vec2(mesh_flags, mat_flags);
 }
 
@@ -175,14 +175,30 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         B::opaque_render_method(&self.base)
     }
 
+    fn requires_forward_rendering(&self) -> bool {
+        B::requires_forward_rendering(&self.base)
+    }
+
     fn depth_bias(&self) -> f32 {
         B::depth_bias(&self.base)
     }
 
+    fn depth_write_enabled(&self) -> Option<bool> {
+        B::depth_write_enabled(&self.base)
+    }
+
     fn reads_view_transmission_texture(&self) -> bool {
         B::reads_view_transmission_texture(&self.base)
     }
 
+    fn deferred_opaque_cutoff(&self) -> Option<f32> {
+        B::deferred_opaque_cutoff(&self.base)
+    }
+
+    fn transparent_depth_prepass(&self) -> bool {
+        B::transparent_depth_prepass(&self.base)
+    }
+
     fn prepass_vertex_shader() -> ShaderRef {
         match E::prepass_vertex_shader() {
             ShaderRef::Default => B::prepass_vertex_shader(),
@@ -223,6 +239,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
             material_layout,
             vertex_shader,
             fragment_shader,
+            prepass_enabled,
             ..
         } = pipeline.clone();
         let base_pipeline = MaterialPipeline::<B> {
@@ -230,6 +247,7 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
             material_layout,
             vertex_shader,
             fragment_shader,
+            prepass_enabled,
             marker: Default::default(),
         };
         let base_key = MaterialPipelineKey::<B> {
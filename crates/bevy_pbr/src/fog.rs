@@ -1,8 +1,12 @@
 use crate::ReflectComponent;
+use bevy_asset::Handle;
 use bevy_ecs::prelude::*;
 use bevy_math::Vec3;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::{color::LegacyColor, extract_component::ExtractComponent, prelude::Camera};
+use bevy_render::{
+    color::LegacyColor, extract_component::ExtractComponent, prelude::Camera, texture::Image,
+    view::RenderLayers,
+};
 
 /// Configures the “classic” computer graphics [distance fog](https://en.wikipedia.org/wiki/Distance_fog) effect,
 /// in which objects appear progressively more covered in atmospheric haze the further away they are from the camera.
@@ -25,6 +29,7 @@ use bevy_render::{color::LegacyColor, extract_component::ExtractComponent, prelu
 /// # use bevy_render::prelude::*;
 /// # use bevy_core_pipeline::prelude::*;
 /// # use bevy_pbr::prelude::*;
+/// # use bevy_math::prelude::*;
 /// # fn system(mut commands: Commands) {
 /// commands.spawn((
 ///     // Setup your camera as usual
@@ -35,7 +40,9 @@ use bevy_render::{color::LegacyColor, extract_component::ExtractComponent, prelu
 ///     // Add fog to the same entity
 ///     FogSettings {
 ///         color: LegacyColor::WHITE,
-///         falloff: FogFalloff::Exponential { density: 1e-3 },
+///         falloff: FogFalloff::Exponential {
+///             density: Vec3::splat(1e-3),
+///         },
 ///         ..Default::default()
 ///     },
 /// ));
@@ -46,7 +53,8 @@ use bevy_render::{color::LegacyColor, extract_component::ExtractComponent, prelu
 /// ## Material Override
 ///
 /// Once enabled for a specific camera, the fog effect can also be disabled for individual
-/// [`StandardMaterial`](crate::StandardMaterial) instances via the `fog_enabled` flag.
+/// [`StandardMaterial`](crate::StandardMaterial) instances via the `fog_enabled` flag, or
+/// for whole render layers via [`FogSettings::render_layers`].
 #[derive(Debug, Clone, Component, Reflect, ExtractComponent)]
 #[extract_component_filter(With<Camera>)]
 #[reflect(Component, Default)]
@@ -70,6 +78,56 @@ pub struct FogSettings {
 
     /// Determines which falloff mode to use, and its parameters.
     pub falloff: FogFalloff,
+
+    /// Whether the fog also covers the camera's [`Skybox`](bevy_core_pipeline::Skybox),
+    /// blending it towards [`FogSettings::color`] near the horizon.
+    ///
+    /// Fogged meshes otherwise end in a hard line against the sky. Defaults to `false`.
+    pub affects_skybox: bool,
+
+    /// How strongly the fog covers the skybox when [`FogSettings::affects_skybox`] is set,
+    /// from `0.0` (not at all) to `1.0` (fully covered at and below the horizon, fading out
+    /// towards the zenith).
+    ///
+    /// Defaults to `1.0`.
+    pub skybox_blend: f32,
+
+    /// How much screen space ambient occlusion thickens the fog, giving occluded creases and
+    /// corners a dusty look.
+    ///
+    /// Fog in front of a fragment is computed as if the fragment were further away, by a factor of
+    /// `1.0 + ambient_occlusion_density * (1.0 - ambient_occlusion)`. Only has an effect on
+    /// cameras with [`ScreenSpaceAmbientOcclusionSettings`](crate::ScreenSpaceAmbientOcclusionSettings).
+    ///
+    /// Defaults to `0.0`, which keeps fog independent from ambient occlusion.
+    pub ambient_occlusion_density: f32,
+
+    /// Only meshes on at least one of these [`RenderLayers`] are covered by the fog, so that
+    /// e.g. a first-person weapon or a HUD model rendered by the same camera can stay crisp.
+    ///
+    /// Meshes without a [`RenderLayers`] component are on layer `0`. The deferred lighting pass
+    /// can't tell meshes apart, so meshes left out of the fog are drawn with the forward renderer
+    /// even if their material uses [`OpaqueRendererMethod::Deferred`](crate::OpaqueRendererMethod).
+    ///
+    /// Defaults to [`RenderLayers::all()`], which fogs every mesh.
+    pub render_layers: RenderLayers,
+
+    /// A horizontal gradient texture that tints [`FogSettings::color`] by the distance from the
+    /// camera, e.g. for stylized skies that fade from a warm haze nearby to a cold one far away.
+    ///
+    /// The left edge of the texture is used at the camera and the right edge at
+    /// [`FogSettings::color_gradient_distance`] and beyond. The texture is sampled along its
+    /// vertical middle and multiplied with [`FogSettings::color`]. The skybox, when
+    /// [`FogSettings::affects_skybox`] is set, uses the flat color.
+    ///
+    /// Defaults to `None`, which uses the flat color.
+    pub color_gradient: Option<Handle<Image>>,
+
+    /// The distance from the camera at which the end of [`FogSettings::color_gradient`] is
+    /// reached.
+    ///
+    /// Defaults to `100.0`.
+    pub color_gradient_distance: f32,
 }
 
 /// Allows switching between different fog falloff modes, and configuring their parameters.
@@ -148,11 +206,13 @@ pub enum FogFalloff {
     /// scale. Typically, for scenes with objects in the scale of thousands of units, you might want density values
     /// in the ballpark of `0.001`. Conversely, for really small scale scenes you might want really high values of
     /// density;
-    /// - Combine the `density` parameter with the [`FogSettings`] `color`'s alpha channel for easier artistic control.
+    /// - Combine the `density` parameter with the [`FogSettings`] `color`'s alpha channel for easier artistic control;
+    /// - Give the `R`/`G`/`B` channels different densities to shift the hue of objects as they recede, e.g.
+    ///   `Vec3::new(0.02, 0.05, 0.1)` fades blue out first. Equal densities produce regular monochrome fog.
     ///
     /// ## Formula
     ///
-    /// The fog intensity for a given point in the scene is determined by the following formula:
+    /// The fog intensity for a given point in the scene is determined per color channel by the following formula:
     ///
     /// ```text
     /// let fog_intensity = 1.0 - 1.0 / (distance * density).exp();
@@ -181,8 +241,9 @@ pub enum FogFalloff {
     /// <text font-family="sans-serif" transform="translate(10 132) rotate(-90)" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="0" y="11.8636">fog intensity</tspan></text>
     /// </svg>
     Exponential {
-        /// Multiplier applied to the world distance (within the exponential fog falloff calculation).
-        density: f32,
+        /// Multiplier applied to the world distance (within the exponential fog falloff calculation),
+        /// per `R`/`G`/`B` channel.
+        density: Vec3,
     },
 
     /// A squared exponential fog falloff with a given `density`.
@@ -196,11 +257,12 @@ pub enum FogFalloff {
     ///
     /// - Use the [`FogFalloff::from_visibility_squared()`] convenience method to create an exponential squared falloff
     /// with the proper density for a desired visibility distance in world units;
-    /// - Combine the `density` parameter with the [`FogSettings`] `color`'s alpha channel for easier artistic control.
+    /// - Combine the `density` parameter with the [`FogSettings`] `color`'s alpha channel for easier artistic control;
+    /// - Like with [`FogFalloff::Exponential`], the `R`/`G`/`B` channels can have different densities.
     ///
     /// ## Formula
     ///
-    /// The fog intensity for a given point in the scene is determined by the following formula:
+    /// The fog intensity for a given point in the scene is determined per color channel by the following formula:
     ///
     /// ```text
     /// let fog_intensity = 1.0 - 1.0 / (distance * density).powi(2).exp();
@@ -229,8 +291,9 @@ pub enum FogFalloff {
     /// <text font-family="sans-serif" transform="translate(10 132) rotate(-90)" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="0" y="11.8636">fog intensity</tspan></text>
     /// </svg>
     ExponentialSquared {
-        /// Multiplier applied to the world distance (within the exponential squared fog falloff calculation).
-        density: f32,
+        /// Multiplier applied to the world distance (within the exponential squared fog falloff calculation),
+        /// per `R`/`G`/`B` channel.
+        density: Vec3,
     },
 
     /// A more general form of the [`FogFalloff::Exponential`] mode. The falloff formula is separated into
@@ -267,7 +330,7 @@ pub enum FogFalloff {
     /// # const D: f32 = 0.5;
     /// #
     /// let exponential = FogFalloff::Exponential {
-    ///     density: D,
+    ///     density: Vec3::new(D, D, D),
     /// };
     ///
     /// let atmospheric = FogFalloff::Atmospheric {
@@ -317,7 +380,7 @@ impl FogFalloff {
     /// and a given contrast threshold in the range of `0.0` to `1.0`.
     pub fn from_visibility_contrast(visibility: f32, contrast_threshold: f32) -> FogFalloff {
         FogFalloff::Exponential {
-            density: FogFalloff::koschmieder(visibility, contrast_threshold),
+            density: Vec3::splat(FogFalloff::koschmieder(visibility, contrast_threshold)),
         }
     }
 
@@ -337,7 +400,9 @@ impl FogFalloff {
         contrast_threshold: f32,
     ) -> FogFalloff {
         FogFalloff::ExponentialSquared {
-            density: (FogFalloff::koschmieder(visibility, contrast_threshold) / visibility).sqrt(),
+            density: Vec3::splat(
+                (FogFalloff::koschmieder(visibility, contrast_threshold) / visibility).sqrt(),
+            ),
         }
     }
 
@@ -462,6 +527,13 @@ impl FogFalloff {
     }
 }
 
+impl FogSettings {
+    /// Whether meshes on `render_layers` are left out of this fog.
+    pub(crate) fn excludes(&self, render_layers: &RenderLayers) -> bool {
+        !self.render_layers.intersects(render_layers)
+    }
+}
+
 impl Default for FogSettings {
     fn default() -> Self {
         FogSettings {
@@ -472,6 +544,12 @@ impl Default for FogSettings {
             },
             directional_light_color: LegacyColor::NONE,
             directional_light_exponent: 8.0,
+            affects_skybox: false,
+            skybox_blend: 1.0,
+            ambient_occlusion_density: 0.0,
+            render_layers: RenderLayers::all(),
+            color_gradient: None,
+            color_gradient_distance: 100.0,
         }
     }
 }
@@ -0,0 +1,256 @@
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::Vec3;
+use bevy_render::{color::LegacyColor, view::InheritedVisibility};
+use bevy_transform::components::GlobalTransform;
+use std::f32::consts::PI;
+
+use crate::{AmbientLight, DirectionalLight, PointLight, SpotLight};
+
+/// The lights of the world, as read by [`compute_illuminance_at`].
+///
+/// Lights that are hidden through their [`Visibility`](bevy_render::view::Visibility) are
+/// skipped, like they are when rendering.
+#[derive(SystemParam)]
+pub struct LightQuery<'w, 's> {
+    point_lights: Query<
+        'w,
+        's,
+        (
+            &'static PointLight,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+    >,
+    spot_lights: Query<
+        'w,
+        's,
+        (
+            &'static SpotLight,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+    >,
+    directional_lights: Query<'w, 's, (&'static DirectionalLight, &'static InheritedVisibility)>,
+    ambient_light: Option<Res<'w, AmbientLight>>,
+}
+
+/// Estimates the illuminance, in lux, received at `world_pos` from all lights and the
+/// [`AmbientLight`].
+///
+/// Each light contributes the illuminance of a surface facing it, using the same distance
+/// falloff, range smoothing and spot cone as the shaders, weighted by the luminance of the light
+/// color. The ambient light contributes the illuminance of a surface under a uniform sky of its
+/// brightness, averaged between the sky and ground colors of a hemisphere light.
+///
+/// This is meant for gameplay, such as deciding whether a character stands in the dark, and only
+/// approximates what is rendered:
+/// - Occlusion is ignored: walls and shadows do not block any light. Callers that need it can
+///   cast rays towards the lights themselves.
+/// - Light probes, lightmaps, emissive surfaces and spot light barn doors are not taken into
+///   account.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_pbr::{compute_illuminance_at, LightQuery};
+/// # use bevy_transform::components::GlobalTransform;
+/// # #[derive(Component)]
+/// # struct Player;
+/// fn player_visibility(lights: LightQuery, players: Query<&GlobalTransform, With<Player>>) {
+///     for transform in &players {
+///         let hidden = compute_illuminance_at(transform.translation(), &lights) < 10.0;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(player_visibility);
+/// ```
+pub fn compute_illuminance_at(world_pos: Vec3, lights: &LightQuery) -> f32 {
+    let mut illuminance = 0.0;
+
+    for (light, transform, visibility) in &lights.point_lights {
+        if !visibility.get() {
+            continue;
+        }
+        illuminance += luminance(light.color)
+            * point_light_illuminance(
+                world_pos - transform.translation(),
+                light.intensity,
+                light.range,
+                light.range_smoothing,
+            );
+    }
+
+    for (light, transform, visibility) in &lights.spot_lights {
+        if !visibility.get() {
+            continue;
+        }
+        let light_to_pos = world_pos - transform.translation();
+        // Mirrors the precomputed spot_scale and spot_offset of the shaders.
+        let cos_outer = light.outer_angle.cos();
+        let spot_scale = 1.0 / f32::max(light.inner_angle.cos() - cos_outer, 1e-4);
+        let spot_offset = -cos_outer * spot_scale;
+        let cd = transform.forward().dot(light_to_pos.normalize_or_zero());
+        let attenuation = (cd * spot_scale + spot_offset).clamp(0.0, 1.0);
+        illuminance += luminance(light.color)
+            * attenuation
+            * attenuation
+            * point_light_illuminance(
+                light_to_pos,
+                light.intensity,
+                light.range,
+                light.range_smoothing,
+            );
+    }
+
+    for (light, visibility) in &lights.directional_lights {
+        if visibility.get() {
+            illuminance += luminance(light.color) * light.illuminance;
+        }
+    }
+
+    if let Some(ambient) = &lights.ambient_light {
+        let color_luminance = match ambient.ground_color {
+            Some(ground_color) => 0.5 * (luminance(ambient.color) + luminance(ground_color)),
+            None => luminance(ambient.color),
+        };
+        // The ambient light is a luminance: a uniform sky of luminance L lights a surface with
+        // an illuminance of π L.
+        illuminance += PI * color_luminance * ambient.brightness;
+    }
+
+    illuminance
+}
+
+/// The illuminance of a point or spot light of luminous power `intensity`, in lumens, at an
+/// offset of `light_to_pos`, with the falloff of `getDistanceAttenuation` in `pbr_lighting.wgsl`.
+fn point_light_illuminance(
+    light_to_pos: Vec3,
+    intensity: f32,
+    range: f32,
+    range_smoothing: f32,
+) -> f32 {
+    let distance_square = light_to_pos.length_squared();
+    let factor = distance_square / (range * range);
+    let smooth_factor = (1.0 - factor * factor).clamp(0.0, 1.0);
+    let mut attenuation = smooth_factor * smooth_factor / distance_square.max(0.0001);
+    let range_smoothing = range_smoothing.clamp(0.0, 1.0);
+    if range_smoothing > 0.0 {
+        let range_fraction = factor.sqrt();
+        attenuation *= 1.0 - smoothstep(1.0 - range_smoothing, 1.0, range_fraction);
+    }
+    // Luminous intensity, in lumens per steradian, times the falloff.
+    intensity / (4.0 * PI) * attenuation
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// The relative luminance of a light color, so that a white light contributes its full intensity.
+fn luminance(color: LegacyColor) -> f32 {
+    let [r, g, b, _] = color.as_linear_rgba_f32();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::system::SystemState;
+
+    use super::*;
+
+    fn illuminance_at(world: &mut World, world_pos: Vec3) -> f32 {
+        let mut state = SystemState::<LightQuery>::new(world);
+        compute_illuminance_at(world_pos, &state.get(world))
+    }
+
+    #[test]
+    fn inverse_square_falloff() {
+        let near = point_light_illuminance(Vec3::X, 1000.0, 1000.0, 0.0);
+        let far = point_light_illuminance(2.0 * Vec3::X, 1000.0, 1000.0, 0.0);
+        assert!((near / (4.0 * far) - 1.0).abs() < 1e-3);
+        assert!((near - 1000.0 / (4.0 * PI)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn zero_beyond_range() {
+        assert!(point_light_illuminance(Vec3::X * 9.0, 1000.0, 10.0, 0.0) > 0.0);
+        assert_eq!(
+            point_light_illuminance(Vec3::X * 10.0, 1000.0, 10.0, 0.0),
+            0.0
+        );
+        assert_eq!(
+            point_light_illuminance(Vec3::X * 11.0, 1000.0, 10.0, 0.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn range_smoothing_fades_out() {
+        // Closer than the smoothed part of the range, nothing changes.
+        let unsmoothed = point_light_illuminance(Vec3::X * 4.0, 1000.0, 10.0, 0.0);
+        let smoothed = point_light_illuminance(Vec3::X * 4.0, 1000.0, 10.0, 0.5);
+        assert_eq!(unsmoothed, smoothed);
+
+        // Within it, the light fades out further, down to nothing at the range.
+        let unsmoothed = point_light_illuminance(Vec3::X * 7.5, 1000.0, 10.0, 0.0);
+        let smoothed = point_light_illuminance(Vec3::X * 7.5, 1000.0, 10.0, 0.5);
+        assert!(smoothed < unsmoothed);
+        assert!((smoothed / unsmoothed - 0.5).abs() < 1e-5);
+        let unsmoothed = point_light_illuminance(Vec3::X * 9.99, 1000.0, 10.0, 0.0);
+        let smoothed = point_light_illuminance(Vec3::X * 9.99, 1000.0, 10.0, 1.0);
+        assert!(smoothed < 1e-4 * unsmoothed);
+    }
+
+    #[test]
+    fn smoothstep_edges() {
+        assert_eq!(smoothstep(1.0, 2.0, 0.0), 0.0);
+        assert_eq!(smoothstep(1.0, 2.0, 1.5), 0.5);
+        assert_eq!(smoothstep(1.0, 2.0, 3.0), 1.0);
+    }
+
+    #[test]
+    fn luminance_of_colors() {
+        assert!((luminance(LegacyColor::WHITE) - 1.0).abs() < 1e-5);
+        assert_eq!(luminance(LegacyColor::BLACK), 0.0);
+        assert!(luminance(LegacyColor::GREEN) > luminance(LegacyColor::RED));
+        assert!(luminance(LegacyColor::RED) > luminance(LegacyColor::BLUE));
+    }
+
+    #[test]
+    fn spot_light_cone_cutoff() {
+        let mut world = World::new();
+        // Points down -Z, with a 45 degree outer angle.
+        world.spawn((
+            SpotLight::default(),
+            GlobalTransform::IDENTITY,
+            InheritedVisibility::VISIBLE,
+        ));
+
+        let on_axis = illuminance_at(&mut world, Vec3::new(0.0, 0.0, -2.0));
+        let expected = point_light_illuminance(
+            Vec3::new(0.0, 0.0, -2.0),
+            SpotLight::default().intensity,
+            SpotLight::default().range,
+            0.0,
+        );
+        assert!((on_axis / expected - 1.0).abs() < 1e-5);
+        assert_eq!(illuminance_at(&mut world, Vec3::new(0.0, 2.0, -1.0)), 0.0);
+        assert_eq!(illuminance_at(&mut world, Vec3::new(0.0, 0.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn hidden_lights_are_skipped() {
+        let mut world = World::new();
+        let light = world
+            .spawn((
+                PointLight::default(),
+                GlobalTransform::IDENTITY,
+                InheritedVisibility::VISIBLE,
+            ))
+            .id();
+        world.spawn((DirectionalLight::default(), InheritedVisibility::HIDDEN));
+
+        assert!(illuminance_at(&mut world, Vec3::X) > 0.0);
+        world.entity_mut(light).insert(InheritedVisibility::HIDDEN);
+        assert_eq!(illuminance_at(&mut world, Vec3::X), 0.0);
+    }
+}
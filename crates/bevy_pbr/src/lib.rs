@@ -1,12 +1,15 @@
 // FIXME(3492): remove once docs are ready
 #![allow(missing_docs)]
 
+pub mod light_billboard;
 pub mod wireframe;
 
 mod bundle;
+mod debug;
 pub mod deferred;
 mod extended_material;
 mod fog;
+mod illuminance;
 mod light;
 mod light_probe;
 mod lightmap;
@@ -18,8 +21,10 @@ mod render;
 mod ssao;
 
 pub use bundle::*;
+pub use debug::*;
 pub use extended_material::*;
 pub use fog::*;
+pub use illuminance::*;
 pub use light::*;
 pub use light_probe::*;
 pub use lightmap::*;
@@ -41,11 +46,12 @@ pub mod prelude {
         light::{light_consts, AmbientLight, DirectionalLight, PointLight, SpotLight},
         light_probe::{
             environment_map::{EnvironmentMapLight, ReflectionProbeBundle},
+            skybox_ambient::SkyboxAmbientLight,
             LightProbe,
         },
         material::{Material, MaterialPlugin},
-        parallax::ParallaxMappingMethod,
-        pbr_material::StandardMaterial,
+        parallax::{ParallaxDepthScale, ParallaxMappingMethod},
+        pbr_material::{AlphaMaskThreshold, StandardMaterial},
         ssao::ScreenSpaceAmbientOcclusionPlugin,
     };
 }
@@ -60,6 +66,10 @@ pub mod graph {
         /// Label for the screen space ambient occlusion render node.
         ScreenSpaceAmbientOcclusion,
         DeferredLightingPass,
+        /// Label for the node that projects the skybox onto spherical harmonics for ambient light.
+        SkyboxAmbient,
+        /// Label for the node that assigns lights to clusters on the GPU.
+        GpuClustering,
     }
 }
 
@@ -75,9 +85,10 @@ use bevy_render::{
     extract_resource::ExtractResourcePlugin,
     prelude::LegacyColor,
     render_asset::prepare_assets,
-    render_graph::RenderGraph,
+    render_graph::{RenderGraph, RenderGraphApp, ViewNodeRunner},
     render_phase::sort_phase_system,
-    render_resource::Shader,
+    render_resource::{BufferBindingType, Shader},
+    renderer::RenderDevice,
     texture::Image,
     view::VisibilitySystems,
     ExtractSchedule, Render, RenderApp, RenderSet,
@@ -114,6 +125,11 @@ pub struct PbrPlugin {
     pub prepass_enabled: bool,
     /// Controls if [`DeferredPbrLightingPlugin`] is added.
     pub add_default_deferred_lighting_plugin: bool,
+    /// The material stored at the default [`Handle<StandardMaterial>`].
+    ///
+    /// This is used as a fallback for meshes whose material is missing, and defaults to an
+    /// unlit magenta material so that such meshes are easy to spot.
+    pub default_material: StandardMaterial,
 }
 
 impl Default for PbrPlugin {
@@ -121,6 +137,11 @@ impl Default for PbrPlugin {
         Self {
             prepass_enabled: true,
             add_default_deferred_lighting_plugin: true,
+            default_material: StandardMaterial {
+                base_color: LegacyColor::rgb(1.0, 0.0, 0.5),
+                unlit: true,
+                ..Default::default()
+            },
         }
     }
 }
@@ -207,6 +228,12 @@ impl Plugin for PbrPlugin {
             Shader::from_wgsl
         );
         load_internal_asset!(app, PBR_SHADER_HANDLE, "render/pbr.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            GPU_CLUSTERING_SHADER_HANDLE,
+            "render/gpu_clustering.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(
             app,
             PBR_PREPASS_FUNCTIONS_SHADER_HANDLE,
@@ -239,6 +266,8 @@ impl Plugin for PbrPlugin {
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
             .register_type::<ClusterConfig>()
+            .register_type::<ClusteringBackend>()
+            .register_type::<LightingStrategy>()
             .register_type::<ClusterFarZMode>()
             .register_type::<ClusterZConfig>()
             .register_type::<CubemapVisibleEntities>()
@@ -248,18 +277,35 @@ impl Plugin for PbrPlugin {
             .register_type::<NotShadowReceiver>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
+            .register_type::<PointLightShadowMapMode>()
+            .register_type::<ShadowsEnabled>()
+            .register_type::<MaxDirectionalLights>()
             .register_type::<SpotLight>()
             .register_type::<FogSettings>()
             .register_type::<FogFalloff>()
             .register_type::<ShadowFilteringMethod>()
+            .register_type::<ShadowFilteringQuality>()
+            .register_type::<PointLightShadowFilteringMethod>()
+            .register_type::<NoShadowFilteringJitter>()
             .register_type::<ParallaxMappingMethod>()
+            .register_type::<PbrWorkflow>()
+            .register_type::<ParallaxDepthScale>()
+            .register_type::<AlphaMaskThreshold>()
             .register_type::<OpaqueRendererMethod>()
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisiblePointLights>()
+            .init_resource::<ClusteringBackend>()
+            .init_resource::<LightingStrategy>()
             .init_resource::<DirectionalLightShadowMap>()
+            .init_resource::<DirectionalShadowMapReadbacks>()
+            .init_resource::<ShadowMapRefresh>()
             .init_resource::<PointLightShadowMap>()
+            .init_resource::<ShadowsEnabled>()
+            .init_resource::<MaxDirectionalLights>()
             .register_type::<DefaultOpaqueRendererMethod>()
             .init_resource::<DefaultOpaqueRendererMethod>()
+            .register_type::<PbrDebugSettings>()
+            .init_resource::<PbrDebugSettings>()
             .add_plugins((
                 MeshRenderPlugin,
                 MaterialPlugin::<StandardMaterial> {
@@ -268,9 +314,15 @@ impl Plugin for PbrPlugin {
                 },
                 ScreenSpaceAmbientOcclusionPlugin,
                 ExtractResourcePlugin::<AmbientLight>::default(),
+                ExtractComponentPlugin::<AmbientLight>::default(),
                 FogPlugin,
                 ExtractResourcePlugin::<DefaultOpaqueRendererMethod>::default(),
+                ExtractResourcePlugin::<PbrDebugSettings>::default(),
+                ExtractResourcePlugin::<LightingStrategy>::default(),
                 ExtractComponentPlugin::<ShadowFilteringMethod>::default(),
+                ExtractComponentPlugin::<ShadowFilteringQuality>::default(),
+                ExtractComponentPlugin::<PointLightShadowFilteringMethod>::default(),
+                ExtractComponentPlugin::<NoShadowFilteringJitter>::default(),
                 LightmapPlugin,
                 LightProbePlugin,
             ))
@@ -329,17 +381,15 @@ impl Plugin for PbrPlugin {
                 ),
             );
 
+        app.add_systems(PostUpdate, warn_on_missing_vertex_attributes);
+
         if self.add_default_deferred_lighting_plugin {
             app.add_plugins(DeferredPbrLightingPlugin);
         }
 
         app.world.resource_mut::<Assets<StandardMaterial>>().insert(
             Handle::<StandardMaterial>::default(),
-            StandardMaterial {
-                base_color: LegacyColor::rgb(1.0, 0.0, 0.5),
-                unlit: true,
-                ..Default::default()
-            },
+            self.default_material.clone(),
         );
 
         let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
@@ -348,7 +398,15 @@ impl Plugin for PbrPlugin {
 
         // Extract the required data from the main world
         render_app
-            .add_systems(ExtractSchedule, (extract_clusters, extract_lights))
+            .add_systems(
+                ExtractSchedule,
+                (
+                    extract_clusters,
+                    extract_lights,
+                    extract_directional_shadow_map_readbacks,
+                    extract_shadow_map_refresh,
+                ),
+            )
             .add_systems(
                 Render,
                 (
@@ -357,9 +415,14 @@ impl Plugin for PbrPlugin {
                         .after(prepare_assets::<Image>),
                     sort_phase_system::<Shadow>.in_set(RenderSet::PhaseSort),
                     prepare_clusters.in_set(RenderSet::PrepareResources),
+                    collect_shadow_map_readbacks
+                        .in_set(RenderSet::Cleanup)
+                        .before(World::clear_entities),
                 ),
             )
-            .init_resource::<LightMeta>();
+            .init_resource::<LightMeta>()
+            .init_resource::<ExtractedDirectionalShadowMapReadbacks>()
+            .init_resource::<ExtractedShadowMapRefresh>();
 
         let shadow_pass_node = ShadowPassNode::new(&mut render_app.world);
         let mut graph = render_app.world.resource_mut::<RenderGraph>();
@@ -386,5 +449,41 @@ impl Plugin for PbrPlugin {
         render_app
             .init_resource::<ShadowSamplers>()
             .init_resource::<GlobalLightMeta>();
+
+        // Assigning lights to clusters on the GPU needs compute shaders and storage
+        // buffers; without them, `ClusteringBackend::Gpu` falls back to the CPU.
+        let render_device = render_app.world.resource::<RenderDevice>();
+        if !gpu_clustering_is_supported(render_device)
+            || !matches!(
+                render_device
+                    .get_supported_read_only_binding_type(CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT),
+                BufferBindingType::Storage { .. }
+            )
+        {
+            return;
+        }
+
+        render_app
+            .init_resource::<GpuClusteringPipeline>()
+            .init_resource::<GpuClusteringBuffers>()
+            .add_systems(ExtractSchedule, extract_gpu_clusters)
+            .add_systems(
+                Render,
+                prepare_gpu_clusters
+                    .in_set(RenderSet::PrepareResources)
+                    .after(prepare_lights),
+            )
+            .add_render_graph_node::<ViewNodeRunner<GpuClusteringNode>>(
+                Core3d,
+                NodePbr::GpuClustering,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndPrepasses,
+                    NodePbr::GpuClustering,
+                    Node3d::StartMainPass,
+                ),
+            );
     }
 }
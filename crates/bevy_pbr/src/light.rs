@@ -1,4 +1,10 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::prelude::*;
@@ -102,13 +108,53 @@ pub struct PointLight {
     /// Luminous power in lumens, representing the amount of light emitted by this source in all directions.
     pub intensity: f32,
     pub range: f32,
+    /// The fraction of `range`, from `0.0` to `1.0`, at its end over which the light's intensity
+    /// smoothly fades to zero, hiding the ring that can appear where its influence ends.
+    ///
+    /// At `0.0`, only the standard inverse-square window is applied. The fraction is rounded to a
+    /// 255th. Defaults to `0.0`.
+    pub range_smoothing: f32,
     pub radius: f32,
     pub shadows_enabled: bool,
+    /// A bias moving the fragment towards the light before it is compared against the shadow
+    /// map. Raise it if lit surfaces shadow themselves (shadow acne), lower it if shadows detach
+    /// from their casters (peter-panning).
+    ///
+    /// Defaults to [`PointLight::DEFAULT_SHADOW_DEPTH_BIAS`].
     pub shadow_depth_bias: f32,
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it can be small close to the camera and gets larger further
     /// away.
+    ///
+    /// Defaults to [`PointLight::DEFAULT_SHADOW_NORMAL_BIAS`].
     pub shadow_normal_bias: f32,
+    /// The [`RenderLayers`] of the meshes that cast shadows from this light.
+    ///
+    /// Independent of the layers the light illuminates, e.g. to have characters cast shadows
+    /// while scenery doesn't. A mesh only casts a shadow if it is on one of these layers and
+    /// on one of the light's own [`RenderLayers`]. Defaults to [`RenderLayers::all`].
+    pub shadow_caster_layers: RenderLayers,
+    /// Render the shadow map of this light only every `shadow_update_interval` frames, reusing
+    /// the previous one in between.
+    ///
+    /// This saves the cost of re-rendering shadows for lights in the background of scenes with
+    /// many shadowed lights. Lights with the same interval are staggered over the frames. The
+    /// shadow map is still updated right away when the light moves or its shape changes, but not
+    /// when shadow casters move, so their shadows lag behind by up to `shadow_update_interval - 1`
+    /// frames. Request a [`ShadowMapRefresh`] to update every shadow map in the next frame.
+    ///
+    /// Defaults to `1`, which updates the shadow map every frame.
+    pub shadow_update_interval: u32,
+    /// Whether this light contributes diffuse lighting. Set to `false`, together with
+    /// `affects_specular`, for a light that only adds highlights, e.g. a rim light.
+    ///
+    /// Defaults to `true`.
+    pub affects_diffuse: bool,
+    /// Whether this light contributes specular highlights. Set to `false` for a light that only
+    /// adds diffuse lighting, e.g. a fill light.
+    ///
+    /// Defaults to `true`.
+    pub affects_specular: bool,
 }
 
 impl Default for PointLight {
@@ -120,10 +166,15 @@ impl Default for PointLight {
             // this would be way too bright.
             intensity: 1_000_000.0,
             range: 20.0,
+            range_smoothing: 0.0,
             radius: 0.0,
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_caster_layers: RenderLayers::all(),
+            shadow_update_interval: 1,
+            affects_diffuse: true,
+            affects_specular: true,
         }
     }
 }
@@ -133,18 +184,42 @@ impl PointLight {
     pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.6;
 }
 
+/// Controls the resolution and layout of [`PointLight`] shadow maps.
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct PointLightShadowMap {
+    /// The width and height of each face of the shadow map, in texels.
     pub size: usize,
+    /// How the space around each light is projected onto the faces of its shadow map.
+    pub mode: PointLightShadowMapMode,
 }
 
 impl Default for PointLightShadowMap {
     fn default() -> Self {
-        Self { size: 1024 }
+        Self {
+            size: 1024,
+            mode: PointLightShadowMapMode::default(),
+        }
     }
 }
 
+/// The projection used by [`PointLight`] shadow maps.
+///
+/// Set through [`PointLightShadowMap::mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum PointLightShadowMapMode {
+    /// Renders each light into the six faces of a cubemap.
+    #[default]
+    Cubemap,
+    /// Renders each light into two paraboloid maps, one per hemisphere.
+    ///
+    /// This uses a third of the memory of a cubemap and only two shadow passes per light
+    /// instead of six. In exchange, the paraboloid warp is applied per vertex, so coarsely
+    /// tessellated casters can show distorted shadows, and there may be a visible seam where
+    /// the two hemispheres meet.
+    DualParaboloid,
+}
+
 /// A light that emits light in a given direction from a central point.
 /// Behaves like a point light in a perfectly absorbent housing that
 /// shines light only in a given direction. The direction is taken from
@@ -156,12 +231,25 @@ pub struct SpotLight {
     /// Luminous power in lumens, representing the amount of light emitted by this source in all directions.
     pub intensity: f32,
     pub range: f32,
+    /// The fraction of `range`, from `0.0` to `1.0`, at its end over which the light's intensity
+    /// smoothly fades to zero, hiding the ring that can appear where its influence ends.
+    ///
+    /// At `0.0`, only the standard inverse-square window is applied. The fraction is rounded to a
+    /// 255th. Defaults to `0.0`.
+    pub range_smoothing: f32,
     pub radius: f32,
     pub shadows_enabled: bool,
+    /// A bias moving the fragment towards the light before it is compared against the shadow
+    /// map. Raise it if lit surfaces shadow themselves (shadow acne), lower it if shadows detach
+    /// from their casters (peter-panning).
+    ///
+    /// Defaults to [`SpotLight::DEFAULT_SHADOW_DEPTH_BIAS`].
     pub shadow_depth_bias: f32,
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it can be small close to the camera and gets larger further
     /// away.
+    ///
+    /// Defaults to [`SpotLight::DEFAULT_SHADOW_NORMAL_BIAS`].
     pub shadow_normal_bias: f32,
     /// Angle defining the distance from the spot light direction to the outer limit
     /// of the light's cone of effect.
@@ -174,6 +262,41 @@ pub struct SpotLight {
     /// Light is attenuated from `inner_angle` to `outer_angle` to give a smooth falloff.
     /// `inner_angle` should be <= `outer_angle`
     pub inner_angle: f32,
+    /// Angles by which "barn doors" on each side cut into the light's cone, in the order
+    /// left, right, bottom, top.
+    ///
+    /// Sides are relative to the light's local X (left/right) and Y (bottom/top) axes, and each
+    /// angle is measured inward from `outer_angle`, so `0.0` leaves that side of the cone uncut
+    /// and `outer_angle` cuts it back to the spot light direction. The cut has a hard edge, and
+    /// its position is rounded to a 255th of `outer_angle`.
+    pub barn_doors: [f32; 4],
+    /// The [`RenderLayers`] of the meshes that cast shadows from this light.
+    ///
+    /// Independent of the layers the light illuminates, e.g. to have characters cast shadows
+    /// while scenery doesn't. A mesh only casts a shadow if it is on one of these layers and
+    /// on one of the light's own [`RenderLayers`]. Defaults to [`RenderLayers::all`].
+    pub shadow_caster_layers: RenderLayers,
+    /// Render the shadow map of this light only every `shadow_update_interval` frames, reusing
+    /// the previous one in between.
+    ///
+    /// This saves the cost of re-rendering shadows for lights in the background of scenes with
+    /// many shadowed lights. Lights with the same interval are staggered over the frames. The
+    /// shadow map is still updated right away when the light moves or its shape changes, but not
+    /// when shadow casters move, so their shadows lag behind by up to `shadow_update_interval - 1`
+    /// frames. Request a [`ShadowMapRefresh`] to update every shadow map in the next frame.
+    ///
+    /// Defaults to `1`, which updates the shadow map every frame.
+    pub shadow_update_interval: u32,
+    /// Whether this light contributes diffuse lighting. Set to `false`, together with
+    /// `affects_specular`, for a light that only adds highlights, e.g. a rim light.
+    ///
+    /// Defaults to `true`.
+    pub affects_diffuse: bool,
+    /// Whether this light contributes specular highlights. Set to `false` for a light that only
+    /// adds diffuse lighting, e.g. a fill light.
+    ///
+    /// Defaults to `true`.
+    pub affects_specular: bool,
 }
 
 impl SpotLight {
@@ -191,12 +314,18 @@ impl Default for SpotLight {
             // this would be way too bright.
             intensity: 1_000_000.0,
             range: 20.0,
+            range_smoothing: 0.0,
             radius: 0.0,
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
             inner_angle: 0.0,
             outer_angle: std::f32::consts::FRAC_PI_4,
+            barn_doors: [0.0; 4],
+            shadow_caster_layers: RenderLayers::all(),
+            shadow_update_interval: 1,
+            affects_diffuse: true,
+            affects_specular: true,
         }
     }
 }
@@ -245,9 +374,13 @@ impl Default for SpotLight {
 /// ```
 /// # use bevy_app::prelude::*;
 /// # use bevy_pbr::DirectionalLightShadowMap;
+/// # use bevy_utils::default;
 /// App::new()
-///     .insert_resource(DirectionalLightShadowMap { size: 2048 });
+///     .insert_resource(DirectionalLightShadowMap { size: 2048, ..default() });
 /// ```
+///
+/// Only a limited number of directional lights can light a scene at once. See
+/// [`MaxDirectionalLights`] for how the lights are chosen when there are more.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct DirectionalLight {
@@ -260,10 +393,33 @@ pub struct DirectionalLight {
     /// area.
     pub illuminance: f32,
     pub shadows_enabled: bool,
+    /// A bias moving the fragment towards the light before it is compared against the shadow
+    /// map. Raise it if lit surfaces shadow themselves (shadow acne), lower it if shadows detach
+    /// from their casters (peter-panning).
+    ///
+    /// Defaults to [`DirectionalLight::DEFAULT_SHADOW_DEPTH_BIAS`].
     pub shadow_depth_bias: f32,
     /// A bias applied along the direction of the fragment's surface normal. It is scaled to the
     /// shadow map's texel size so that it is automatically adjusted to the orthographic projection.
+    ///
+    /// Defaults to [`DirectionalLight::DEFAULT_SHADOW_NORMAL_BIAS`].
     pub shadow_normal_bias: f32,
+    /// The [`RenderLayers`] of the meshes that cast shadows from this light.
+    ///
+    /// Independent of the layers the light illuminates, e.g. to have characters cast shadows
+    /// while scenery doesn't. A mesh only casts a shadow if it is on one of these layers and
+    /// on one of the light's own [`RenderLayers`]. Defaults to [`RenderLayers::all`].
+    pub shadow_caster_layers: RenderLayers,
+    /// Whether this light contributes diffuse lighting. Set to `false`, together with
+    /// `affects_specular`, for a light that only adds highlights, e.g. a rim light.
+    ///
+    /// Defaults to `true`.
+    pub affects_diffuse: bool,
+    /// Whether this light contributes specular highlights. Set to `false` for a light that only
+    /// adds diffuse lighting, e.g. a fill light.
+    ///
+    /// Defaults to `true`.
+    pub affects_specular: bool,
 }
 
 impl Default for DirectionalLight {
@@ -274,6 +430,9 @@ impl Default for DirectionalLight {
             shadows_enabled: false,
             shadow_depth_bias: Self::DEFAULT_SHADOW_DEPTH_BIAS,
             shadow_normal_bias: Self::DEFAULT_SHADOW_NORMAL_BIAS,
+            shadow_caster_layers: RenderLayers::all(),
+            affects_diffuse: true,
+            affects_specular: true,
         }
     }
 }
@@ -287,12 +446,194 @@ impl DirectionalLight {
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct DirectionalLightShadowMap {
+    /// The width and height of each shadow map, in texels. Defaults to `2048`.
+    ///
+    /// Shadow cascades and [`SpotLight`] shadow maps are stored in a shared texture array with
+    /// layers of this size. Each spot light takes a whole layer, while cascades rendered at a lower
+    /// resolution with `cascade_sizes` share layers.
     pub size: usize,
+    /// The width and height of each cascade's shadow map, in texels, from nearest to furthest,
+    /// for all lights. Cascades without an entry use `size`. Defaults to empty.
+    ///
+    /// A light's [`CascadeShadowConfig::cascade_sizes`] takes precedence over this, which also
+    /// describes how the sizes are rounded and how they save memory.
+    pub cascade_sizes: Vec<usize>,
 }
 
 impl Default for DirectionalLightShadowMap {
     fn default() -> Self {
-        Self { size: 2048 }
+        Self {
+            size: 2048,
+            cascade_sizes: Vec::new(),
+        }
+    }
+}
+
+/// A callback receiving the depth of a directional light shadow cascade read back to the CPU.
+pub type CascadeShadowMapReadbackFn = Box<dyn FnOnce(CascadeShadowMapReadback) + Send + Sync>;
+
+/// The depth of a single [`DirectionalLight`] shadow cascade, as read back to the CPU by a
+/// [`DirectionalShadowMapReadbacks::request`].
+#[derive(Clone, Debug)]
+pub struct CascadeShadowMapReadback {
+    /// The world space to clip space transform of the shadow camera that rendered this cascade.
+    ///
+    /// A texel at `(x, y)` with depth `d` corresponds to the clip space position
+    /// `(2 * (x + 0.5) / size - 1, 1 - 2 * (y + 0.5) / size, d)`.
+    pub view_projection: Mat4,
+    /// The width and height of the shadow map, in texels.
+    pub size: u32,
+    /// The depth of each texel, row by row from the top. Like the rest of Bevy this uses
+    /// reversed-z, so `1.0` is at the near plane and `0.0` at the far plane or where nothing was
+    /// drawn.
+    pub depth: Vec<f32>,
+}
+
+/// Requests the depth of [`DirectionalLight`] shadow cascades to be copied back to the CPU,
+/// e.g. to bake shadows or visibility data offline.
+///
+/// Each request is fulfilled once, with the cascade rendered in the frame it was issued; nothing
+/// is read back in the frames after that. The light must have
+/// [`shadows_enabled`](DirectionalLight::shadows_enabled) set, otherwise the request is dropped
+/// with a warning.
+///
+/// The cascade is rendered a second time in that frame, into a texture of its own that is then
+/// copied back, so requests are not free and shouldn't be issued every frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_pbr::DirectionalShadowMapReadbacks;
+/// fn bake(
+///     readbacks: Res<DirectionalShadowMapReadbacks>,
+///     sun: Query<Entity, With<bevy_pbr::DirectionalLight>>,
+///     camera: Query<Entity, With<bevy_render::camera::Camera>>,
+/// ) {
+///     readbacks.request(sun.single(), camera.single(), 0, |readback| {
+///         println!("read {} texels", readback.depth.len());
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(bake);
+/// ```
+#[derive(Resource, Default)]
+pub struct DirectionalShadowMapReadbacks {
+    pub(crate) requests: Mutex<Vec<DirectionalShadowMapReadbackRequest>>,
+}
+
+pub(crate) struct DirectionalShadowMapReadbackRequest {
+    pub(crate) light: Entity,
+    pub(crate) view: Entity,
+    pub(crate) cascade_index: usize,
+    pub(crate) callback: CascadeShadowMapReadbackFn,
+}
+
+impl DirectionalShadowMapReadbacks {
+    /// Reads back cascade `cascade_index` of the shadow map `light` renders for the camera
+    /// `view`, calling `callback` with it once it is available on the CPU.
+    ///
+    /// The callback runs on the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool),
+    /// usually a frame or two after the request.
+    pub fn request(
+        &self,
+        light: Entity,
+        view: Entity,
+        cascade_index: usize,
+        callback: impl FnOnce(CascadeShadowMapReadback) + Send + Sync + 'static,
+    ) {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(DirectionalShadowMapReadbackRequest {
+                light,
+                view,
+                cascade_index,
+                callback: Box::new(callback),
+            });
+    }
+}
+
+/// Requests every shadow map to be re-rendered in the next frame, e.g. after teleporting the
+/// camera or streaming in geometry.
+///
+/// This renders the shadow maps of lights with a
+/// [`shadow_update_interval`](PointLight::shadow_update_interval) above 1 even if they are not
+/// due, and restarts the per-frame rotation of the shadow filter (see
+/// [`NoShadowFilteringJitter`]) for TAA to converge again from scratch. The request is consumed
+/// by the next frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_pbr::ShadowMapRefresh;
+/// fn teleport(refresh: Res<ShadowMapRefresh>) {
+///     refresh.request();
+/// }
+/// # bevy_ecs::system::assert_is_system(teleport);
+/// ```
+#[derive(Resource, Default)]
+pub struct ShadowMapRefresh {
+    requested: AtomicBool,
+}
+
+impl ShadowMapRefresh {
+    /// Re-renders every shadow map in the next frame.
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether a refresh was requested since the last call, and clears the request.
+    pub(crate) fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Limits how many [`DirectionalLight`]s light the scene.
+///
+/// When more directional lights are visible than this, the brightest ones by
+/// [`illuminance`](DirectionalLight::illuminance) are kept, ties are broken by entity so that the
+/// selection is stable, and a warning is logged once. Shadow casting doesn't affect which lights
+/// are kept.
+///
+/// The limit can't exceed [`MAX_DIRECTIONAL_LIGHTS`], the number of directional lights the
+/// shaders have room for, and is clamped to it. Lowering it is a cheap way to trade lighting
+/// accuracy for performance.
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct MaxDirectionalLights(pub usize);
+
+impl Default for MaxDirectionalLights {
+    fn default() -> Self {
+        Self(MAX_DIRECTIONAL_LIGHTS)
+    }
+}
+
+/// Globally enables or disables shadows for each type of light.
+///
+/// A light casts shadows only if its own `shadows_enabled` is set and shadows are enabled
+/// for its type here. This makes it cheap to turn off, for example, all point light
+/// shadows for a quality setting, without touching every light and triggering change
+/// detection on them.
+///
+/// All light types have shadows enabled by default.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ShadowsEnabled {
+    /// Whether [`PointLight`]s with [`shadows_enabled`](PointLight::shadows_enabled) set cast
+    /// shadows. Defaults to `true`.
+    pub point_lights: bool,
+    /// Whether [`SpotLight`]s with [`shadows_enabled`](SpotLight::shadows_enabled) set cast
+    /// shadows. Defaults to `true`.
+    pub spot_lights: bool,
+    /// Whether [`DirectionalLight`]s with [`shadows_enabled`](DirectionalLight::shadows_enabled)
+    /// set cast shadows. Defaults to `true`.
+    pub directional_lights: bool,
+}
+
+impl Default for ShadowsEnabled {
+    fn default() -> Self {
+        Self {
+            point_lights: true,
+            spot_lights: true,
+            directional_lights: true,
+        }
     }
 }
 
@@ -318,6 +659,66 @@ pub struct CascadeShadowConfig {
     pub overlap_proportion: f32,
     /// The (positive) distance to the near boundary of the first cascade.
     pub minimum_distance: f32,
+    /// The (positive) distance by which the near plane of each cascade's shadow projection is
+    /// pulled back toward the light.
+    ///
+    /// A cascade's projection only spans the depth of its slice of the view frustum, as seen from
+    /// the light. Pulling its near plane back lets occluders between the light and that slice,
+    /// e.g. tall objects or ones behind the camera, still cast shadows into it, at the cost of
+    /// some shadow map depth precision.
+    pub near_plane_pullback: f32,
+    /// A multiplier applied to the light's contribution in each cascade, from nearest to
+    /// furthest. Cascades without an entry use `1.0`.
+    ///
+    /// This is an artistic control, e.g. to slightly darken distant cascades to fake indirect
+    /// lighting falloff. Fragments beyond the last cascade use the last cascade's multiplier, and
+    /// the multipliers are blended across the overlap between cascades.
+    pub intensity_multipliers: Vec<f32>,
+    /// The (positive) distance before the far boundary of the last cascade over which shadows
+    /// fade out, instead of ending abruptly.
+    ///
+    /// `0.0` disables the fade.
+    pub fade_distance: f32,
+    /// Whether to snap each cascade to a stable grid of shadow map texels.
+    ///
+    /// When enabled, the size of each cascade is rounded up to a whole number of world units and
+    /// its position is snapped to texel increments, so shadow edges don't shimmer as the camera
+    /// moves or rotates. This makes cascades cover slightly more area than strictly needed, which
+    /// lowers the effective shadow resolution a little. Disabling it fits each cascade tightly to
+    /// the view frustum slice instead.
+    pub stabilize: bool,
+    /// The width and height of each cascade's shadow map, in texels, from nearest to furthest.
+    /// Cascades without an entry use [`DirectionalLightShadowMap::cascade_sizes`], or
+    /// [`DirectionalLightShadowMap::size`] if that has no entry either.
+    ///
+    /// This lets distant cascades, which cover few pixels on screen, use a lower resolution than
+    /// near ones to save memory. Entries are clamped to [`DirectionalLightShadowMap::size`] and
+    /// rounded up to it divided by a power of two, and smaller cascades share layers of the shadow
+    /// map texture array. E.g. with the default size of `2048`, `vec![2048, 1024, 1024, 512]`
+    /// takes two layers instead of four.
+    pub cascade_sizes: Vec<usize>,
+}
+
+impl CascadeShadowConfig {
+    /// Returns the width and height, in texels, of the shadow map of the cascade at
+    /// `cascade_index`, after rounding its entry in
+    /// [`cascade_sizes`](CascadeShadowConfig::cascade_sizes).
+    pub fn cascade_size(
+        &self,
+        cascade_index: usize,
+        shadow_map: &DirectionalLightShadowMap,
+    ) -> usize {
+        let size = self
+            .cascade_sizes
+            .get(cascade_index)
+            .or_else(|| shadow_map.cascade_sizes.get(cascade_index))
+            .map_or(shadow_map.size, |&size| size.min(shadow_map.size));
+        let mut rounded_size = shadow_map.size;
+        while rounded_size / 2 >= size.max(1) {
+            rounded_size /= 2;
+        }
+        rounded_size
+    }
 }
 
 impl Default for CascadeShadowConfig {
@@ -375,6 +776,21 @@ pub struct CascadeShadowConfigBuilder {
     /// The overlap is used to make the transition from one cascade's shadow map to the next
     /// less abrupt by blending between both shadow maps.
     pub overlap_proportion: f32,
+    /// The distance by which the near plane of each cascade's shadow projection is pulled back
+    /// toward the light. Defaults to `0.0`, i.e. no pullback.
+    pub near_plane_pullback: f32,
+    /// A multiplier applied to the light's contribution in each cascade, from nearest to
+    /// furthest. Cascades without an entry use `1.0`. Defaults to empty, i.e. unchanged lighting.
+    pub intensity_multipliers: Vec<f32>,
+    /// The distance before `maximum_distance` over which shadows fade out. Defaults to `0.0`,
+    /// i.e. shadows end abruptly at `maximum_distance`.
+    pub fade_distance: f32,
+    /// Whether to snap cascades to shadow map texels to avoid shimmering. Defaults to `true`.
+    pub stabilize: bool,
+    /// The shadow map size of each cascade, from nearest to furthest, see
+    /// [`CascadeShadowConfig::cascade_sizes`]. Cascades without an entry use
+    /// [`DirectionalLightShadowMap::cascade_sizes`]. Defaults to empty.
+    pub cascade_sizes: Vec<usize>,
 }
 
 impl CascadeShadowConfigBuilder {
@@ -405,6 +821,26 @@ impl CascadeShadowConfigBuilder {
             "overlap_proportion must be in [0.0, 1.0) but was {}",
             self.overlap_proportion
         );
+        assert!(
+            self.near_plane_pullback >= 0.0,
+            "near_plane_pullback must be non-negative, but was {}",
+            self.near_plane_pullback
+        );
+        assert!(
+            self.intensity_multipliers.len() <= self.num_cascades,
+            "intensity_multipliers must have at most num_cascades entries, but had {}",
+            self.intensity_multipliers.len()
+        );
+        assert!(
+            self.fade_distance >= 0.0,
+            "fade_distance must be non-negative, but was {}",
+            self.fade_distance
+        );
+        assert!(
+            self.cascade_sizes.len() <= self.num_cascades,
+            "cascade_sizes must have at most num_cascades entries, but had {}",
+            self.cascade_sizes.len()
+        );
         CascadeShadowConfig {
             bounds: calculate_cascade_bounds(
                 self.num_cascades,
@@ -413,6 +849,11 @@ impl CascadeShadowConfigBuilder {
             ),
             overlap_proportion: self.overlap_proportion,
             minimum_distance: self.minimum_distance,
+            near_plane_pullback: self.near_plane_pullback,
+            intensity_multipliers: self.intensity_multipliers.clone(),
+            fade_distance: self.fade_distance,
+            stabilize: self.stabilize,
+            cascade_sizes: self.cascade_sizes.clone(),
         }
     }
 }
@@ -431,6 +872,11 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 100.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                near_plane_pullback: 0.0,
+                intensity_multipliers: Vec::new(),
+                fade_distance: 0.0,
+                stabilize: true,
+                cascade_sizes: Vec::new(),
             }
         } else {
             Self {
@@ -439,6 +885,11 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 1000.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                near_plane_pullback: 0.0,
+                intensity_multipliers: Vec::new(),
+                fade_distance: 0.0,
+                stabilize: true,
+                cascade_sizes: Vec::new(),
             }
         }
     }
@@ -535,9 +986,11 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
 
                     calculate_cascade(
                         corners,
-                        directional_light_shadow_map.size as f32,
+                        cascades_config.cascade_size(idx, &directional_light_shadow_map) as f32,
                         light_to_world,
                         camera_to_light_view,
+                        cascades_config.near_plane_pullback,
+                        cascades_config.stabilize,
                     )
                 })
                 .collect();
@@ -549,11 +1002,18 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
 /// Returns a [`Cascade`] for the frustum defined by `frustum_corners`.
 /// The corner vertices should be specified in the following order:
 /// first the bottom right, top right, top left, bottom left for the near plane, then similar for the far plane.
+///
+/// The near plane of the cascade is pulled back toward the light by `near_plane_pullback`, see
+/// [`CascadeShadowConfig::near_plane_pullback`].
+///
+/// If `stabilize` is `true`, the cascade is snapped to shadow map texels, see [`CascadeShadowConfig::stabilize`].
 fn calculate_cascade(
     frustum_corners: [Vec3A; 8],
     cascade_texture_size: f32,
     light_to_world: Mat4,
     camera_to_light: Mat4,
+    near_plane_pullback: f32,
+    stabilize: bool,
 ) -> Cascade {
     let mut min = Vec3A::splat(f32::MAX);
     let mut max = Vec3A::splat(f32::MIN);
@@ -562,6 +1022,8 @@ fn calculate_cascade(
         min = min.min(corner_light_view);
         max = max.max(corner_light_view);
     }
+    // NOTE: The light looks down -z, so moving max.z up moves the near plane toward the light.
+    max.z += near_plane_pullback;
 
     // NOTE: Use the larger of the frustum slice far plane diagonal and body diagonal lengths as this
     //       will be the maximum possible projection size. Use the ceiling to get an integer which is
@@ -570,23 +1032,27 @@ fn calculate_cascade(
     //       as even though the lengths using corner_light_view above should be the same, precision can
     //       introduce small but significant differences.
     // NOTE: The size remains the same unless the view frustum or cascade configuration is modified.
-    let cascade_diameter = (frustum_corners[0] - frustum_corners[6])
+    let mut cascade_diameter = (frustum_corners[0] - frustum_corners[6])
         .length()
-        .max((frustum_corners[4] - frustum_corners[6]).length())
-        .ceil();
+        .max((frustum_corners[4] - frustum_corners[6]).length());
+    if stabilize {
+        cascade_diameter = cascade_diameter.ceil();
+    }
 
     // NOTE: If we ensure that cascade_texture_size is a power of 2, then as we made cascade_diameter an
     //       integer, cascade_texel_size is then an integer multiple of a power of 2 and can be
     //       exactly represented in a floating point value.
     let cascade_texel_size = cascade_diameter / cascade_texture_size;
-    // NOTE: For shadow stability it is very important that the near_plane_center is at integer
-    //       multiples of the texel size to be exactly representable in a floating point value.
-    let near_plane_center = Vec3A::new(
-        (0.5 * (min.x + max.x) / cascade_texel_size).floor() * cascade_texel_size,
-        (0.5 * (min.y + max.y) / cascade_texel_size).floor() * cascade_texel_size,
-        // NOTE: max.z is the near plane for right-handed y-up
-        max.z,
-    );
+    // NOTE: max.z is the near plane for right-handed y-up
+    let mut near_plane_center = Vec3A::new(0.5 * (min.x + max.x), 0.5 * (min.y + max.y), max.z);
+    if stabilize {
+        // NOTE: For shadow stability it is very important that the near_plane_center is at integer
+        //       multiples of the texel size to be exactly representable in a floating point value.
+        near_plane_center.x =
+            (near_plane_center.x / cascade_texel_size).floor() * cascade_texel_size;
+        near_plane_center.y =
+            (near_plane_center.y / cascade_texel_size).floor() * cascade_texel_size;
+    }
 
     // It is critical for `world_to_cascade` to be stable. So rather than forming `cascade_to_world`
     // and inverting it, which risks instability due to numerical precision, we directly form
@@ -622,6 +1088,14 @@ fn calculate_cascade(
 ///
 /// This resource is inserted by the [`PbrPlugin`] and by default it is set to a low ambient light.
 ///
+/// It can also be added as a component to a camera, to override the resource for that view only,
+/// e.g. for a split-screen player whose camera is underwater.
+///
+/// Setting a [`ground_color`](Self::ground_color) turns it into a hemisphere light: surfaces
+/// facing [`up`](Self::up) receive `color`, surfaces facing down receive `ground_color`, and
+/// everything in between a blend of the two. This adds some directionality to ambient lighting
+/// without any light probes.
+///
 /// # Examples
 ///
 /// Make ambient light slightly brighter:
@@ -633,12 +1107,40 @@ fn calculate_cascade(
 ///    ambient_light.brightness = 100.0;
 /// }
 /// ```
-#[derive(Resource, Clone, Debug, ExtractResource, Reflect)]
-#[reflect(Resource)]
+///
+/// Use a darker ambient light for a single camera:
+///
+/// ```
+/// # use bevy_ecs::{entity::Entity, system::Commands};
+/// # use bevy_pbr::AmbientLight;
+/// # use bevy_render::color::LegacyColor;
+/// fn darken_view(mut commands: Commands, camera: Entity) {
+///     commands.entity(camera).insert(AmbientLight {
+///         color: LegacyColor::rgb(0.2, 0.4, 0.8),
+///         brightness: 20.0,
+///         ..Default::default()
+///     });
+/// }
+/// # let _ = darken_view;
+/// ```
+#[derive(Resource, Component, Clone, Debug, ExtractResource, ExtractComponent, Reflect)]
+#[reflect(Resource, Component)]
 pub struct AmbientLight {
+    /// The color of the light, or of the sky when [`ground_color`](Self::ground_color) is set.
     pub color: LegacyColor,
     /// A direct scale factor multiplied with `color` before being passed to the shader.
     pub brightness: f32,
+    /// The color of the light reaching surfaces that face away from [`up`](Self::up).
+    ///
+    /// `None` uses [`color`](Self::color) in every direction, which is a flat ambient light.
+    ///
+    /// Defaults to `None`.
+    pub ground_color: Option<LegacyColor>,
+    /// The direction of the sky for hemisphere lighting. Only used when
+    /// [`ground_color`](Self::ground_color) is set.
+    ///
+    /// Defaults to [`Vec3::Y`].
+    pub up: Vec3,
 }
 
 impl Default for AmbientLight {
@@ -646,6 +1148,8 @@ impl Default for AmbientLight {
         Self {
             color: LegacyColor::WHITE,
             brightness: 80.0,
+            ground_color: None,
+            up: Vec3::Y,
         }
     }
 }
@@ -653,6 +1157,8 @@ impl AmbientLight {
     pub const NONE: AmbientLight = AmbientLight {
         color: LegacyColor::WHITE,
         brightness: 0.0,
+        ground_color: None,
+        up: Vec3::Y,
     };
 }
 
@@ -709,6 +1215,64 @@ pub enum ShadowFilteringMethod {
     Jimenez14,
 }
 
+/// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d)
+/// to control how many shadow map samples the [`ShadowFilteringMethod`] takes per fragment,
+/// trading shadow edge quality for performance.
+///
+/// | Quality  | [`Castano13`](ShadowFilteringMethod::Castano13) | [`Jimenez14`](ShadowFilteringMethod::Jimenez14) |
+/// |----------|-----------------------|------------|
+/// | `Low`    | 4 samples (3x3 kernel) | 4 samples  |
+/// | `Medium` | 9 samples (5x5 kernel) | 8 samples  |
+/// | `High`   | 16 samples (7x7 kernel) | 16 samples |
+///
+/// [`ShadowFilteringMethod::Hardware2x2`] always takes a single sample.
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component, Default)]
+pub enum ShadowFilteringQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+/// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d) that uses
+/// [`TemporalAntiAliasSettings`](bevy_core_pipeline::experimental::taa::TemporalAntiAliasSettings)
+/// to keep the shadow filter kernel from rotating every frame.
+///
+/// [`ShadowFilteringMethod::Jimenez14`] randomly rotates its kernel per pixel, which leaves a
+/// noise pattern along shadow edges. On views with
+/// [`TemporalJitter`](bevy_render::camera::TemporalJitter), the rotation also changes every frame
+/// so TAA averages the noise out over time and fewer samples, e.g.
+/// [`ShadowFilteringQuality::Low`], still give smooth edges. This component opts out of that.
+///
+/// Without TAA, the noise pattern always stays stable across frames, as a changing pattern would
+/// be visible as flickering.
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, Default)]
+#[reflect(Component, Default)]
+pub struct NoShadowFilteringJitter;
+
+/// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d)
+/// to control how shadows cast by [`PointLight`]s are filtered.
+///
+/// Point light shadow maps are cubemaps, so they can't use the filtering
+/// methods of [`ShadowFilteringMethod`], which work in the 2D space of a
+/// directional or spot light shadow map.
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component, Default)]
+pub enum PointLightShadowFilteringMethod {
+    /// Hardware 2x2.
+    ///
+    /// Fast but has a lot of aliasing artifacts.
+    #[default]
+    Hardware2x2,
+    /// Percentage-closer filtering in cube space, using 9 hardware 2x2
+    /// samples spread over the plane perpendicular to the direction from the
+    /// light.
+    ///
+    /// Softer penumbras at the cost of more texture lookups.
+    Pcf,
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SimulationLightSystems {
     AddClusters,
@@ -874,6 +1438,53 @@ impl ClusterConfig {
     }
 }
 
+/// Selects where the per-cluster light lists used by clustered forward rendering are built.
+///
+/// With [`ClusteringBackend::Gpu`], only the view frustum culling of lights happens on the
+/// CPU; the lights are then assigned to clusters by a compute shader that writes the same
+/// `cluster_offsets_and_counts` and `cluster_light_index_lists` buffers the CPU path
+/// uploads. This removes the per-light cluster assignment cost from the main world, which
+/// helps in scenes with many lights.
+///
+/// The GPU backend requires compute shaders and storage buffers. Where either is
+/// unavailable, e.g. on WebGL2, lights keep being assigned on the CPU. It is also ignored
+/// with [`LightingStrategy::SimpleForward`], which doesn't assign lights to clusters.
+///
+/// Clusters are tested against the bounding sphere of each light's range, so spot lights
+/// may be assigned to a few more clusters than on the CPU, which also tests their cone. A
+/// cluster holds at most [`GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER`] lights; any further
+/// lights are ignored for that cluster.
+///
+/// [`GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER`]: crate::GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub enum ClusteringBackend {
+    /// Assign lights to clusters on the CPU.
+    #[default]
+    Cpu,
+    /// Assign lights to clusters with a compute shader.
+    Gpu,
+}
+
+/// Selects how the point and spot lights affecting each fragment are found.
+///
+/// [`LightingStrategy::SimpleForward`] skips the assignment of lights to clusters: each view
+/// gets a single flat list of the lights intersecting its frustum, which every fragment loops
+/// over. For scenes with a handful of lights, e.g. mobile or 2.5D games, this is cheaper than
+/// clustering on the CPU and simpler on the GPU. As every fragment evaluates every light in
+/// view, it scales poorly with the number of lights.
+///
+/// Cameras with [`ClusterConfig::None`] still receive no point or spot lights.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, ExtractResource, Reflect)]
+#[reflect(Resource, Default)]
+pub enum LightingStrategy {
+    /// Assign lights to the clusters configured by each camera's [`ClusterConfig`].
+    #[default]
+    Clustered,
+    /// Give each view one flat list of the lights in its frustum.
+    SimpleForward,
+}
+
 #[derive(Component, Debug, Default)]
 pub struct Clusters {
     /// Tile size
@@ -1022,6 +1633,63 @@ fn ndc_position_to_cluster(
         .clamp(UVec3::ZERO, cluster_dimensions - UVec3::ONE)
 }
 
+/// Returns the `X` / `Y` / `Z` index of the cluster containing `world_position` for a camera
+/// that has had lights assigned to its [`Clusters`].
+///
+/// This matches the cluster lookup done by `fragment_cluster_index` in `clustered_forward.wgsl`,
+/// so it can be used to verify clustering or to query the lights affecting a point.
+///
+/// Returns `None` if the camera has no clusters, or if `world_position` is behind the camera or
+/// outside of its viewport.
+pub fn cluster_index_for_world_pos(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    clusters: &Clusters,
+    world_position: Vec3,
+) -> Option<UVec3> {
+    let view_position = camera_transform
+        .compute_matrix()
+        .inverse()
+        .transform_point3(world_position);
+    cluster_index_for_view_pos(clusters, camera.projection_matrix(), view_position)
+}
+
+/// Returns the cluster containing `view_position`, see [`cluster_index_for_world_pos`].
+fn cluster_index_for_view_pos(
+    clusters: &Clusters,
+    projection_matrix: Mat4,
+    view_position: Vec3,
+) -> Option<UVec3> {
+    if clusters.dimensions.cmpeq(UVec3::ZERO).any() {
+        return None;
+    }
+
+    let ndc_position = projection_matrix.project_point3(view_position);
+    if ndc_position.is_nan() || ndc_position.xy().abs().cmpgt(Vec2::ONE).any() {
+        return None;
+    }
+
+    let view_z = view_position.z;
+    let is_orthographic = projection_matrix.w_axis.w == 1.0;
+    if !is_orthographic && view_z >= 0.0 {
+        return None;
+    }
+
+    let cluster_factors = calculate_cluster_factors(
+        clusters.near,
+        clusters.far,
+        clusters.dimensions.z as f32,
+        is_orthographic,
+    );
+    Some(ndc_position_to_cluster(
+        clusters.dimensions,
+        cluster_factors,
+        is_orthographic,
+        ndc_position,
+        view_z,
+    ))
+}
+
 const VEC2_HALF: Vec2 = Vec2::splat(0.5);
 const VEC2_HALF_NEGATIVE_Y: Vec2 = Vec2::new(0.5, -0.5);
 
@@ -1297,6 +1965,8 @@ pub(crate) fn assign_lights_to_clusters(
     mut cluster_aabb_spheres: Local<Vec<Option<Sphere>>>,
     mut max_point_lights_warning_emitted: Local<bool>,
     render_device: Option<Res<RenderDevice>>,
+    clustering_backend: Res<ClusteringBackend>,
+    lighting_strategy: Res<LightingStrategy>,
 ) {
     let Some(render_device) = render_device else {
         return;
@@ -1346,6 +2016,13 @@ pub(crate) fn assign_lights_to_clusters(
         clustered_forward_buffer_binding_type,
         BufferBindingType::Storage { .. }
     );
+    // The GPU backend only needs the lights visible to each view; the compute shader does
+    // the per-cluster assignment.
+    let simple_forward = *lighting_strategy == LightingStrategy::SimpleForward;
+    let assign_on_gpu = !simple_forward
+        && *clustering_backend == ClusteringBackend::Gpu
+        && supports_storage_buffers
+        && gpu_clustering_is_supported(&render_device);
     if lights.len() > MAX_UNIFORM_BUFFER_POINT_LIGHTS && !supports_storage_buffers {
         lights.sort_by(|light_1, light_2| {
             point_light_order(
@@ -1424,7 +2101,12 @@ pub(crate) fn assign_lights_to_clusters(
             continue;
         };
 
-        let mut requested_cluster_dimensions = config.dimensions_for_screen_size(screen_size);
+        // A single cluster holds the flat light list of the view.
+        let mut requested_cluster_dimensions = if simple_forward {
+            UVec3::ONE
+        } else {
+            config.dimensions_for_screen_size(screen_size)
+        };
 
         let view_transform = camera_transform.compute_matrix();
         let view_inv_scale = camera_transform.compute_transform().scale.recip();
@@ -1472,7 +2154,7 @@ pub(crate) fn assign_lights_to_clusters(
             is_orthographic,
         );
 
-        if config.dynamic_resizing() {
+        if config.dynamic_resizing() && !simple_forward {
             let mut cluster_index_estimate = 0.0;
             for light in &lights {
                 let light_sphere = light.sphere();
@@ -1646,6 +2328,21 @@ pub(crate) fn assign_lights_to_clusters(
                 global_lights.entities.insert(light.entity);
                 visible_lights.push(light.entity);
 
+                if simple_forward {
+                    let cluster_lights = &mut clusters.lights[0];
+                    cluster_lights.entities.push(light.entity);
+                    if light.spot_light_angle.is_some() {
+                        cluster_lights.spot_light_count += 1;
+                    } else {
+                        cluster_lights.point_light_count += 1;
+                    }
+                    continue;
+                }
+
+                if assign_on_gpu {
+                    continue;
+                }
+
                 // note: caching seems to be slower than calling twice for this aabb calculation
                 let (light_aabb_xy_ndc_z_view_min, light_aabb_xy_ndc_z_view_max) =
                     cluster_space_light_aabb(
@@ -2143,7 +2840,11 @@ pub fn check_light_mesh_visibility(
             }
 
             let entity_mask = maybe_entity_mask.copied().unwrap_or_default();
-            if !view_mask.intersects(&entity_mask) {
+            if !view_mask.intersects(&entity_mask)
+                || !directional_light
+                    .shadow_caster_layers
+                    .intersects(&entity_mask)
+            {
                 continue;
             }
 
@@ -2227,7 +2928,9 @@ pub fn check_light_mesh_visibility(
                     }
 
                     let entity_mask = maybe_entity_mask.copied().unwrap_or_default();
-                    if !view_mask.intersects(&entity_mask) {
+                    if !view_mask.intersects(&entity_mask)
+                        || !point_light.shadow_caster_layers.intersects(&entity_mask)
+                    {
                         continue;
                     }
 
@@ -2292,7 +2995,9 @@ pub fn check_light_mesh_visibility(
                     }
 
                     let entity_mask = maybe_entity_mask.copied().unwrap_or_default();
-                    if !view_mask.intersects(&entity_mask) {
+                    if !view_mask.intersects(&entity_mask)
+                        || !point_light.shadow_caster_layers.intersects(&entity_mask)
+                    {
                         continue;
                     }
 
@@ -2322,6 +3027,8 @@ pub fn check_light_mesh_visibility(
 
 #[cfg(test)]
 mod test {
+    use bevy_render::camera::PerspectiveProjection;
+
     use super::*;
 
     fn test_cluster_tiling(config: ClusterConfig, screen_size: UVec2) -> Clusters {
@@ -2380,4 +3087,178 @@ mod test {
             }
         }
     }
+
+    fn test_clusters(dimensions: UVec3) -> Clusters {
+        Clusters {
+            dimensions,
+            near: 5.0,
+            far: 100.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cluster_index_for_perspective_view_pos() {
+        let clusters = test_clusters(UVec3::new(4, 4, 8));
+        let projection = PerspectiveProjection {
+            fov: std::f32::consts::FRAC_PI_2,
+            aspect_ratio: 1.0,
+            ..Default::default()
+        }
+        .get_projection_matrix();
+        let cluster =
+            |view_position| cluster_index_for_view_pos(&clusters, projection, view_position);
+
+        // The first depth slice extends to `near`.
+        assert_eq!(
+            cluster(Vec3::new(0.0, 0.0, -1.0)),
+            Some(UVec3::new(2, 2, 0))
+        );
+        // Y points up in view space but down in cluster space.
+        assert_eq!(
+            cluster(Vec3::new(-9.0, 9.0, -10.0)),
+            Some(UVec3::new(0, 0, 2))
+        );
+        assert_eq!(
+            cluster(Vec3::new(9.0, -9.0, -10.0)),
+            Some(UVec3::new(3, 3, 2))
+        );
+        // Depth slices are exponentially spaced, and everything beyond `far` is in the last one.
+        assert_eq!(
+            cluster(Vec3::new(0.0, 0.0, -20.0)),
+            Some(UVec3::new(2, 2, 4))
+        );
+        assert_eq!(
+            cluster(Vec3::new(0.0, 0.0, -500.0)),
+            Some(UVec3::new(2, 2, 7))
+        );
+
+        // Behind the camera and outside of the viewport.
+        assert_eq!(cluster(Vec3::new(0.0, 0.0, 1.0)), None);
+        assert_eq!(cluster(Vec3::new(-11.0, 0.0, -10.0)), None);
+        // No clusters.
+        let clusters = test_clusters(UVec3::ZERO);
+        assert_eq!(
+            cluster_index_for_view_pos(&clusters, projection, -Vec3::Z),
+            None
+        );
+    }
+
+    #[test]
+    fn cluster_index_for_orthographic_view_pos() {
+        let clusters = test_clusters(UVec3::new(4, 4, 4));
+        let projection = Mat4::orthographic_rh(-10.0, 10.0, -10.0, 10.0, 0.0, 100.0);
+        let cluster =
+            |view_position| cluster_index_for_view_pos(&clusters, projection, view_position);
+
+        // Depth slices are linearly spaced.
+        assert_eq!(
+            cluster(Vec3::new(-9.0, 9.0, -10.0)),
+            Some(UVec3::new(0, 0, 0))
+        );
+        assert_eq!(
+            cluster(Vec3::new(9.0, -9.0, -90.0)),
+            Some(UVec3::new(3, 3, 3))
+        );
+        assert_eq!(
+            cluster(Vec3::new(0.0, 0.0, -30.0)),
+            Some(UVec3::new(2, 2, 1))
+        );
+        assert_eq!(cluster(Vec3::new(0.0, 11.0, -10.0)), None);
+    }
+
+    // A 2x2x1 box in front of the camera, which is also the light's view space.
+    fn box_frustum_corners() -> [Vec3A; 8] {
+        [
+            Vec3A::new(1.0, -1.0, -1.0),
+            Vec3A::new(1.0, 1.0, -1.0),
+            Vec3A::new(-1.0, 1.0, -1.0),
+            Vec3A::new(-1.0, -1.0, -1.0),
+            Vec3A::new(1.0, -1.0, -2.0),
+            Vec3A::new(1.0, 1.0, -2.0),
+            Vec3A::new(-1.0, 1.0, -2.0),
+            Vec3A::new(-1.0, -1.0, -2.0),
+        ]
+    }
+
+    fn cascade_depth(cascade: &Cascade, z: f32) -> f32 {
+        cascade
+            .view_projection
+            .project_point3(Vec3::new(0.0, 0.0, z))
+            .z
+    }
+
+    #[test]
+    fn cascade_near_plane_pullback() {
+        let corners = box_frustum_corners();
+
+        // Without pullback the projection spans exactly the depth of the frustum slice.
+        let cascade =
+            calculate_cascade(corners, 1024.0, Mat4::IDENTITY, Mat4::IDENTITY, 0.0, false);
+        assert!((cascade_depth(&cascade, -1.0) - 1.0).abs() < 1e-5);
+        assert!(cascade_depth(&cascade, -2.0).abs() < 1e-5);
+        assert!(cascade_depth(&cascade, 2.0) > 1.0);
+
+        // With pullback an occluder between the light and the slice is inside the projection.
+        let cascade =
+            calculate_cascade(corners, 1024.0, Mat4::IDENTITY, Mat4::IDENTITY, 3.0, false);
+        assert!((cascade_depth(&cascade, 2.0) - 1.0).abs() < 1e-5);
+        assert!(cascade_depth(&cascade, -2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cascade_stabilize() {
+        let corners = box_frustum_corners().map(|corner| 1.1 * corner);
+        let diameter = (corners[0] - corners[6]).length();
+
+        let cascade =
+            calculate_cascade(corners, 1024.0, Mat4::IDENTITY, Mat4::IDENTITY, 0.0, false);
+        assert_eq!(cascade.texel_size, diameter / 1024.0);
+
+        // The diameter is rounded up to whole world units.
+        let cascade = calculate_cascade(corners, 1024.0, Mat4::IDENTITY, Mat4::IDENTITY, 0.0, true);
+        assert_eq!(cascade.texel_size, diameter.ceil() / 1024.0);
+
+        // However the camera moves, the cascade stays on a grid of whole texels.
+        for offset in [0.0, 0.001, 0.37, -1.234] {
+            let camera_to_light = Mat4::from_translation(Vec3::new(offset, 2.0 * offset, 0.0));
+            let cascade =
+                calculate_cascade(corners, 1024.0, Mat4::IDENTITY, camera_to_light, 0.0, true);
+            let texels = cascade.view_transform.w_axis.xy() / cascade.texel_size;
+            assert!((texels - texels.round()).abs().max_element() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn cascade_sizes_are_clamped_and_rounded() {
+        let shadow_map = DirectionalLightShadowMap::default();
+        let config = CascadeShadowConfigBuilder {
+            cascade_sizes: vec![4096, 1000, 1024, 1],
+            ..Default::default()
+        }
+        .build();
+
+        let sizes: Vec<_> = (0..5)
+            .map(|index| config.cascade_size(index, &shadow_map))
+            .collect();
+        assert_eq!(sizes, [2048, 1024, 1024, 1, 2048]);
+    }
+
+    #[test]
+    fn cascade_sizes_fall_back_to_shadow_map() {
+        let shadow_map = DirectionalLightShadowMap {
+            size: 2048,
+            cascade_sizes: vec![2048, 1024, 512],
+        };
+        let config = CascadeShadowConfigBuilder {
+            cascade_sizes: vec![256],
+            ..Default::default()
+        }
+        .build();
+
+        let sizes: Vec<_> = (0..4)
+            .map(|index| config.cascade_size(index, &shadow_map))
+            .collect();
+        assert_eq!(sizes, [256, 1024, 512, 2048]);
+    }
 }
@@ -0,0 +1,175 @@
+use crate::{AlphaMode, Material, MaterialPlugin, NotShadowCaster, PointLight, SpotLight};
+use bevy_app::{Plugin, PostUpdate, Startup};
+use bevy_asset::{load_internal_asset, Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::primitives::Rectangle;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_render::{
+    color::LegacyColor, mesh::Mesh, prelude::*, render_resource::*, texture::Image,
+    view::NoFrustumCulling,
+};
+use std::f32::consts::PI;
+
+pub const LIGHT_BILLBOARD_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(281652960184731977);
+
+/// A [`Plugin`] that draws the [`PointLight`]s and [`SpotLight`]s with a [`LightBillboard`] as
+/// camera-facing quads.
+///
+/// This plugin is not part of the default plugins and has to be added explicitly.
+#[derive(Debug, Default)]
+pub struct LightBillboardPlugin;
+
+impl Plugin for LightBillboardPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        load_internal_asset!(
+            app,
+            LIGHT_BILLBOARD_SHADER_HANDLE,
+            "render/light_billboard.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<LightBillboard>()
+            .add_plugins(MaterialPlugin::<LightBillboardMaterial> {
+                prepass_enabled: false,
+                ..Default::default()
+            })
+            .add_systems(Startup, setup_light_billboard_mesh)
+            .add_systems(PostUpdate, apply_light_billboard_material);
+    }
+}
+
+/// Draws the [`PointLight`] or [`SpotLight`] it is attached to as a quad that always faces the
+/// camera.
+///
+/// The quad is a real emissive mesh: it is depth tested against the scene and, on HDR cameras,
+/// picks up bloom like any other bright surface. It is given the brightness of a disc of
+/// diameter [`LightBillboard::size`] emitting the intensity of the light, so it stays consistent
+/// with the light as its color or intensity change.
+///
+/// This requires the [`LightBillboardPlugin`] to be enabled.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightBillboard {
+    /// The texture of the quad, multiplied by the color of the light.
+    ///
+    /// If `None`, the quad is filled with the light color.
+    pub texture: Option<Handle<Image>>,
+    /// The width and height of the quad, in world units. Defaults to `0.1`.
+    pub size: f32,
+}
+
+impl Default for LightBillboard {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            size: 0.1,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct LightBillboardMesh {
+    handle: Handle<Mesh>,
+}
+
+fn setup_light_billboard_mesh(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.insert_resource(LightBillboardMesh {
+        handle: meshes.add(Rectangle::new(1.0, 1.0)),
+    });
+}
+
+/// Adds, updates or removes the billboard of every light with a [`LightBillboard`].
+#[allow(clippy::type_complexity)]
+fn apply_light_billboard_material(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<LightBillboardMaterial>>,
+    mesh: Res<LightBillboardMesh>,
+    billboards: Query<
+        (
+            Entity,
+            &LightBillboard,
+            AnyOf<(&PointLight, &SpotLight)>,
+            Option<&Handle<LightBillboardMaterial>>,
+        ),
+        Or<(
+            Changed<LightBillboard>,
+            Changed<PointLight>,
+            Changed<SpotLight>,
+        )>,
+    >,
+    mut removed_billboards: RemovedComponents<LightBillboard>,
+) {
+    for e in removed_billboards.read() {
+        if let Some(mut commands) = commands.get_entity(e) {
+            commands.remove::<(
+                Handle<Mesh>,
+                Handle<LightBillboardMaterial>,
+                NotShadowCaster,
+                NoFrustumCulling,
+            )>();
+        }
+    }
+
+    for (e, billboard, (point_light, spot_light), handle) in &billboards {
+        let (color, intensity) = match (point_light, spot_light) {
+            (Some(light), _) => (light.color, light.intensity),
+            (None, Some(light)) => (light.color, light.intensity),
+            (None, None) => continue,
+        };
+        let material = LightBillboardMaterial::new(billboard, color, intensity);
+
+        if let Some(existing) = handle.and_then(|handle| materials.get_mut(handle)) {
+            *existing = material;
+        } else {
+            commands.entity(e).insert((
+                mesh.handle.clone(),
+                materials.add(material),
+                NotShadowCaster,
+                NoFrustumCulling,
+            ));
+        }
+    }
+}
+
+/// The material used to draw a [`LightBillboard`].
+#[derive(AsBindGroup, TypePath, Debug, Clone, Asset)]
+pub struct LightBillboardMaterial {
+    /// The linear color of the quad, in nits.
+    #[uniform(0)]
+    pub color: LegacyColor,
+    #[uniform(0)]
+    pub size: f32,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Option<Handle<Image>>,
+}
+
+impl LightBillboardMaterial {
+    fn new(billboard: &LightBillboard, color: LegacyColor, intensity: f32) -> Self {
+        // Spread the luminous intensity (lumens / 4π sr) over the area of the disc to get its
+        // luminance in nits, the unit emissive colors are expressed in.
+        let radius = (billboard.size * 0.5).max(f32::EPSILON);
+        let luminance = intensity / (4.0 * PI) / (PI * radius * radius);
+        let [r, g, b, _] = color.as_linear_rgba_f32();
+        Self {
+            color: LegacyColor::rgb_linear(r * luminance, g * luminance, b * luminance),
+            size: billboard.size,
+            texture: billboard.texture.clone(),
+        }
+    }
+}
+
+impl Material for LightBillboardMaterial {
+    fn vertex_shader() -> ShaderRef {
+        LIGHT_BILLBOARD_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        LIGHT_BILLBOARD_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Add
+    }
+}
@@ -50,6 +50,7 @@ use bevy_asset::{AssetId, Handle};
 use bevy_ecs::{
     bundle::Bundle, component::Component, query::QueryItem, system::lifetimeless::Read,
 };
+use bevy_math::Quat;
 use bevy_reflect::Reflect;
 use bevy_render::{
     extract_instances::ExtractInstance,
@@ -96,6 +97,13 @@ pub struct EnvironmentMapLight {
     ///
     /// See also <https://google.github.io/filament/Filament.html#lighting/imagebasedlights/iblunit>.
     pub intensity: f32,
+
+    /// The orientation of the environment map relative to the world.
+    ///
+    /// Use this to align the reflections with the scene when the cubemaps were authored in a
+    /// different orientation, e.g. to match a rotated skybox. [`Quat::IDENTITY`] leaves the
+    /// cubemaps as they are.
+    pub rotation: Quat,
 }
 
 /// Like [`EnvironmentMapLight`], but contains asset IDs instead of handles.
@@ -174,6 +182,8 @@ pub struct EnvironmentMapViewLightProbeInfo {
     /// The scale factor applied to the diffuse and specular light in the
     /// cubemap. This is in units of cd/m² (candela per square meter).
     pub(crate) intensity: f32,
+    /// The orientation of the cubemap relative to the world.
+    pub(crate) rotation: Quat,
 }
 
 impl ExtractInstance for EnvironmentMapIds {
@@ -300,6 +310,10 @@ impl LightProbeComponent for EnvironmentMapLight {
         self.intensity
     }
 
+    fn rotation(&self) -> Quat {
+        self.rotation
+    }
+
     fn create_render_view_light_probes(
         view_component: Option<&EnvironmentMapLight>,
         image_assets: &RenderAssets<Image>,
@@ -312,6 +326,7 @@ impl LightProbeComponent for EnvironmentMapLight {
             diffuse_map: diffuse_map_handle,
             specular_map: specular_map_handle,
             intensity,
+            rotation,
         }) = view_component
         {
             if let (Some(_), Some(specular_map)) = (
@@ -327,6 +342,7 @@ impl LightProbeComponent for EnvironmentMapLight {
                     ) as i32,
                     smallest_specular_mip_level: specular_map.mip_level_count - 1,
                     intensity: *intensity,
+                    rotation: *rotation,
                 };
             }
         };
@@ -341,6 +357,7 @@ impl Default for EnvironmentMapViewLightProbeInfo {
             cubemap_index: -1,
             smallest_specular_mip_level: 0,
             intensity: 1.0,
+            rotation: Quat::IDENTITY,
         }
     }
 }
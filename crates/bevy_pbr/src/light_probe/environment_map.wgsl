@@ -1,6 +1,6 @@
 #define_import_path bevy_pbr::environment_map
 
-#import bevy_pbr::light_probe::query_light_probe
+#import bevy_pbr::light_probe::{query_light_probe, quat_rotate}
 #import bevy_pbr::mesh_view_bindings as bindings
 #import bevy_pbr::mesh_view_bindings::light_probes
 
@@ -36,6 +36,7 @@ fn compute_radiances(
     if (query_result.texture_index < 0) {
         query_result.texture_index = light_probes.view_cubemap_index;
         query_result.intensity = light_probes.intensity_for_view;
+        query_result.rotation = light_probes.rotation_for_view;
     }
 
     // If there's no cubemap, bail out.
@@ -49,18 +50,22 @@ fn compute_radiances(
     let radiance_level = perceptual_roughness * f32(textureNumLevels(
         bindings::specular_environment_maps[query_result.texture_index]) - 1u);
 
+    // Rotate the directions into the space of the cubemap.
+    let N_cubemap = quat_rotate(query_result.rotation, N);
+    let R_cubemap = quat_rotate(query_result.rotation, R);
+
     if (!found_diffuse_indirect) {
         radiances.irradiance = textureSampleLevel(
             bindings::diffuse_environment_maps[query_result.texture_index],
             bindings::environment_map_sampler,
-            vec3(N.xy, -N.z),
+            vec3(N_cubemap.xy, -N_cubemap.z),
             0.0).rgb * query_result.intensity;
     }
 
     radiances.radiance = textureSampleLevel(
         bindings::specular_environment_maps[query_result.texture_index],
         bindings::environment_map_sampler,
-        vec3(R.xy, -R.z),
+        vec3(R_cubemap.xy, -R_cubemap.z),
         radiance_level).rgb * query_result.intensity;
 
     return radiances;
@@ -90,18 +95,22 @@ fn compute_radiances(
 
     let intensity = light_probes.intensity_for_view;
 
+    // Rotate the directions into the space of the cubemap.
+    let N_cubemap = quat_rotate(light_probes.rotation_for_view, N);
+    let R_cubemap = quat_rotate(light_probes.rotation_for_view, R);
+
     if (!found_diffuse_indirect) {
         radiances.irradiance = textureSampleLevel(
             bindings::diffuse_environment_map,
             bindings::environment_map_sampler,
-            vec3(N.xy, -N.z),
+            vec3(N_cubemap.xy, -N_cubemap.z),
             0.0).rgb * intensity;
     }
 
     radiances.radiance = textureSampleLevel(
         bindings::specular_environment_map,
         bindings::environment_map_sampler,
-        vec3(R.xy, -R.z),
+        vec3(R_cubemap.xy, -R_cubemap.z),
         radiance_level).rgb * intensity;
 
     return radiances;
@@ -11,11 +11,20 @@ struct LightProbeQueryResult {
     // A scale factor that's applied to the diffuse and specular light from the
     // light probe. This is in units of cd/m² (candela per square meter).
     intensity: f32,
+    // The rotation from world space to the space the light probe textures are
+    // sampled in, as a quaternion.
+    rotation: vec4<f32>,
     // Transform from world space to the light probe model space. In light probe
     // model space, the light probe is a 1×1×1 cube centered on the origin.
     inverse_transform: mat4x4<f32>,
 };
 
+// Rotates `v` by the unit quaternion `q`.
+fn quat_rotate(q: vec4<f32>, v: vec3<f32>) -> vec3<f32> {
+    let t = 2.0 * cross(q.xyz, v);
+    return v + q.w * t + cross(q.xyz, t);
+}
+
 fn transpose_affine_matrix(matrix: mat3x4<f32>) -> mat4x4<f32> {
     let matrix4x4 = mat4x4<f32>(
         matrix[0],
@@ -62,6 +71,7 @@ fn query_light_probe(
         if (all(abs(probe_space_pos) <= vec3(0.5f))) {
             result.texture_index = light_probe.cubemap_index;
             result.intensity = light_probe.intensity;
+            result.rotation = light_probe.rotation;
             result.inverse_transform = inverse_transform;
 
             // TODO: Workaround for ICE in DXC https://github.com/microsoft/DirectXShaderCompiler/issues/6183
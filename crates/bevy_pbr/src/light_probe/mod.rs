@@ -2,7 +2,10 @@
 
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, AssetId, Handle};
-use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_core_pipeline::core_3d::{
+    graph::{Core3d, Node3d},
+    Camera3d,
+};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     component::Component,
@@ -12,12 +15,14 @@ use bevy_ecs::{
     schedule::IntoSystemConfigs,
     system::{Commands, Local, Query, Res, ResMut, Resource},
 };
-use bevy_math::{Affine3A, Mat4, Vec3A, Vec4};
+use bevy_math::{Affine3A, Mat4, Quat, Vec3A, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
+    extract_component::ExtractComponentPlugin,
     extract_instances::ExtractInstancesPlugin,
     primitives::{Aabb, Frustum},
     render_asset::RenderAssets,
+    render_graph::{RenderGraphApp, ViewNodeRunner},
     render_resource::{DynamicUniformBuffer, Sampler, Shader, ShaderType, TextureView},
     renderer::{RenderDevice, RenderQueue},
     settings::WgpuFeatures,
@@ -32,9 +37,15 @@ use std::hash::Hash;
 use std::ops::Deref;
 
 use crate::{
+    graph::NodePbr,
     irradiance_volume::IRRADIANCE_VOLUME_SHADER_HANDLE,
-    light_probe::environment_map::{
-        EnvironmentMapIds, EnvironmentMapLight, ENVIRONMENT_MAP_SHADER_HANDLE,
+    light_probe::{
+        environment_map::{EnvironmentMapIds, EnvironmentMapLight, ENVIRONMENT_MAP_SHADER_HANDLE},
+        skybox_ambient::{
+            prepare_skybox_ambient, SkyboxAmbientCache, SkyboxAmbientFallbackBuffer,
+            SkyboxAmbientLight, SkyboxAmbientNode, SkyboxAmbientPipeline, SKYBOX_AMBIENT_IS_USABLE,
+            SKYBOX_AMBIENT_SHADER_HANDLE,
+        },
     },
 };
 
@@ -44,6 +55,7 @@ pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(895
 
 pub mod environment_map;
 pub mod irradiance_volume;
+pub mod skybox_ambient;
 
 /// The maximum number of each type of light probe that each view will consider.
 ///
@@ -124,6 +136,12 @@ struct RenderLightProbe {
     ///
     /// See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
+
+    /// The rotation from world space to the space the light probe's textures
+    /// are sampled in, as a quaternion.
+    ///
+    /// See the comment in [`EnvironmentMapLight`] for details.
+    rotation: Vec4,
 }
 
 /// A per-view shader uniform that specifies all the light probes that the view
@@ -157,6 +175,10 @@ pub struct LightProbesUniform {
     ///
     /// See the comment in [`EnvironmentMapLight`] for details.
     intensity_for_view: f32,
+
+    /// The rotation from world space to the space the environment cubemap
+    /// associated with the view is sampled in, as a quaternion.
+    rotation_for_view: Vec4,
 }
 
 /// A GPU buffer that stores information about all light probes.
@@ -190,6 +212,9 @@ where
     // See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
 
+    // The orientation of the light probe's textures relative to the world.
+    rotation: Quat,
+
     // The IDs of all assets associated with this light probe.
     //
     // Because each type of light probe component may reference different types
@@ -278,6 +303,14 @@ pub trait LightProbeComponent: Send + Sync + Component + Sized {
     /// sampled from the texture.
     fn intensity(&self) -> f32;
 
+    /// Returns the orientation of the textures of this light probe relative to
+    /// the world.
+    ///
+    /// Textures are sampled with directions rotated by the inverse of this.
+    fn rotation(&self) -> Quat {
+        Quat::IDENTITY
+    }
+
     /// Creates an instance of [`RenderViewLightProbes`] containing all the
     /// information needed to render this light probe.
     ///
@@ -316,10 +349,18 @@ impl Plugin for LightProbePlugin {
             "irradiance_volume.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            SKYBOX_AMBIENT_SHADER_HANDLE,
+            "skybox_ambient.wgsl",
+            Shader::from_wgsl
+        );
 
         app.register_type::<LightProbe>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<IrradianceVolume>()
+            .register_type::<SkyboxAmbientLight>()
+            .add_plugins(ExtractComponentPlugin::<SkyboxAmbientLight>::default());
     }
 
     fn finish(&self, app: &mut App) {
@@ -336,6 +377,43 @@ impl Plugin for LightProbePlugin {
                 Render,
                 upload_light_probes.in_set(RenderSet::PrepareResources),
             );
+
+        if !SKYBOX_AMBIENT_IS_USABLE {
+            return;
+        }
+
+        // The fallback buffer is needed by the mesh view bind group even if the
+        // skybox can't be projected.
+        render_app.init_resource::<SkyboxAmbientFallbackBuffer>();
+
+        // Without compute shaders writing to storage buffers, the skybox can't
+        // be projected and views keep using the flat ambient light.
+        let limits = render_app.world.resource::<RenderDevice>().limits();
+        if limits.max_compute_invocations_per_workgroup < 64
+            || limits.max_storage_buffers_per_shader_stage == 0
+        {
+            return;
+        }
+
+        render_app
+            .init_resource::<SkyboxAmbientPipeline>()
+            .init_resource::<SkyboxAmbientCache>()
+            .add_systems(
+                Render,
+                prepare_skybox_ambient.in_set(RenderSet::PrepareResources),
+            )
+            .add_render_graph_node::<ViewNodeRunner<SkyboxAmbientNode>>(
+                Core3d,
+                NodePbr::SkyboxAmbient,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::EndPrepasses,
+                    NodePbr::SkyboxAmbient,
+                    Node3d::StartMainPass,
+                ),
+            );
     }
 }
 
@@ -453,6 +531,10 @@ fn upload_light_probes(
             intensity_for_view: render_view_environment_maps
                 .map(|maps| maps.view_light_probe_info.intensity)
                 .unwrap_or(1.0),
+            rotation_for_view: render_view_environment_maps
+                .map(|maps| maps.view_light_probe_info.rotation.inverse())
+                .unwrap_or_default()
+                .into(),
         };
 
         // Add any environment maps that [`gather_light_probes`] found to the
@@ -492,6 +574,7 @@ impl Default for LightProbesUniform {
             view_cubemap_index: -1,
             smallest_specular_mip_level_for_view: 0,
             intensity_for_view: 1.0,
+            rotation_for_view: Quat::IDENTITY.into(),
         }
     }
 }
@@ -512,6 +595,7 @@ where
             inverse_transform: light_probe_transform.compute_matrix().inverse(),
             asset_id: id,
             intensity: environment_map.intensity(),
+            rotation: environment_map.rotation(),
         })
     }
 
@@ -609,6 +693,7 @@ where
                 ],
                 texture_index: cubemap_index as i32,
                 intensity: light_probe.intensity,
+                rotation: light_probe.rotation.inverse().into(),
             });
         }
     }
@@ -623,6 +708,7 @@ where
             inverse_transform: self.inverse_transform,
             affine_transform: self.affine_transform,
             intensity: self.intensity,
+            rotation: self.rotation,
             asset_id: self.asset_id.clone(),
         }
     }
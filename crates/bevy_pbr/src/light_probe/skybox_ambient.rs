@@ -0,0 +1,381 @@
+//! Ambient light derived from the skybox.
+//!
+//! When a view has a [`Skybox`] and a [`SkyboxAmbientLight`], but no
+//! [`EnvironmentMapLight`], the skybox cubemap is projected onto the first
+//! three bands of spherical harmonics on the GPU. The resulting nine
+//! coefficients replace the [`AmbientLight`] of the view, giving indirect
+//! lighting some directionality without requiring any baked light probes:
+//! surfaces facing a bright sky are lit more strongly than surfaces facing the
+//! ground.
+//!
+//! The projection is only recomputed when the skybox image or its brightness
+//! changes. All other views keep using the [`AmbientLight`].
+//!
+//! [`AmbientLight`]: crate::AmbientLight
+
+use bevy_asset::{AssetId, Handle};
+use bevy_core_pipeline::Skybox;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    prelude::ReflectComponent,
+    query::{QueryItem, With},
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::Vec4;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::ExtractComponent,
+    render_asset::RenderAssets,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        binding_types::{sampler, storage_buffer, texture_cube, uniform_buffer},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, Buffer,
+        BufferInitDescriptor, BufferUsages, CachedComputePipelineId, ComputePassDescriptor,
+        ComputePipelineDescriptor, PipelineCache, SamplerBindingType, Shader, ShaderStages,
+        ShaderType, TextureSampleType, TextureViewId,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::Image,
+    view::ExtractedView,
+};
+
+use super::{environment_map::EnvironmentMapLight, RenderViewLightProbes};
+
+pub const SKYBOX_AMBIENT_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(37423940843506829105514826346071123902);
+
+/// On WebGL 2, compute shaders aren't available, so the skybox can't be
+/// projected and the mesh view binding for its result is omitted.
+///
+/// Other platforms, including WebGPU, still check the device limits for compute
+/// and storage buffer support before setting up the projection.
+pub(crate) const SKYBOX_AMBIENT_IS_USABLE: bool = cfg!(any(
+    not(feature = "webgl"),
+    not(target_arch = "wasm32"),
+    feature = "webgpu"
+));
+
+/// Add this component to a camera with a [`Skybox`] to light the scene with the skybox instead
+/// of the [`AmbientLight`](crate::AmbientLight).
+///
+/// The ambient light then comes from the colors of the skybox, scaled by
+/// [`Skybox::brightness`]: surfaces facing a bright part of the sky receive more light than those
+/// facing a dark one. The [`AmbientLight`](crate::AmbientLight) resource, or a per-camera
+/// [`AmbientLight`](crate::AmbientLight) component, is ignored for this camera, including its
+/// `brightness`.
+///
+/// Has no effect if the camera also has an [`EnvironmentMapLight`], which already lights the scene
+/// with its own diffuse map, or on platforms without compute shaders like WebGL 2, where the
+/// [`AmbientLight`](crate::AmbientLight) is used instead.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect, ExtractComponent)]
+#[reflect(Component, Default)]
+pub struct SkyboxAmbientLight;
+
+/// The spherical harmonics projection of a skybox, as seen by the shader.
+///
+/// This is written by the projection compute shader and read as the
+/// `skybox_ambient` mesh view binding.
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct GpuSkyboxAmbient {
+    /// The nine L2 spherical harmonics coefficients of the skybox radiance, in
+    /// cd/m². The alpha channel is unused.
+    pub coefficients: [Vec4; 9],
+    /// Nonzero if `coefficients` holds a valid projection.
+    pub enabled: u32,
+}
+
+/// A uniform buffer holding a disabled [`GpuSkyboxAmbient`], bound for views
+/// that don't use skybox ambient light.
+#[derive(Resource)]
+pub struct SkyboxAmbientFallbackBuffer(pub Buffer);
+
+impl FromWorld for SkyboxAmbientFallbackBuffer {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        Self(
+            render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("skybox_ambient_fallback_buffer"),
+                contents: &vec![0u8; GpuSkyboxAmbient::min_size().get() as usize],
+                usage: BufferUsages::UNIFORM,
+            }),
+        )
+    }
+}
+
+#[derive(Resource)]
+pub struct SkyboxAmbientPipeline {
+    bind_group_layout: BindGroupLayout,
+    pipeline_id: CachedComputePipelineId,
+}
+
+impl FromWorld for SkyboxAmbientPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "skybox_ambient_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_cube(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<Vec4>(false),
+                    storage_buffer::<GpuSkyboxAmbient>(false),
+                ),
+            ),
+        );
+
+        let pipeline_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("skybox_ambient_pipeline".into()),
+            layout: vec![bind_group_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: SKYBOX_AMBIENT_SHADER_HANDLE,
+            shader_defs: Vec::new(),
+            entry_point: "project_skybox".into(),
+        });
+
+        Self {
+            bind_group_layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// The skybox ambient light of a view, in the render world.
+#[derive(Component)]
+pub struct ViewSkyboxAmbient {
+    /// The buffer holding the [`GpuSkyboxAmbient`] for this view.
+    pub buffer: Buffer,
+    /// The bind group for the projection pass, present only if the projection
+    /// has to be (re)computed this frame.
+    bind_group: Option<BindGroup>,
+}
+
+/// Skybox projections that persist across frames, keyed by view entity.
+#[derive(Resource, Default)]
+pub struct SkyboxAmbientCache(EntityHashMap<CachedSkyboxAmbient>);
+
+struct CachedSkyboxAmbient {
+    buffer: Buffer,
+    /// The skybox texture and brightness that `buffer` was last projected
+    /// from, or `None` if the projection hasn't run yet.
+    source: Option<(AssetId<Image>, TextureViewId, f32)>,
+}
+
+pub(crate) fn prepare_skybox_ambient(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    pipeline: Res<SkyboxAmbientPipeline>,
+    images: Res<RenderAssets<Image>>,
+    mut cache: ResMut<SkyboxAmbientCache>,
+    views: Query<
+        (
+            Entity,
+            &Skybox,
+            Option<&RenderViewLightProbes<EnvironmentMapLight>>,
+        ),
+        (With<ExtractedView>, With<SkyboxAmbientLight>),
+    >,
+) {
+    let pipeline_ready = pipeline_cache
+        .get_compute_pipeline(pipeline.pipeline_id)
+        .is_some();
+
+    let mut live_views = EntityHashMap::default();
+    for (entity, skybox, environment_maps) in &views {
+        // An environment map on the view takes precedence over the skybox.
+        if environment_maps.is_some_and(|maps| maps.view_light_probe_info.cubemap_index >= 0) {
+            continue;
+        }
+        let Some(skybox_image) = images.get(&skybox.image) else {
+            continue;
+        };
+
+        let mut cached = cache
+            .0
+            .remove(&entity)
+            .unwrap_or_else(|| CachedSkyboxAmbient {
+                buffer: render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("skybox_ambient_buffer"),
+                    contents: &vec![0u8; GpuSkyboxAmbient::min_size().get() as usize],
+                    usage: BufferUsages::STORAGE | BufferUsages::UNIFORM,
+                }),
+                source: None,
+            });
+
+        let source = (
+            skybox.image.id(),
+            skybox_image.texture_view.id(),
+            skybox.brightness,
+        );
+        let bind_group = if cached.source != Some(source) {
+            let brightness = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("skybox_ambient_brightness_buffer"),
+                contents: bytemuck::bytes_of(&[skybox.brightness, 0.0, 0.0, 0.0]),
+                usage: BufferUsages::UNIFORM,
+            });
+            // Only consider the projection done once the pipeline can actually run it.
+            if pipeline_ready {
+                cached.source = Some(source);
+            }
+            Some(render_device.create_bind_group(
+                "skybox_ambient_bind_group",
+                &pipeline.bind_group_layout,
+                &BindGroupEntries::sequential((
+                    &skybox_image.texture_view,
+                    &skybox_image.sampler,
+                    brightness.as_entire_binding(),
+                    cached.buffer.as_entire_binding(),
+                )),
+            ))
+        } else {
+            None
+        };
+
+        commands.entity(entity).insert(ViewSkyboxAmbient {
+            buffer: cached.buffer.clone(),
+            bind_group,
+        });
+        live_views.insert(entity, cached);
+    }
+
+    // Drop projections belonging to views that no longer use skybox ambient light.
+    cache.0 = live_views;
+}
+
+/// Projects the skybox of a view onto spherical harmonics, when needed.
+#[derive(Default)]
+pub struct SkyboxAmbientNode;
+
+impl ViewNode for SkyboxAmbientNode {
+    type ViewQuery = &'static ViewSkyboxAmbient;
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        skybox_ambient: QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let Some(bind_group) = &skybox_ambient.bind_group else {
+            return Ok(());
+        };
+
+        let pipeline = world.resource::<SkyboxAmbientPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+
+        let mut pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("skybox_ambient_pass"),
+                    timestamp_writes: None,
+                });
+        pass.set_pipeline(compute_pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        // A single workgroup projects all six faces of the cubemap.
+        pass.dispatch_workgroups(1, 1, 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_math::{Vec2, Vec3};
+
+    // CPU mirrors of `face_direction` and `spherical_harmonics_basis` in
+    // `skybox_ambient.wgsl`, and of `skybox_ambient_radiance` in
+    // `pbr_ambient.wgsl`, for a single color channel.
+
+    fn face_direction(face: u32, uv: Vec2) -> Vec3 {
+        match face {
+            0 => Vec3::new(1.0, -uv.y, -uv.x),
+            1 => Vec3::new(-1.0, -uv.y, uv.x),
+            2 => Vec3::new(uv.x, 1.0, uv.y),
+            3 => Vec3::new(uv.x, -1.0, -uv.y),
+            4 => Vec3::new(uv.x, -uv.y, 1.0),
+            _ => Vec3::new(-uv.x, -uv.y, -1.0),
+        }
+    }
+
+    fn spherical_harmonics_basis(d: Vec3) -> [f32; 9] {
+        [
+            0.282095,
+            0.488603 * d.y,
+            0.488603 * d.z,
+            0.488603 * d.x,
+            1.092548 * d.x * d.y,
+            1.092548 * d.y * d.z,
+            0.315392 * (3.0 * d.z * d.z - 1.0),
+            1.092548 * d.x * d.z,
+            0.546274 * (d.x * d.x - d.y * d.y),
+        ]
+    }
+
+    fn project(radiance: impl Fn(Vec3) -> f32) -> [f32; 9] {
+        const SAMPLES_PER_FACE_SIDE: u32 = 32;
+        let mut coefficients = [0.0; 9];
+        let mut weight_sum = 0.0;
+        for face in 0..6 {
+            for y in 0..SAMPLES_PER_FACE_SIDE {
+                for x in 0..SAMPLES_PER_FACE_SIDE {
+                    let uv = (Vec2::new(x as f32, y as f32) + 0.5) / SAMPLES_PER_FACE_SIDE as f32
+                        * 2.0
+                        - 1.0;
+                    let direction = face_direction(face, uv);
+                    let weight = direction.length_squared().powf(-1.5);
+                    let radiance = radiance(direction.normalize());
+                    let basis = spherical_harmonics_basis(direction.normalize());
+                    for (coefficient, basis) in coefficients.iter_mut().zip(basis) {
+                        *coefficient += radiance * basis * weight;
+                    }
+                    weight_sum += weight;
+                }
+            }
+        }
+        coefficients.map(|c| c * 4.0 * std::f32::consts::PI / weight_sum)
+    }
+
+    fn skybox_ambient_radiance(c: &[f32; 9], world_normal: Vec3) -> f32 {
+        let n = Vec3::new(world_normal.x, world_normal.y, -world_normal.z);
+        let mut radiance = c[0] * 0.282095;
+        radiance += (c[1] * n.y + c[2] * n.z + c[3] * n.x) * (0.488603 * 2.0 / 3.0);
+        radiance += (c[4] * n.x * n.y + c[5] * n.y * n.z + c[7] * n.x * n.z) * (1.092548 * 0.25);
+        radiance += c[6] * (0.315392 * 0.25) * (3.0 * n.z * n.z - 1.0);
+        radiance += c[8] * (0.546274 * 0.25) * (n.x * n.x - n.y * n.y);
+        radiance.max(0.0)
+    }
+
+    #[test]
+    fn skybox_ambient_matches_skybox_orientation() {
+        // A sky that is only bright toward world +X +Y -Z. Cubemaps are sampled
+        // with z flipped, so that is cubemap direction +X +Y +Z.
+        let bright_direction = Vec3::new(1.0, 1.0, -1.0).normalize();
+        let coefficients = project(|cubemap_direction| {
+            let world_direction = cubemap_direction * Vec3::new(1.0, 1.0, -1.0);
+            world_direction.dot(bright_direction).max(0.0)
+        });
+
+        let facing = skybox_ambient_radiance(&coefficients, bright_direction);
+        for mirrored in [
+            Vec3::new(-1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, -1.0),
+        ] {
+            let away = skybox_ambient_radiance(&coefficients, bright_direction * mirrored);
+            assert!(
+                facing > away * 1.5,
+                "{facing} should be brighter than {away} mirrored by {mirrored}"
+            );
+        }
+    }
+}
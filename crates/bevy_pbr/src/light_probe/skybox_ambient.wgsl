@@ -0,0 +1,110 @@
+// Projects the skybox cubemap onto the first three bands of spherical harmonics,
+// so that it can be used as ambient light by views without an environment map.
+//
+// A single workgroup takes a fixed number of samples from every cubemap face,
+// weights each one by the solid angle it covers, and reduces the per-thread sums
+// in workgroup memory.
+
+#import bevy_pbr::utils::PI
+
+struct SkyboxAmbient {
+    coefficients: array<vec4<f32>, 9>,
+    enabled: u32,
+}
+
+@group(0) @binding(0) var skybox: texture_cube<f32>;
+@group(0) @binding(1) var skybox_sampler: sampler;
+// Only `x` is used.
+@group(0) @binding(2) var<uniform> brightness: vec4<f32>;
+@group(0) @binding(3) var<storage, read_write> skybox_ambient: SkyboxAmbient;
+
+const WORKGROUP_SIZE: u32 = 64u;
+// The number of samples along each side of a cubemap face.
+const SAMPLES_PER_FACE_SIDE: u32 = 32u;
+
+var<workgroup> partial_coefficients: array<array<vec3<f32>, 9>, WORKGROUP_SIZE>;
+var<workgroup> partial_weights: array<f32, WORKGROUP_SIZE>;
+
+// Returns the (unnormalized) direction through `uv` on the given cubemap face,
+// with `uv` in [-1, 1].
+fn face_direction(face: u32, uv: vec2<f32>) -> vec3<f32> {
+    switch face {
+        case 0u: { return vec3(1.0, -uv.y, -uv.x); }
+        case 1u: { return vec3(-1.0, -uv.y, uv.x); }
+        case 2u: { return vec3(uv.x, 1.0, uv.y); }
+        case 3u: { return vec3(uv.x, -1.0, -uv.y); }
+        case 4u: { return vec3(uv.x, -uv.y, 1.0); }
+        default: { return vec3(-uv.x, -uv.y, -1.0); }
+    }
+}
+
+// The real spherical harmonics basis functions up to band 2, for a unit direction.
+fn spherical_harmonics_basis(d: vec3<f32>) -> array<f32, 9> {
+    return array<f32, 9>(
+        0.282095,
+        0.488603 * d.y,
+        0.488603 * d.z,
+        0.488603 * d.x,
+        1.092548 * d.x * d.y,
+        1.092548 * d.y * d.z,
+        0.315392 * (3.0 * d.z * d.z - 1.0),
+        1.092548 * d.x * d.z,
+        0.546274 * (d.x * d.x - d.y * d.y),
+    );
+}
+
+@compute
+@workgroup_size(64, 1, 1)
+fn project_skybox(@builtin(local_invocation_index) thread: u32) {
+    // Sample the mip level whose resolution is closest to the sampling grid, to
+    // avoid aliasing on large skyboxes.
+    let face_size = textureDimensions(skybox).x;
+    let level = min(
+        firstLeadingBit(max(face_size / SAMPLES_PER_FACE_SIDE, 1u)),
+        textureNumLevels(skybox) - 1u
+    );
+
+    var coefficients: array<vec3<f32>, 9>;
+    var weight_sum = 0.0;
+
+    let samples_per_face = SAMPLES_PER_FACE_SIDE * SAMPLES_PER_FACE_SIDE;
+    for (var i = thread; i < 6u * samples_per_face; i += WORKGROUP_SIZE) {
+        let face = i / samples_per_face;
+        let texel = vec2(i % SAMPLES_PER_FACE_SIDE, (i % samples_per_face) / SAMPLES_PER_FACE_SIDE);
+        let uv = (vec2<f32>(texel) + 0.5) / f32(SAMPLES_PER_FACE_SIDE) * 2.0 - 1.0;
+        let direction = face_direction(face, uv);
+
+        // The solid angle covered by a sample is proportional to (1 + u² + v²)^(-3/2).
+        let weight = pow(dot(direction, direction), -1.5);
+        let radiance = textureSampleLevel(skybox, skybox_sampler, direction, f32(level)).rgb;
+
+        var basis = spherical_harmonics_basis(normalize(direction));
+        for (var j = 0u; j < 9u; j += 1u) {
+            coefficients[j] += radiance * basis[j] * weight;
+        }
+        weight_sum += weight;
+    }
+
+    partial_coefficients[thread] = coefficients;
+    partial_weights[thread] = weight_sum;
+    workgroupBarrier();
+
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride /= 2u) {
+        if thread < stride {
+            for (var j = 0u; j < 9u; j += 1u) {
+                partial_coefficients[thread][j] += partial_coefficients[thread + stride][j];
+            }
+            partial_weights[thread] += partial_weights[thread + stride];
+        }
+        workgroupBarrier();
+    }
+
+    if thread == 0u {
+        // Normalize the weights so that they integrate to the full sphere.
+        let scale = 4.0 * PI * brightness.x / partial_weights[0];
+        for (var j = 0u; j < 9u; j += 1u) {
+            skybox_ambient.coefficients[j] = vec4(partial_coefficients[0][j] * scale, 0.0);
+        }
+        skybox_ambient.enabled = 1u;
+    }
+}
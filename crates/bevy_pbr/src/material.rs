@@ -30,7 +30,10 @@ use bevy_render::{
     view::{ExtractedView, Msaa, VisibleEntities},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_utils::{tracing::error, HashMap, HashSet};
+use bevy_utils::{
+    tracing::{error, info},
+    HashMap, HashSet,
+};
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::{hash::Hash, num::NonZeroU32};
@@ -122,6 +125,16 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         OpaqueRendererMethod::Forward
     }
 
+    /// Returns whether this material uses features the deferred renderer doesn't support.
+    ///
+    /// [`OpaqueRendererMethod::Auto`] materials returning `true` are rendered with the forward
+    /// renderer, even when the [`DefaultOpaqueRendererMethod`] selects the deferred renderer.
+    /// Materials explicitly set to [`OpaqueRendererMethod::Deferred`] are not affected.
+    #[inline]
+    fn requires_forward_rendering(&self) -> bool {
+        false
+    }
+
     #[inline]
     /// Add a bias to the view depth of the mesh which can be used to force a specific render order.
     /// for meshes with similar depth, to avoid z-fighting.
@@ -130,6 +143,15 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         0.0
     }
 
+    #[inline]
+    /// Overrides whether the material writes to the depth buffer in the main pass.
+    ///
+    /// If `None`, depth writes follow the [`AlphaMode`]: opaque and alpha-masked materials write
+    /// depth, while blended materials don't. The prepass and shadow passes always write depth.
+    fn depth_write_enabled(&self) -> Option<bool> {
+        None
+    }
+
     #[inline]
     /// Returns whether the material would like to read from [`ViewTransmissionTexture`](bevy_core_pipeline::core_3d::ViewTransmissionTexture).
     ///
@@ -139,6 +161,33 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         false
     }
 
+    #[inline]
+    /// Returns whether a transparent material records its depth in the transparent depth prepass,
+    /// see [`TransparentDepthPrepass`](bevy_core_pipeline::prepass::TransparentDepthPrepass).
+    ///
+    /// Only applies to materials with a transparent [`AlphaMode`]. Fragments with an alpha
+    /// close to zero are discarded.
+    fn transparent_depth_prepass(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    /// Returns the alpha above which the fragments of a blended material are rendered deferred.
+    ///
+    /// Only applies to [`AlphaMode::Blend`] and [`AlphaMode::Premultiplied`] materials whose
+    /// [`opaque_render_method`](Material::opaque_render_method) resolves to
+    /// [`OpaqueRendererMethod::Deferred`], in views with a
+    /// [`DeferredPrepass`](bevy_core_pipeline::prepass::DeferredPrepass). The mesh is then drawn
+    /// twice: fragments with an alpha of at least the cutoff go to the G-buffer, so they receive
+    /// the deferred lighting like opaque geometry, and the remaining fragments are blended in the
+    /// forward transparent pass.
+    ///
+    /// The shaders of the material are responsible for discarding the fragments of the other
+    /// pass; they are specialized with the `DEFERRED_OPAQUE_SPLIT` shader def for both passes.
+    fn deferred_opaque_cutoff(&self) -> Option<f32> {
+        None
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the default prepass vertex shader
     /// will be used.
     ///
@@ -253,6 +302,10 @@ where
     fn finish(&self, app: &mut App) {
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<MaterialPipeline<M>>();
+            render_app
+                .world
+                .resource_mut::<MaterialPipeline<M>>()
+                .prepass_enabled = self.prepass_enabled;
         }
     }
 }
@@ -303,6 +356,10 @@ pub struct MaterialPipeline<M: Material> {
     pub material_layout: BindGroupLayout,
     pub vertex_shader: Option<Handle<Shader>>,
     pub fragment_shader: Option<Handle<Shader>>,
+    /// Whether the [`PrepassPlugin`] is enabled for this material, see
+    /// [`MaterialPlugin::prepass_enabled`]. Without it, the main pass can't reuse the normals of
+    /// the normal prepass, as the material doesn't write them.
+    pub prepass_enabled: bool,
     pub marker: PhantomData<M>,
 }
 
@@ -313,6 +370,7 @@ impl<M: Material> Clone for MaterialPipeline<M> {
             material_layout: self.material_layout.clone(),
             vertex_shader: self.vertex_shader.clone(),
             fragment_shader: self.fragment_shader.clone(),
+            prepass_enabled: self.prepass_enabled,
             marker: PhantomData,
         }
     }
@@ -340,6 +398,21 @@ where
 
         descriptor.layout.insert(2, self.material_layout.clone());
 
+        if key.mesh_key.contains(MeshPipelineKey::DEPTH_WRITE_OVERRIDE) {
+            if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+                depth_stencil.depth_write_enabled =
+                    key.mesh_key.contains(MeshPipelineKey::DEPTH_WRITE_ENABLED);
+            }
+        }
+
+        if !self.prepass_enabled {
+            if let Some(fragment) = descriptor.fragment.as_mut() {
+                fragment
+                    .shader_defs
+                    .retain(|def| *def != "LOAD_PREPASS_NORMALS".into());
+            }
+        }
+
         M::specialize(self, &mut descriptor, layout, key)?;
         Ok(descriptor)
     }
@@ -363,6 +436,7 @@ impl<M: Material> FromWorld for MaterialPipeline<M> {
                 ShaderRef::Handle(handle) => Some(handle),
                 ShaderRef::Path(path) => Some(asset_server.load(path)),
             },
+            prepass_enabled: true,
             marker: PhantomData,
         }
     }
@@ -419,6 +493,16 @@ pub const fn alpha_mode_pipeline_key(alpha_mode: AlphaMode) -> MeshPipelineKey {
     }
 }
 
+pub const fn depth_write_pipeline_key(depth_write_enabled: Option<bool>) -> MeshPipelineKey {
+    match depth_write_enabled {
+        Some(true) => {
+            MeshPipelineKey::DEPTH_WRITE_OVERRIDE.union(MeshPipelineKey::DEPTH_WRITE_ENABLED)
+        }
+        Some(false) => MeshPipelineKey::DEPTH_WRITE_OVERRIDE,
+        None => MeshPipelineKey::NONE,
+    }
+}
+
 pub const fn tonemapping_pipeline_key(tonemapping: Tonemapping) -> MeshPipelineKey {
     match tonemapping {
         Tonemapping::None => MeshPipelineKey::TONEMAP_METHOD_NONE,
@@ -468,12 +552,19 @@ pub fn queue_material_meshes<M: Material>(
     render_mesh_instances: Res<RenderMeshInstances>,
     render_material_instances: Res<RenderMaterialInstances<M>>,
     render_lightmaps: Res<RenderLightmaps>,
+    point_light_shadow_map: Res<PointLightShadowMap>,
+    (pbr_debug_settings, lighting_strategy): (Res<PbrDebugSettings>, Res<LightingStrategy>),
     mut views: Query<(
         &ExtractedView,
         &VisibleEntities,
         Option<&Tonemapping>,
         Option<&DebandDither>,
-        Option<&ShadowFilteringMethod>,
+        (
+            Option<&ShadowFilteringMethod>,
+            Option<&ShadowFilteringQuality>,
+            Option<&PointLightShadowFilteringMethod>,
+            Has<NoShadowFilteringJitter>,
+        ),
         Has<ScreenSpaceAmbientOcclusionSettings>,
         (
             Has<NormalPrepass>,
@@ -481,7 +572,7 @@ pub fn queue_material_meshes<M: Material>(
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
         ),
-        Option<&Camera3d>,
+        (Option<&Camera3d>, Option<&FogSettings>),
         Has<TemporalJitter>,
         Option<&Projection>,
         &mut RenderPhase<Opaque3d>,
@@ -501,10 +592,15 @@ pub fn queue_material_meshes<M: Material>(
         visible_entities,
         tonemapping,
         dither,
-        shadow_filter_method,
+        (
+            shadow_filter_method,
+            shadow_filter_quality,
+            point_light_shadow_filter_method,
+            no_shadow_filter_jitter,
+        ),
         ssao,
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
-        camera_3d,
+        (camera_3d, fog),
         temporal_jitter,
         projection,
         mut opaque_phase,
@@ -550,6 +646,10 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if fog.is_some_and(|fog| fog.color_gradient.is_some()) {
+            view_key |= MeshPipelineKey::FOG_COLOR_GRADIENT;
+        }
+
         if let Some(projection) = projection {
             view_key |= match projection {
                 Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
@@ -569,6 +669,36 @@ pub fn queue_material_meshes<M: Material>(
             }
         }
 
+        match shadow_filter_quality.unwrap_or(&ShadowFilteringQuality::default()) {
+            ShadowFilteringQuality::Low => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_LOW;
+            }
+            ShadowFilteringQuality::Medium => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_MEDIUM;
+            }
+            ShadowFilteringQuality::High => {
+                view_key |= MeshPipelineKey::SHADOW_FILTER_QUALITY_HIGH;
+            }
+        }
+
+        if let Some(PointLightShadowFilteringMethod::Pcf) = point_light_shadow_filter_method {
+            view_key |= MeshPipelineKey::POINT_LIGHT_SHADOW_FILTER_PCF;
+        }
+
+        if temporal_jitter && !no_shadow_filter_jitter {
+            view_key |= MeshPipelineKey::SHADOW_FILTER_JITTER;
+        }
+
+        if point_light_shadow_map.mode == PointLightShadowMapMode::DualParaboloid {
+            view_key |= MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS;
+        }
+
+        view_key |= MeshPipelineKey::from_pbr_debug_visualization(pbr_debug_settings.visualization);
+
+        if *lighting_strategy == LightingStrategy::SimpleForward {
+            view_key |= MeshPipelineKey::SIMPLE_FORWARD_LIGHTING;
+        }
+
         if !view.hdr {
             if let Some(tonemapping) = tonemapping {
                 view_key |= MeshPipelineKey::TONEMAP_IN_SHADER;
@@ -603,7 +733,11 @@ pub fn queue_material_meshes<M: Material>(
 
             let forward = match material.properties.render_method {
                 OpaqueRendererMethod::Forward => true,
-                OpaqueRendererMethod::Deferred => false,
+                // The deferred lighting pass fogs every mesh, so meshes left out of the fog are
+                // drawn forward.
+                OpaqueRendererMethod::Deferred => {
+                    fog.is_some_and(|fog| fog.excludes(&mesh_instance.render_layers))
+                }
                 OpaqueRendererMethod::Auto => unreachable!(),
             };
 
@@ -621,6 +755,12 @@ pub fn queue_material_meshes<M: Material>(
 
             mesh_key |= alpha_mode_pipeline_key(material.properties.alpha_mode);
 
+            mesh_key |= depth_write_pipeline_key(material.properties.depth_write_enabled);
+
+            if deferred_prepass && !forward && material.properties.splits_deferred_opaque() {
+                mesh_key |= MeshPipelineKey::DEFERRED_OPAQUE_SPLIT;
+            }
+
             if render_lightmaps
                 .render_lightmaps
                 .contains_key(visible_entity)
@@ -720,6 +860,17 @@ pub fn queue_material_meshes<M: Material>(
 }
 
 /// Default render method used for opaque materials.
+///
+/// This can be changed at runtime, e.g. to offer a forward vs deferred rendering setting. Materials
+/// using [`OpaqueRendererMethod::Auto`] are then prepared again and move to the new render method.
+/// Note that deferred materials are only drawn by cameras with a
+/// [`DeferredPrepass`](bevy_core_pipeline::prepass::DeferredPrepass), so it should be added to the
+/// cameras before switching to [`OpaqueRendererMethod::Deferred`].
+///
+/// Setting it to [`OpaqueRendererMethod::Auto`] picks the render method per material: materials
+/// using features the deferred renderer doesn't support (see
+/// [`Material::requires_forward_rendering`]) are rendered with the forward renderer, all others
+/// with the deferred renderer.
 #[derive(Default, Resource, Clone, Debug, ExtractResource, Reflect)]
 pub struct DefaultOpaqueRendererMethod(OpaqueRendererMethod);
 
@@ -739,6 +890,14 @@ impl DefaultOpaqueRendererMethod {
     pub fn set_to_deferred(&mut self) {
         self.0 = OpaqueRendererMethod::Deferred;
     }
+
+    pub fn auto() -> Self {
+        DefaultOpaqueRendererMethod(OpaqueRendererMethod::Auto)
+    }
+
+    pub fn set_to_auto(&mut self) {
+        self.0 = OpaqueRendererMethod::Auto;
+    }
 }
 
 /// Render method used for opaque materials.
@@ -758,7 +917,8 @@ impl DefaultOpaqueRendererMethod {
 /// for one pass over geometry, but is at the cost of not being able to use MSAA, and has heavier
 /// bandwidth usage which can be unsuitable for low end mobile or other bandwidth-constrained devices.
 ///
-/// If a material indicates `OpaqueRendererMethod::Auto`, `DefaultOpaqueRendererMethod` will be used.
+/// If a material indicates `OpaqueRendererMethod::Auto`, `DefaultOpaqueRendererMethod` will be used,
+/// unless the material [requires forward rendering](Material::requires_forward_rendering).
 #[derive(Default, Clone, Copy, Debug, Reflect)]
 pub enum OpaqueRendererMethod {
     #[default]
@@ -778,11 +938,29 @@ pub struct MaterialProperties {
     /// for meshes with equal depth, to avoid z-fighting.
     /// The bias is in depth-texture units so large values may be needed to overcome small depth differences.
     pub depth_bias: f32,
+    /// Overrides whether the material writes to the depth buffer in the main pass, see
+    /// [`Material::depth_write_enabled`].
+    pub depth_write_enabled: Option<bool>,
     /// Whether the material would like to read from [`ViewTransmissionTexture`](bevy_core_pipeline::core_3d::ViewTransmissionTexture).
     ///
     /// This allows taking color output from the [`Opaque3d`] pass as an input, (for screen-space transmission) but requires
     /// rendering to take place in a separate [`Transmissive3d`] pass.
     pub reads_view_transmission_texture: bool,
+    /// Whether a transparent material records its depth in the transparent depth prepass.
+    pub transparent_depth_prepass: bool,
+    /// The alpha above which the fragments of a blended material are rendered deferred, see
+    /// [`Material::deferred_opaque_cutoff`].
+    pub deferred_opaque_cutoff: Option<f32>,
+}
+
+impl MaterialProperties {
+    /// Whether the opaque fragments of this blended material are rendered deferred, in views with
+    /// a [`DeferredPrepass`](bevy_core_pipeline::prepass::DeferredPrepass).
+    pub fn splits_deferred_opaque(&self) -> bool {
+        self.deferred_opaque_cutoff.is_some()
+            && matches!(self.render_method, OpaqueRendererMethod::Deferred)
+            && matches!(self.alpha_mode, AlphaMode::Blend | AlphaMode::Premultiplied)
+    }
 }
 
 /// Data prepared for a [`Material`] instance.
@@ -873,8 +1051,16 @@ pub fn extract_materials<M: Material>(
     mut commands: Commands,
     mut events: Extract<EventReader<AssetEvent<M>>>,
     assets: Extract<Res<Assets<M>>>,
+    default_opaque_render_method: Extract<Res<DefaultOpaqueRendererMethod>>,
 ) {
     let mut changed_assets = HashSet::default();
+    // The render method of `OpaqueRendererMethod::Auto` materials is resolved when they are
+    // prepared, so they need to be prepared again to move between the forward and deferred passes.
+    if default_opaque_render_method.is_changed() {
+        changed_assets.extend(assets.iter().filter_map(|(id, material)| {
+            matches!(material.opaque_render_method(), OpaqueRendererMethod::Auto).then_some(id)
+        }));
+    }
     let mut removed = Vec::new();
     for event in events.read() {
         #[allow(clippy::match_same_arms)]
@@ -931,6 +1117,7 @@ pub fn prepare_materials<M: Material>(
     fallback_image: Res<FallbackImage>,
     pipeline: Res<MaterialPipeline<M>>,
     default_opaque_render_method: Res<DefaultOpaqueRendererMethod>,
+    mut forward_override_logged: Local<bool>,
 ) {
     let queued_assets = std::mem::take(&mut prepare_next_frame.assets);
     for (id, material) in queued_assets.into_iter() {
@@ -941,6 +1128,7 @@ pub fn prepare_materials<M: Material>(
             &fallback_image,
             &pipeline,
             default_opaque_render_method.0,
+            &mut forward_override_logged,
         ) {
             Ok(prepared_asset) => {
                 render_materials.insert(id, prepared_asset);
@@ -963,6 +1151,7 @@ pub fn prepare_materials<M: Material>(
             &fallback_image,
             &pipeline,
             default_opaque_render_method.0,
+            &mut forward_override_logged,
         ) {
             Ok(prepared_asset) => {
                 render_materials.insert(id, prepared_asset);
@@ -981,6 +1170,7 @@ fn prepare_material<M: Material>(
     fallback_image: &FallbackImage,
     pipeline: &MaterialPipeline<M>,
     default_opaque_render_method: OpaqueRendererMethod,
+    forward_override_logged: &mut bool,
 ) -> Result<PreparedMaterial<M>, AsBindGroupError> {
     let prepared = material.as_bind_group(
         &pipeline.material_layout,
@@ -991,7 +1181,25 @@ fn prepare_material<M: Material>(
     let method = match material.opaque_render_method() {
         OpaqueRendererMethod::Forward => OpaqueRendererMethod::Forward,
         OpaqueRendererMethod::Deferred => OpaqueRendererMethod::Deferred,
-        OpaqueRendererMethod::Auto => default_opaque_render_method,
+        OpaqueRendererMethod::Auto => match default_opaque_render_method {
+            OpaqueRendererMethod::Forward => OpaqueRendererMethod::Forward,
+            // An `Auto` default prefers the deferred renderer where the material supports it
+            OpaqueRendererMethod::Deferred | OpaqueRendererMethod::Auto => {
+                if material.requires_forward_rendering() {
+                    if !*forward_override_logged {
+                        info!(
+                            "Rendering `{}` materials that require forward rendering with the \
+                            forward renderer instead of the default deferred renderer",
+                            std::any::type_name::<M>()
+                        );
+                        *forward_override_logged = true;
+                    }
+                    OpaqueRendererMethod::Forward
+                } else {
+                    OpaqueRendererMethod::Deferred
+                }
+            }
+        },
     };
     Ok(PreparedMaterial {
         bindings: prepared.bindings,
@@ -1000,7 +1208,10 @@ fn prepare_material<M: Material>(
         properties: MaterialProperties {
             alpha_mode: material.alpha_mode(),
             depth_bias: material.depth_bias(),
+            depth_write_enabled: material.depth_write_enabled(),
             reads_view_transmission_texture: material.reads_view_transmission_texture(),
+            transparent_depth_prepass: material.transparent_depth_prepass(),
+            deferred_opaque_cutoff: material.deferred_opaque_cutoff(),
             render_method: method,
         },
     })
@@ -1,4 +1,5 @@
-use bevy_reflect::Reflect;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 /// The [parallax mapping] method to use to compute depth based on the
 /// material's [`depth_map`].
@@ -43,3 +44,20 @@ impl ParallaxMappingMethod {
         }
     }
 }
+
+/// Overrides the [`parallax_depth_scale`] of the material of the entity it is added to.
+///
+/// This lets entities sharing a material use different depths, or animate the depth of a
+/// single entity, without creating a material per entity. Entities without this component use
+/// the value from their material.
+///
+/// [`parallax_depth_scale`]: crate::StandardMaterial::parallax_depth_scale
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct ParallaxDepthScale(pub f32);
+
+impl Default for ParallaxDepthScale {
+    fn default() -> Self {
+        Self(0.1)
+    }
+}
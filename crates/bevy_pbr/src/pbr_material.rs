@@ -1,10 +1,15 @@
-use bevy_asset::{Asset, Handle};
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
 use bevy_math::{Affine2, Vec2, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    color::LegacyColor, mesh::MeshVertexBufferLayout, render_asset::RenderAssets,
-    render_resource::*, texture::Image,
+    color::LegacyColor,
+    mesh::{Mesh, MeshVertexBufferLayout},
+    render_asset::RenderAssets,
+    render_resource::*,
+    texture::Image,
 };
+use bevy_utils::{tracing::warn, HashSet};
 
 use crate::deferred::DEFAULT_PBR_DEFERRED_LIGHTING_PASS_ID;
 use crate::*;
@@ -63,6 +68,17 @@ pub struct StandardMaterial {
     /// it just adds a value to the color seen on screen.
     pub emissive: LegacyColor,
 
+    /// A multiplier applied to the emitted light, on top of [`emissive`] and the [`emissive_texture`].
+    ///
+    /// Values above `1.0` push the emissive component into HDR range, which is useful to drive
+    /// bloom without having to encode the brightness in the color itself.
+    ///
+    /// Defaults to `1.0`.
+    ///
+    /// [`emissive`]: StandardMaterial::emissive
+    /// [`emissive_texture`]: StandardMaterial::emissive_texture
+    pub emissive_intensity: f32,
+
     /// The emissive map, multiplies pixels with [`emissive`]
     /// to get the final "emitting" color of a surface.
     ///
@@ -76,7 +92,19 @@ pub struct StandardMaterial {
     #[dependency]
     pub emissive_texture: Option<Handle<Image>>,
 
-    /// Linear perceptual roughness, clamped to `[0.089, 1.0]` in the shader.
+    /// The speed, in UV units per second, at which the UVs of the [`emissive_texture`] scroll.
+    ///
+    /// This is useful for effects such as scrolling neon signs or energy fields. The offset is
+    /// applied after [`uv_transform`] and only affects the emissive texture.
+    ///
+    /// Defaults to [`Vec2::ZERO`], which doesn't scroll.
+    ///
+    /// [`emissive_texture`]: StandardMaterial::emissive_texture
+    /// [`uv_transform`]: StandardMaterial::uv_transform
+    pub emissive_uv_speed: Vec2,
+
+    /// Linear perceptual roughness, clamped to `[0.089, 1.0]` in the shader, unless
+    /// [`clamp_roughness`](StandardMaterial::clamp_roughness) is `false`.
     ///
     /// Defaults to `0.5`.
     ///
@@ -93,6 +121,27 @@ pub struct StandardMaterial {
     // See <https://google.github.io/filament/Filament.html#materialsystem/parameterization/>
     pub perceptual_roughness: f32,
 
+    /// Whether to clamp [`perceptual_roughness`](StandardMaterial::perceptual_roughness) to a
+    /// minimum of `0.089` in the shader.
+    ///
+    /// The clamp avoids precision issues and specular aliasing, where very sharp highlights
+    /// flicker as they fall between pixels. Disabling it allows mirror-sharp highlights, e.g. for
+    /// chrome or stylized reflections, at the cost of that aliasing.
+    ///
+    /// Defaults to `true`.
+    pub clamp_roughness: bool,
+
+    /// Whether to apply geometric specular anti-aliasing, which raises the roughness where the
+    /// surface normal changes quickly across the screen.
+    ///
+    /// This reduces shimmering highlights on thin or highly detailed meshes, at the cost of
+    /// slightly blurrier highlights on curved surfaces seen from afar.
+    ///
+    /// See Tokuyoshi and Kaplanyan, [Improved Geometric Specular Antialiasing](https://www.jp.square-enix.com/tech/library/pdf/ImprovedGeometricSpecularAA.pdf).
+    ///
+    /// Defaults to `false`.
+    pub specular_antialiasing: bool,
+
     /// How "metallic" the material appears, within `[0.0, 1.0]`.
     ///
     /// This should be set to 0.0 for dielectric materials or 1.0 for metallic materials.
@@ -125,6 +174,56 @@ pub struct StandardMaterial {
     #[dependency]
     pub metallic_roughness_texture: Option<Handle<Image>>,
 
+    /// Which set of parameters describes how metallic and rough the surface is.
+    ///
+    /// With [`PbrWorkflow::SpecularGlossiness`], [`base_color`] and [`base_color_texture`] are
+    /// the diffuse color, and [`specular_color`], [`glossiness`] and
+    /// `specular_glossiness_texture` are used instead of [`metallic`],
+    /// [`perceptual_roughness`] and [`metallic_roughness_texture`].
+    ///
+    /// Defaults to [`PbrWorkflow::MetallicRoughness`].
+    ///
+    /// [`base_color`]: StandardMaterial::base_color
+    /// [`base_color_texture`]: StandardMaterial::base_color_texture
+    /// [`specular_color`]: StandardMaterial::specular_color
+    /// [`glossiness`]: StandardMaterial::glossiness
+    /// [`metallic`]: StandardMaterial::metallic
+    /// [`perceptual_roughness`]: StandardMaterial::perceptual_roughness
+    /// [`metallic_roughness_texture`]: StandardMaterial::metallic_roughness_texture
+    pub workflow: PbrWorkflow,
+
+    /// The specular color of the surface, when using [`PbrWorkflow::SpecularGlossiness`].
+    ///
+    /// Defaults to 4% linear gray, which matches the default [`reflectance`] of dielectrics.
+    ///
+    /// [`reflectance`]: StandardMaterial::reflectance
+    pub specular_color: LegacyColor,
+
+    /// How smooth the surface is, within `[0.0, 1.0]`, when using
+    /// [`PbrWorkflow::SpecularGlossiness`]. This is one minus the perceptual roughness.
+    ///
+    /// Defaults to `0.5`, which matches the default [`perceptual_roughness`].
+    ///
+    /// [`perceptual_roughness`]: StandardMaterial::perceptual_roughness
+    pub glossiness: f32,
+
+    /// Specular color and glossiness maps, stored as a single texture, when using
+    /// [`PbrWorkflow::SpecularGlossiness`].
+    ///
+    /// The RGB channels contain the specular color in sRGB, and the alpha channel contains the
+    /// glossiness. They are multiplied by [`specular_color`] and [`glossiness`].
+    ///
+    /// **Important:** This texture is only available with the `pbr_specular_glossiness_textures`
+    /// Cargo feature.
+    ///
+    /// [`specular_color`]: StandardMaterial::specular_color
+    /// [`glossiness`]: StandardMaterial::glossiness
+    #[texture(21)]
+    #[sampler(22)]
+    #[dependency]
+    #[cfg(feature = "pbr_specular_glossiness_textures")]
+    pub specular_glossiness_texture: Option<Handle<Image>>,
+
     /// Specular intensity for non-metals on a linear scale of `[0.0, 1.0]`.
     ///
     /// Use the value as a way to control the intensity of the
@@ -138,6 +237,76 @@ pub struct StandardMaterial {
     #[doc(alias = "specular_intensity")]
     pub reflectance: f32,
 
+    /// A texture whose red channel is multiplied into [`reflectance`], to vary the specular
+    /// intensity of non-metals across the surface, e.g. for wet patches on pavement.
+    ///
+    /// Defaults to `None`, in which case only [`reflectance`] is used.
+    ///
+    /// **Important:** This texture is only available with the `pbr_reflectance_textures` Cargo
+    /// feature.
+    ///
+    /// [`reflectance`]: StandardMaterial::reflectance
+    #[texture(19)]
+    #[sampler(20)]
+    #[dependency]
+    #[cfg(feature = "pbr_reflectance_textures")]
+    pub reflectance_texture: Option<Handle<Image>>,
+
+    /// Scales the specular reflections this material picks up from environment maps.
+    ///
+    /// Use this to keep materials such as very rough plaster from showing strong environment
+    /// reflections where a probe is present, independently of the probe's own intensity. Only
+    /// affects [`EnvironmentMapLight`](crate::environment_map::EnvironmentMapLight)s and
+    /// reflection probes, not direct lighting.
+    ///
+    /// **Note:** Only supported by the forward renderer; materials rendered with the deferred
+    /// renderer always use `1.0`.
+    ///
+    /// Defaults to `1.0`.
+    pub reflectance_env_scale: f32,
+
+    /// Whether this material samples environment maps and reflection probes at all.
+    ///
+    /// Unlike setting [`StandardMaterial::reflectance_env_scale`] to `0.0`, disabling this compiles
+    /// the environment map lookups out of the shader, saving texture bandwidth on materials that
+    /// don't need reflections. Also removes the diffuse light environment maps provide.
+    ///
+    /// **Note:** Only supported by the forward renderer; materials rendered with the deferred
+    /// renderer always use environment maps.
+    ///
+    /// Defaults to `true`.
+    pub use_environment_map: bool,
+
+    /// Whether this material receives diffuse light from environment maps and irradiance volumes.
+    ///
+    /// Together with [`StandardMaterial::receive_env_specular`], this allows e.g. a stylized
+    /// material to pick up reflections but not the diffuse light of its surroundings. Doesn't
+    /// affect lightmaps or [`AmbientLight`](crate::AmbientLight).
+    ///
+    /// Defaults to `true`.
+    pub receive_env_diffuse: bool,
+
+    /// Whether this material receives specular reflections from environment maps and reflection
+    /// probes.
+    ///
+    /// See also [`StandardMaterial::receive_env_diffuse`] and
+    /// [`StandardMaterial::reflectance_env_scale`].
+    ///
+    /// Defaults to `true`.
+    pub receive_env_specular: bool,
+
+    /// Whether this material has specular highlights and reflections at all.
+    ///
+    /// Setting this to `false` removes the specular lobe of every light source, including
+    /// [`AmbientLight`](crate::AmbientLight) and environment maps, unlike setting
+    /// [`StandardMaterial::perceptual_roughness`] to `1.0`, which still leaves a faint sheen.
+    /// Useful for matte and stylized surfaces. Since metals only reflect specularly, a fully
+    /// [`metallic`](StandardMaterial::metallic) material with specular disabled renders black
+    /// (apart from [`emissive`](StandardMaterial::emissive) light).
+    ///
+    /// Defaults to `true`.
+    pub specular_enabled: bool,
+
     /// The amount of light transmitted _diffusely_ through the material (i.e. “translucency”)
     ///
     /// Implemented as a second, flipped [Lambertian diffuse](https://en.wikipedia.org/wiki/Lambertian_reflectance) lobe,
@@ -157,6 +326,8 @@ pub struct StandardMaterial {
     /// - The material's [`StandardMaterial::base_color`] also modulates the transmitted light;
     /// - To receive transmitted shadows on the diffuse transmission lobe (i.e. the “backside”) of the material,
     ///   use the [`TransmittedShadowReceiver`] component.
+    /// - Thin surfaces such as leaves are usually single planes seen from both sides: set
+    ///   [`StandardMaterial::two_sided`] so that they glow when backlit from either side.
     #[doc(alias = "translucency")]
     pub diffuse_transmission: f32,
 
@@ -328,6 +499,16 @@ pub struct StandardMaterial {
     #[dependency]
     pub occlusion_texture: Option<Handle<Image>>,
 
+    /// Whether the emitted light is darkened by occlusion, like the rest of the lighting.
+    ///
+    /// When `true`, [`emissive`](StandardMaterial::emissive) is multiplied by the diffuse
+    /// occlusion of the fragment, which combines the
+    /// [`occlusion_texture`](StandardMaterial::occlusion_texture) and screen space ambient
+    /// occlusion. This can be useful for baked lighting stored in the emissive channel.
+    ///
+    /// Defaults to `false`, leaving emissive unaffected by occlusion.
+    pub occlude_emissive: bool,
+
     /// Support two-sided lighting by automatically flipping the normals for "back" faces
     /// within the PBR lighting shader.
     ///
@@ -336,6 +517,18 @@ pub struct StandardMaterial {
     /// which can be done via `cull_mode`.
     pub double_sided: bool,
 
+    /// Light and draw both sides of the mesh, e.g. for thin foliage.
+    ///
+    /// This enables [`double_sided`](StandardMaterial::double_sided) and disables backface
+    /// culling, ignoring [`cull_mode`](StandardMaterial::cull_mode). Back faces then have their
+    /// normal flipped towards the viewer, so that
+    /// [`diffuse_transmission`](StandardMaterial::diffuse_transmission) lets light coming from
+    /// the other side of the surface through, whichever side is seen. Front faces are shaded as
+    /// usual.
+    ///
+    /// Defaults to `false`.
+    pub two_sided: bool,
+
     /// Whether to cull the "front", "back" or neither side of a mesh.
     /// If set to `None`, the two sides of the mesh are visible.
     ///
@@ -366,6 +559,17 @@ pub struct StandardMaterial {
     /// Whether to enable fog for this material.
     pub fog_enabled: bool,
 
+    /// How strongly fog affects this material, from `0.0` (not at all) to `1.0` (fully).
+    ///
+    /// This lets specific materials partially show through fog, e.g. emissive signs that stay
+    /// dimly visible through mist. Has no effect if [`StandardMaterial::fog_enabled`] is `false`.
+    ///
+    /// **Note:** Only supported by the forward renderer; materials rendered with the deferred
+    /// renderer are always fully fogged.
+    ///
+    /// Defaults to `1.0`.
+    pub fog_intensity: f32,
+
     /// How to apply the alpha channel of the `base_color_texture`.
     ///
     /// See [`AlphaMode`] for details. Defaults to [`AlphaMode::Opaque`].
@@ -383,6 +587,59 @@ pub struct StandardMaterial {
     /// [z-fighting]: https://en.wikipedia.org/wiki/Z-fighting
     pub depth_bias: f32,
 
+    /// Overrides whether this material writes to the depth buffer in the main pass.
+    ///
+    /// By default (`None`), depth writes follow the [`AlphaMode`]: opaque and alpha-masked
+    /// materials write depth, while blended materials don't. Setting this to `Some(true)` lets
+    /// blended materials write depth too, which some decal and hair techniques rely on.
+    ///
+    /// The prepass and shadow passes are not affected, as they always need to write depth.
+    pub depth_write_enabled: Option<bool>,
+
+    /// Whether to shade this material with per-face (flat) normals instead of the interpolated
+    /// vertex normals.
+    ///
+    /// The face normal is derived from the screen-space derivatives of the world position, so
+    /// meshes don't need duplicated vertices to get a faceted, low-poly look. Normal maps are
+    /// applied on top of the face normal.
+    ///
+    /// Defaults to `false`.
+    pub flat_shading: bool,
+
+    /// Whether the main pass reuses the normal written by the normal prepass instead of computing
+    /// it again, e.g. sampling the normal map a second time.
+    ///
+    /// The prepass normal is only reused when it is known to belong to the shaded fragment: for
+    /// opaque and alpha masked materials without specular transmission, drawn in the prepass of a
+    /// camera with a [`NormalPrepass`](bevy_core_pipeline::prepass::NormalPrepass) and without
+    /// MSAA. Set this to `false` to always compute the normal in the main pass, which avoids the
+    /// quantization of the normal prepass texture.
+    ///
+    /// Defaults to `true`.
+    pub reuse_prepass_normal: bool,
+
+    /// Whether this material records its depth in the transparent depth prepass of cameras with a
+    /// [`TransparentDepthPrepass`](bevy_core_pipeline::prepass::TransparentDepthPrepass).
+    ///
+    /// Only applies if the [`AlphaMode`] is transparent, e.g. to let a post-processing effect
+    /// distort what is seen through glass.
+    ///
+    /// Defaults to `false`.
+    pub transparent_depth_prepass: bool,
+
+    /// Renders the fragments of an [`AlphaMode::Blend`] or [`AlphaMode::Premultiplied`] material
+    /// with an alpha of at least this cutoff through the deferred renderer.
+    ///
+    /// Requires [`opaque_render_method`](Self::opaque_render_method) to resolve to
+    /// [`OpaqueRendererMethod::Deferred`] and a camera with a
+    /// [`DeferredPrepass`](bevy_core_pipeline::prepass::DeferredPrepass). The opaque fragments then
+    /// receive the deferred lighting, including screen-space effects like SSAO, while the
+    /// remaining fragments are still blended in the forward transparent pass. This suits materials
+    /// that are mostly opaque with some blended parts, like foliage or hair.
+    ///
+    /// Defaults to `None`, which renders the whole material in the forward transparent pass.
+    pub deferred_opaque_cutoff: Option<f32>,
+
     /// The depth map used for [parallax mapping].
     ///
     /// It is a greyscale image where white represents bottom and black the top.
@@ -462,6 +719,42 @@ pub struct StandardMaterial {
     /// Default is `16.0`.
     pub max_parallax_layer_count: f32,
 
+    /// Whether the prepass and the shadow passes apply the parallax mapping of the
+    /// [`depth_map`](StandardMaterial::depth_map) too.
+    ///
+    /// Without it, these passes sample the material at the undisplaced UVs, so the cutouts of
+    /// [`AlphaMode::Mask`] materials in shadows and the normals written by the normal prepass
+    /// don't line up with the displaced surface drawn by the main pass. When enabled, each pass
+    /// applies the same parallax offset, as seen from its own view: cutouts in the shadows are
+    /// cast by the surface as the light sees it.
+    ///
+    /// Only what these passes sample from the material's textures is displaced. The depth they
+    /// write is still the one of the undisplaced geometry, so the shadows of opaque parts keep
+    /// the outline of the mesh, and effects reading the depth prepass, like SSAO, don't see the
+    /// displacement either.
+    ///
+    /// This repeats the parallax mapping cost in those passes. When disabled, the main pass
+    /// still reuses the normals of the normal prepass if
+    /// [`reuse_prepass_normal`](StandardMaterial::reuse_prepass_normal) is set, even though they
+    /// were sampled at the undisplaced UVs.
+    ///
+    /// Has no effect without a [`depth_map`](StandardMaterial::depth_map). Defaults to `false`.
+    pub parallax_in_prepass: bool,
+
+    /// Whether the [`depth_map`](StandardMaterial::depth_map) casts soft shadows onto itself.
+    ///
+    /// Parallax mapping only offsets the texture lookups, so grooves of the depth map receive as
+    /// much direct light as its peaks, which looks flat under grazing light. With self-shadowing,
+    /// the depth map is marched from each fragment toward every light, and the light is dimmed by
+    /// the parts of the depth map in the way. This uses up to
+    /// [`max_parallax_layer_count`](StandardMaterial::max_parallax_layer_count) extra texture
+    /// samples per light.
+    ///
+    /// **Note:** Only supported by the forward renderer, and only on meshes with UVs and tangents.
+    ///
+    /// Has no effect without a [`depth_map`](StandardMaterial::depth_map). Defaults to `false`.
+    pub parallax_self_shadow: bool,
+
     /// The exposure (brightness) level of the lightmap, if present.
     pub lightmap_exposure: f32,
 
@@ -475,6 +768,24 @@ pub struct StandardMaterial {
 
     /// The transform applied to the UVs corresponding to ATTRIBUTE_UV_0 on the mesh before sampling. Default is identity.
     pub uv_transform: Affine2,
+
+    /// Whether to derive the UVs used to sample [`StandardMaterial::base_color_texture`] from the
+    /// world space XZ position of the surface, instead of the mesh's UVs.
+    ///
+    /// This projects the base color straight down onto the mesh, which is useful for ground decals
+    /// and splat painting on flat surfaces, including meshes without UVs. The projected UVs are
+    /// scaled by [`StandardMaterial::world_uv_scale`] and then go through
+    /// [`StandardMaterial::uv_transform`]. All other textures, like the normal map, still use the
+    /// mesh's UVs.
+    ///
+    /// Defaults to `false`.
+    pub world_uv: bool,
+
+    /// How many times the base color texture repeats per world unit when
+    /// [`StandardMaterial::world_uv`] is enabled.
+    ///
+    /// Defaults to `1.0`.
+    pub world_uv_scale: f32,
 }
 
 impl Default for StandardMaterial {
@@ -485,16 +796,27 @@ impl Default for StandardMaterial {
             base_color: LegacyColor::rgb(1.0, 1.0, 1.0),
             base_color_texture: None,
             emissive: LegacyColor::BLACK,
+            emissive_intensity: 1.0,
             emissive_texture: None,
+            emissive_uv_speed: Vec2::ZERO,
             // Matches Blender's default roughness.
             perceptual_roughness: 0.5,
+            clamp_roughness: true,
+            specular_antialiasing: false,
             // Metallic should generally be set to 0.0 or 1.0.
             metallic: 0.0,
             metallic_roughness_texture: None,
+            workflow: PbrWorkflow::MetallicRoughness,
+            specular_color: LegacyColor::rgb_linear(0.04, 0.04, 0.04),
+            glossiness: 0.5,
+            #[cfg(feature = "pbr_specular_glossiness_textures")]
+            specular_glossiness_texture: None,
             // Minimum real-world reflectance is 2%, most materials between 2-5%
             // Expressed in a linear scale and equivalent to 4% reflectance see
             // <https://google.github.io/filament/Material%20Properties.pdf>
             reflectance: 0.5,
+            #[cfg(feature = "pbr_reflectance_textures")]
+            reflectance_texture: None,
             diffuse_transmission: 0.0,
             #[cfg(feature = "pbr_transmission_textures")]
             diffuse_transmission_texture: None,
@@ -508,26 +830,75 @@ impl Default for StandardMaterial {
             attenuation_color: LegacyColor::WHITE,
             attenuation_distance: f32::INFINITY,
             occlusion_texture: None,
+            occlude_emissive: false,
             normal_map_texture: None,
             flip_normal_map_y: false,
             double_sided: false,
+            two_sided: false,
             cull_mode: Some(Face::Back),
             unlit: false,
             fog_enabled: true,
+            fog_intensity: 1.0,
+            reflectance_env_scale: 1.0,
+            use_environment_map: true,
+            receive_env_diffuse: true,
+            receive_env_specular: true,
+            specular_enabled: true,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
+            depth_write_enabled: None,
+            flat_shading: false,
+            reuse_prepass_normal: true,
+            transparent_depth_prepass: false,
+            deferred_opaque_cutoff: None,
             depth_map: None,
             parallax_depth_scale: 0.1,
             max_parallax_layer_count: 16.0,
+            parallax_in_prepass: false,
+            parallax_self_shadow: false,
             lightmap_exposure: 1.0,
             parallax_mapping_method: ParallaxMappingMethod::Occlusion,
             opaque_render_method: OpaqueRendererMethod::Auto,
             deferred_lighting_pass_id: DEFAULT_PBR_DEFERRED_LIGHTING_PASS_ID,
             uv_transform: Affine2::IDENTITY,
+            world_uv: false,
+            world_uv_scale: 1.0,
         }
     }
 }
 
+/// The set of parameters a [`StandardMaterial`] uses to describe its surface.
+///
+/// See [`StandardMaterial::workflow`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug)]
+pub enum PbrWorkflow {
+    /// A base color, with metallic and roughness values. This is the glTF 2.0 core workflow.
+    #[default]
+    MetallicRoughness,
+    /// A diffuse color, with specular color and glossiness values, as used by older assets and the
+    /// `KHR_materials_pbrSpecularGlossiness` glTF extension.
+    ///
+    /// The parameters are converted to metallic and roughness in the shader.
+    SpecularGlossiness,
+}
+
+/// Overrides the threshold of [`AlphaMode::Mask`] of the material of the entity it is added to.
+///
+/// Fragments with an alpha below the threshold are discarded. Animating the threshold of a single
+/// entity, together with a noise texture in the alpha channel of the base color, gives dissolve
+/// or burn effects without creating a material per entity. Entities without this component use
+/// the threshold of their material. Has no effect on materials with other alpha modes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct AlphaMaskThreshold(pub f32);
+
+impl Default for AlphaMaskThreshold {
+    fn default() -> Self {
+        Self(0.5)
+    }
+}
+
 impl From<LegacyColor> for StandardMaterial {
     fn from(color: LegacyColor) -> Self {
         StandardMaterial {
@@ -551,6 +922,144 @@ impl From<Handle<Image>> for StandardMaterial {
     }
 }
 
+/// The parameters of a glTF metallic-roughness material, including the glTF extensions that have
+/// a [`StandardMaterial`] equivalent.
+///
+/// Converting this into a [`StandardMaterial`] centralizes the mapping from glTF to Bevy, so
+/// loaders and custom importers don't have to repeat it. Colors are linear, as in glTF, and the
+/// defaults are the ones defined by the glTF specification.
+///
+/// ```
+/// # use bevy_pbr::{GltfPbrParameters, StandardMaterial};
+/// # use bevy_utils::default;
+/// let material: StandardMaterial = GltfPbrParameters {
+///     base_color_factor: [0.8, 0.1, 0.1, 1.0],
+///     metallic_factor: 0.0,
+///     transmission_factor: 0.5,
+///     ..default()
+/// }
+/// .into();
+/// ```
+#[derive(Clone, Debug)]
+pub struct GltfPbrParameters {
+    /// The linear base color, `baseColorFactor`.
+    pub base_color_factor: [f32; 4],
+    /// The base color texture, `baseColorTexture`.
+    pub base_color_texture: Option<Handle<Image>>,
+    /// The metalness, `metallicFactor`.
+    pub metallic_factor: f32,
+    /// The perceptual roughness, `roughnessFactor`.
+    pub roughness_factor: f32,
+    /// The combined metallic (blue) and roughness (green) texture, `metallicRoughnessTexture`.
+    pub metallic_roughness_texture: Option<Handle<Image>>,
+    /// The tangent space normal map, `normalTexture`.
+    pub normal_texture: Option<Handle<Image>>,
+    /// The ambient occlusion texture, `occlusionTexture`.
+    pub occlusion_texture: Option<Handle<Image>>,
+    /// The linear emissive color, `emissiveFactor`.
+    pub emissive_factor: [f32; 3],
+    /// The emissive texture, `emissiveTexture`.
+    pub emissive_texture: Option<Handle<Image>>,
+    /// The emissive multiplier, `emissiveStrength` from `KHR_materials_emissive_strength`.
+    pub emissive_strength: f32,
+    /// How the alpha channel is used, from `alphaMode` and `alphaCutoff`.
+    pub alpha_mode: AlphaMode,
+    /// Whether back faces are visible, `doubleSided`.
+    pub double_sided: bool,
+    /// Whether the material is unlit, from `KHR_materials_unlit`.
+    pub unlit: bool,
+    /// The UV transform of the base color texture, from `KHR_texture_transform`.
+    pub uv_transform: Affine2,
+    /// The specular transmission, `transmissionFactor` from `KHR_materials_transmission`.
+    pub transmission_factor: f32,
+    /// The specular transmission texture, `transmissionTexture` from `KHR_materials_transmission`.
+    #[cfg(feature = "pbr_transmission_textures")]
+    pub transmission_texture: Option<Handle<Image>>,
+    /// The thickness of the volume, `thicknessFactor` from `KHR_materials_volume`.
+    pub thickness_factor: f32,
+    /// The thickness texture, `thicknessTexture` from `KHR_materials_volume`.
+    #[cfg(feature = "pbr_transmission_textures")]
+    pub thickness_texture: Option<Handle<Image>>,
+    /// The distance light travels in the volume before reaching `attenuation_color`,
+    /// `attenuationDistance` from `KHR_materials_volume`.
+    pub attenuation_distance: f32,
+    /// The linear color white light turns into inside the volume, `attenuationColor` from
+    /// `KHR_materials_volume`.
+    pub attenuation_color: [f32; 3],
+    /// The index of refraction, `ior` from `KHR_materials_ior`.
+    pub ior: f32,
+}
+
+impl Default for GltfPbrParameters {
+    fn default() -> Self {
+        Self {
+            base_color_factor: [1.0; 4],
+            base_color_texture: None,
+            metallic_factor: 1.0,
+            roughness_factor: 1.0,
+            metallic_roughness_texture: None,
+            normal_texture: None,
+            occlusion_texture: None,
+            emissive_factor: [0.0; 3],
+            emissive_texture: None,
+            emissive_strength: 1.0,
+            alpha_mode: AlphaMode::Opaque,
+            double_sided: false,
+            unlit: false,
+            uv_transform: Affine2::IDENTITY,
+            transmission_factor: 0.0,
+            #[cfg(feature = "pbr_transmission_textures")]
+            transmission_texture: None,
+            thickness_factor: 0.0,
+            #[cfg(feature = "pbr_transmission_textures")]
+            thickness_texture: None,
+            attenuation_distance: f32::INFINITY,
+            attenuation_color: [1.0; 3],
+            ior: 1.5,
+        }
+    }
+}
+
+impl From<GltfPbrParameters> for StandardMaterial {
+    fn from(parameters: GltfPbrParameters) -> Self {
+        let [r, g, b, a] = parameters.base_color_factor;
+        let [emissive_r, emissive_g, emissive_b] = parameters.emissive_factor;
+        let [attenuation_r, attenuation_g, attenuation_b] = parameters.attenuation_color;
+
+        StandardMaterial {
+            base_color: LegacyColor::rgba_linear(r, g, b, a),
+            base_color_texture: parameters.base_color_texture,
+            perceptual_roughness: parameters.roughness_factor,
+            metallic: parameters.metallic_factor,
+            metallic_roughness_texture: parameters.metallic_roughness_texture,
+            normal_map_texture: parameters.normal_texture,
+            occlusion_texture: parameters.occlusion_texture,
+            emissive: LegacyColor::rgb_linear(emissive_r, emissive_g, emissive_b),
+            emissive_texture: parameters.emissive_texture,
+            emissive_intensity: parameters.emissive_strength,
+            alpha_mode: parameters.alpha_mode,
+            double_sided: parameters.double_sided,
+            cull_mode: if parameters.double_sided {
+                None
+            } else {
+                Some(Face::Back)
+            },
+            unlit: parameters.unlit,
+            uv_transform: parameters.uv_transform,
+            specular_transmission: parameters.transmission_factor,
+            #[cfg(feature = "pbr_transmission_textures")]
+            specular_transmission_texture: parameters.transmission_texture,
+            thickness: parameters.thickness_factor,
+            #[cfg(feature = "pbr_transmission_textures")]
+            thickness_texture: parameters.thickness_texture,
+            attenuation_distance: parameters.attenuation_distance,
+            attenuation_color: LegacyColor::rgb_linear(attenuation_r, attenuation_g, attenuation_b),
+            ior: parameters.ior,
+            ..Default::default()
+        }
+    }
+}
+
 // NOTE: These must match the bit flags in bevy_pbr/src/render/pbr_types.wgsl!
 bitflags::bitflags! {
     /// Bitflags info about the material a shader is currently rendering.
@@ -571,6 +1080,14 @@ bitflags::bitflags! {
         const THICKNESS_TEXTURE          = 1 << 11;
         const DIFFUSE_TRANSMISSION_TEXTURE = 1 << 12;
         const ATTENUATION_ENABLED        = 1 << 13;
+        const OCCLUDE_EMISSIVE           = 1 << 14;
+        const SPECULAR_GLOSSINESS        = 1 << 15;
+        const SPECULAR_GLOSSINESS_TEXTURE = 1 << 16;
+        const UNCLAMPED_ROUGHNESS        = 1 << 17;
+        const SPECULAR_ANTIALIASING      = 1 << 18;
+        const NO_ENV_DIFFUSE             = 1 << 19;
+        const NO_ENV_SPECULAR            = 1 << 20;
+        const NO_SPECULAR                = 1 << 21;
         const ALPHA_MODE_RESERVED_BITS   = Self::ALPHA_MODE_MASK_BITS << Self::ALPHA_MODE_SHIFT_BITS; // ← Bitmask reserving bits for the `AlphaMode`
         const ALPHA_MODE_OPAQUE          = 0 << Self::ALPHA_MODE_SHIFT_BITS;                          // ← Values are just sequential values bitshifted into
         const ALPHA_MODE_MASK            = 1 << Self::ALPHA_MODE_SHIFT_BITS;                          //   the bitmask, and can range from 0 to 7.
@@ -642,6 +1159,20 @@ pub struct StandardMaterialUniform {
     pub max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     pub deferred_lighting_pass_id: u32,
+    /// The speed at which the UVs of the emissive texture scroll, in UV units per second.
+    pub emissive_uv_speed: Vec2,
+    /// How strongly fog affects the material, from 0 to 1.
+    pub fog_intensity: f32,
+    /// Scale applied to the specular light from environment maps.
+    pub reflectance_env_scale: f32,
+    /// Multiplier applied to the emitted light.
+    pub emissive_intensity: f32,
+    /// Scale applied to the world space XZ position to derive the base color UVs, when enabled.
+    pub world_uv_scale: f32,
+    /// Specular color for the specular-glossiness workflow.
+    pub specular_color: Vec4,
+    /// Glossiness for the specular-glossiness workflow.
+    pub glossiness: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -659,7 +1190,7 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         if self.occlusion_texture.is_some() {
             flags |= StandardMaterialFlags::OCCLUSION_TEXTURE;
         }
-        if self.double_sided {
+        if self.double_sided || self.two_sided {
             flags |= StandardMaterialFlags::DOUBLE_SIDED;
         }
         if self.unlit {
@@ -671,6 +1202,31 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         if self.depth_map.is_some() {
             flags |= StandardMaterialFlags::DEPTH_MAP;
         }
+        if self.occlude_emissive {
+            flags |= StandardMaterialFlags::OCCLUDE_EMISSIVE;
+        }
+        if !self.clamp_roughness {
+            flags |= StandardMaterialFlags::UNCLAMPED_ROUGHNESS;
+        }
+        if self.specular_antialiasing {
+            flags |= StandardMaterialFlags::SPECULAR_ANTIALIASING;
+        }
+        if !self.receive_env_diffuse {
+            flags |= StandardMaterialFlags::NO_ENV_DIFFUSE;
+        }
+        if !self.receive_env_specular {
+            flags |= StandardMaterialFlags::NO_ENV_SPECULAR;
+        }
+        if !self.specular_enabled {
+            flags |= StandardMaterialFlags::NO_SPECULAR;
+        }
+        if self.workflow == PbrWorkflow::SpecularGlossiness {
+            flags |= StandardMaterialFlags::SPECULAR_GLOSSINESS;
+            #[cfg(feature = "pbr_specular_glossiness_textures")]
+            if self.specular_glossiness_texture.is_some() {
+                flags |= StandardMaterialFlags::SPECULAR_GLOSSINESS_TEXTURE;
+            }
+        }
         #[cfg(feature = "pbr_transmission_textures")]
         {
             if self.specular_transmission_texture.is_some() {
@@ -710,8 +1266,14 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
                 alpha_cutoff = c;
                 flags |= StandardMaterialFlags::ALPHA_MODE_MASK;
             }
-            AlphaMode::Blend => flags |= StandardMaterialFlags::ALPHA_MODE_BLEND,
-            AlphaMode::Premultiplied => flags |= StandardMaterialFlags::ALPHA_MODE_PREMULTIPLIED,
+            AlphaMode::Blend => {
+                alpha_cutoff = self.deferred_opaque_cutoff.unwrap_or(alpha_cutoff);
+                flags |= StandardMaterialFlags::ALPHA_MODE_BLEND;
+            }
+            AlphaMode::Premultiplied => {
+                alpha_cutoff = self.deferred_opaque_cutoff.unwrap_or(alpha_cutoff);
+                flags |= StandardMaterialFlags::ALPHA_MODE_PREMULTIPLIED;
+            }
             AlphaMode::Add => flags |= StandardMaterialFlags::ALPHA_MODE_ADD,
             AlphaMode::Multiply => flags |= StandardMaterialFlags::ALPHA_MODE_MULTIPLY,
         };
@@ -742,6 +1304,13 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             uv_transform_x_axis: self.uv_transform.matrix2.x_axis,
             uv_transform_y_axis: self.uv_transform.matrix2.y_axis,
             uv_transform_translation: self.uv_transform.translation,
+            emissive_uv_speed: self.emissive_uv_speed,
+            fog_intensity: self.fog_intensity,
+            reflectance_env_scale: self.reflectance_env_scale,
+            emissive_intensity: self.emissive_intensity,
+            world_uv_scale: self.world_uv_scale,
+            specular_color: self.specular_color.as_linear_rgba_f32().into(),
+            glossiness: self.glossiness,
         }
     }
 }
@@ -755,13 +1324,25 @@ pub struct StandardMaterialKey {
     relief_mapping: bool,
     diffuse_transmission: bool,
     specular_transmission: bool,
+    fog_intensity: bool,
+    flat_shading: bool,
+    reuse_prepass_normal: bool,
+    use_environment_map: bool,
+    world_uv: bool,
+    parallax_in_prepass: bool,
+    parallax_self_shadow: bool,
+    reflectance_texture: bool,
 }
 
 impl From<&StandardMaterial> for StandardMaterialKey {
     fn from(material: &StandardMaterial) -> Self {
         StandardMaterialKey {
             normal_map: material.normal_map_texture.is_some(),
-            cull_mode: material.cull_mode,
+            cull_mode: if material.two_sided {
+                None
+            } else {
+                material.cull_mode
+            },
             depth_bias: material.depth_bias as i32,
             relief_mapping: matches!(
                 material.parallax_mapping_method,
@@ -769,10 +1350,62 @@ impl From<&StandardMaterial> for StandardMaterialKey {
             ),
             diffuse_transmission: material.diffuse_transmission > 0.0,
             specular_transmission: material.specular_transmission > 0.0,
+            fog_intensity: material.fog_intensity != 1.0,
+            flat_shading: material.flat_shading,
+            reuse_prepass_normal: material.reuse_prepass_normal,
+            use_environment_map: material.use_environment_map,
+            world_uv: material.world_uv,
+            parallax_in_prepass: material.depth_map.is_some() && material.parallax_in_prepass,
+            parallax_self_shadow: material.depth_map.is_some() && material.parallax_self_shadow,
+            #[cfg(feature = "pbr_reflectance_textures")]
+            reflectance_texture: material.reflectance_texture.is_some(),
+            #[cfg(not(feature = "pbr_reflectance_textures"))]
+            reflectance_texture: false,
         }
     }
 }
 
+impl StandardMaterial {
+    /// Whether any of the textures sampled with the mesh's UVs is set.
+    fn has_uv_textures(&self) -> bool {
+        let has_reflectance_texture = {
+            #[cfg(feature = "pbr_reflectance_textures")]
+            {
+                self.reflectance_texture.is_some()
+            }
+            #[cfg(not(feature = "pbr_reflectance_textures"))]
+            false
+        };
+        let has_specular_glossiness_texture = {
+            #[cfg(feature = "pbr_specular_glossiness_textures")]
+            {
+                self.specular_glossiness_texture.is_some()
+            }
+            #[cfg(not(feature = "pbr_specular_glossiness_textures"))]
+            false
+        };
+        let has_transmission_textures = {
+            #[cfg(feature = "pbr_transmission_textures")]
+            {
+                self.diffuse_transmission_texture.is_some()
+                    || self.specular_transmission_texture.is_some()
+                    || self.thickness_texture.is_some()
+            }
+            #[cfg(not(feature = "pbr_transmission_textures"))]
+            false
+        };
+        self.base_color_texture.is_some()
+            || self.emissive_texture.is_some()
+            || self.metallic_roughness_texture.is_some()
+            || self.normal_map_texture.is_some()
+            || self.occlusion_texture.is_some()
+            || has_reflectance_texture
+            || has_specular_glossiness_texture
+            || self.depth_map.is_some()
+            || has_transmission_textures
+    }
+}
+
 impl Material for StandardMaterial {
     fn fragment_shader() -> ShaderRef {
         PBR_SHADER_HANDLE.into()
@@ -785,19 +1418,22 @@ impl Material for StandardMaterial {
 
     #[inline]
     fn opaque_render_method(&self) -> OpaqueRendererMethod {
-        match self.opaque_render_method {
-            // For now, diffuse transmission doesn't work under deferred rendering as we don't pack
-            // the required data into the GBuffer. If this material is set to `Auto`, we report it as
-            // `Forward` so that it's rendered correctly, even when the `DefaultOpaqueRendererMethod`
-            // is set to `Deferred`.
-            //
-            // If the developer explicitly sets the `OpaqueRendererMethod` to `Deferred`, we assume
-            // they know what they're doing and don't override it.
-            OpaqueRendererMethod::Auto if self.diffuse_transmission > 0.0 => {
-                OpaqueRendererMethod::Forward
-            }
-            other => other,
-        }
+        self.opaque_render_method
+    }
+
+    fn requires_forward_rendering(&self) -> bool {
+        // For now, these features don't work under deferred rendering as we don't pack the
+        // required data into the GBuffer, so `Auto` materials using them are rendered with the
+        // forward renderer. Transmissive materials are drawn in their own forward pass anyway.
+        //
+        // If the developer explicitly sets the `OpaqueRendererMethod` to `Deferred`, we assume
+        // they know what they're doing and don't override it.
+        self.diffuse_transmission > 0.0
+            || self.specular_transmission > 0.0
+            || (self.fog_enabled && self.fog_intensity != 1.0)
+            || self.reflectance_env_scale != 1.0
+            || !self.use_environment_map
+            || (self.depth_map.is_some() && self.parallax_self_shadow)
     }
 
     #[inline]
@@ -805,11 +1441,26 @@ impl Material for StandardMaterial {
         self.depth_bias
     }
 
+    #[inline]
+    fn depth_write_enabled(&self) -> Option<bool> {
+        self.depth_write_enabled
+    }
+
     #[inline]
     fn reads_view_transmission_texture(&self) -> bool {
         self.specular_transmission > 0.0
     }
 
+    #[inline]
+    fn transparent_depth_prepass(&self) -> bool {
+        self.transparent_depth_prepass
+    }
+
+    #[inline]
+    fn deferred_opaque_cutoff(&self) -> Option<f32> {
+        self.deferred_opaque_cutoff
+    }
+
     fn prepass_fragment_shader() -> ShaderRef {
         PBR_PREPASS_SHADER_HANDLE.into()
     }
@@ -821,7 +1472,7 @@ impl Material for StandardMaterial {
     fn specialize(
         _pipeline: &MaterialPipeline<Self>,
         descriptor: &mut RenderPipelineDescriptor,
-        _layout: &MeshVertexBufferLayout,
+        layout: &MeshVertexBufferLayout,
         key: MaterialPipelineKey<Self>,
     ) -> Result<(), SpecializedMeshPipelineError> {
         if let Some(fragment) = descriptor.fragment.as_mut() {
@@ -833,6 +1484,16 @@ impl Material for StandardMaterial {
             if key.bind_group_data.relief_mapping {
                 shader_defs.push("RELIEF_MAPPING".into());
             }
+            // Only where the main pass applies the parallax mapping too.
+            if key.bind_group_data.parallax_in_prepass && layout.contains(Mesh::ATTRIBUTE_TANGENT) {
+                shader_defs.push("STANDARD_MATERIAL_PARALLAX_PREPASS".into());
+            }
+            if key.bind_group_data.parallax_self_shadow
+                && layout.contains(Mesh::ATTRIBUTE_UV_0)
+                && layout.contains(Mesh::ATTRIBUTE_TANGENT)
+            {
+                shader_defs.push("STANDARD_MATERIAL_PARALLAX_SELF_SHADOW".into());
+            }
 
             if key.bind_group_data.diffuse_transmission {
                 shader_defs.push("STANDARD_MATERIAL_DIFFUSE_TRANSMISSION".into());
@@ -846,6 +1507,30 @@ impl Material for StandardMaterial {
             {
                 shader_defs.push("STANDARD_MATERIAL_SPECULAR_OR_DIFFUSE_TRANSMISSION".into());
             }
+
+            if key.bind_group_data.fog_intensity {
+                shader_defs.push("STANDARD_MATERIAL_FOG_INTENSITY".into());
+            }
+
+            if key.bind_group_data.flat_shading {
+                shader_defs.push("STANDARD_MATERIAL_FLAT_SHADING".into());
+            }
+
+            if key.bind_group_data.world_uv {
+                shader_defs.push("STANDARD_MATERIAL_WORLD_UV".into());
+            }
+
+            if key.bind_group_data.reflectance_texture {
+                shader_defs.push("STANDARD_MATERIAL_REFLECTANCE_TEXTURE".into());
+            }
+
+            if !key.bind_group_data.use_environment_map {
+                shader_defs.retain(|def| *def != "ENVIRONMENT_MAP".into());
+            }
+
+            if !key.bind_group_data.reuse_prepass_normal {
+                shader_defs.retain(|def| *def != "LOAD_PREPASS_NORMALS".into());
+            }
         }
         descriptor.primitive.cull_mode = key.bind_group_data.cull_mode;
         if let Some(label) = &mut descriptor.label {
@@ -857,3 +1542,64 @@ impl Material for StandardMaterial {
         Ok(())
     }
 }
+
+/// Warns once per mesh asset when a [`StandardMaterial`] is applied to a mesh
+/// without the vertex attributes it needs:
+/// - with a [`normal_map_texture`](StandardMaterial::normal_map_texture) or a
+///   [`depth_map`](StandardMaterial::depth_map), but no [`Mesh::ATTRIBUTE_TANGENT`].
+///   The mesh pipeline then shades it with the geometric normal.
+/// - with any texture, but no [`Mesh::ATTRIBUTE_UV_0`]. The mesh pipeline then
+///   skips its textures.
+///
+/// Meshes are only rechecked when their entity's handles change, or when a
+/// mesh or material asset is added or modified.
+pub fn warn_on_missing_vertex_attributes(
+    mut warned_tangents: Local<HashSet<AssetId<Mesh>>>,
+    mut warned_uvs: Local<HashSet<AssetId<Mesh>>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut material_events: EventReader<AssetEvent<StandardMaterial>>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+    query: Query<(Ref<Handle<Mesh>>, Ref<Handle<StandardMaterial>>)>,
+) {
+    let assets_changed = mesh_events.read().count() + material_events.read().count() > 0;
+
+    for (mesh_handle, material_handle) in &query {
+        if !assets_changed && !mesh_handle.is_changed() && !material_handle.is_changed() {
+            continue;
+        }
+        let mesh_id = mesh_handle.id();
+        if warned_tangents.contains(&mesh_id) && warned_uvs.contains(&mesh_id) {
+            continue;
+        }
+        let (Some(mesh), Some(material)) =
+            (meshes.get(&*mesh_handle), materials.get(&*material_handle))
+        else {
+            continue;
+        };
+
+        if (material.normal_map_texture.is_some() || material.depth_map.is_some())
+            && !mesh.contains_attribute(Mesh::ATTRIBUTE_TANGENT)
+            && warned_tangents.insert(mesh_id)
+        {
+            warn!(
+                "Mesh {:?} uses a StandardMaterial with a normal map or a depth map, but has no \
+                vertex tangents, so it will be shaded incorrectly. Generate them with \
+                `Mesh::generate_tangents` or `Mesh::with_generated_tangents`.",
+                mesh_id
+            );
+        }
+
+        if material.has_uv_textures()
+            && !mesh.contains_attribute(Mesh::ATTRIBUTE_UV_0)
+            && warned_uvs.insert(mesh_id)
+        {
+            warn!(
+                "Mesh {:?} uses a StandardMaterial with textures, but has no {}, so its textures \
+                won't be sampled.",
+                mesh_id,
+                Mesh::ATTRIBUTE_UV_0.name
+            );
+        }
+    }
+}
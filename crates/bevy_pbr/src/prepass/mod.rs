@@ -155,6 +155,7 @@ where
                         prepare_previous_view_projection_uniforms,
                         batch_and_prepare_render_phase::<Opaque3dPrepass, MeshPipeline>,
                         batch_and_prepare_render_phase::<AlphaMask3dPrepass, MeshPipeline>,
+                        batch_and_prepare_render_phase::<Transparent3dPrepass, MeshPipeline>,
                     )
                         .in_set(RenderSet::PrepareResources),
                 );
@@ -163,6 +164,7 @@ where
         render_app
             .add_render_command::<Opaque3dPrepass, DrawPrepass<M>>()
             .add_render_command::<AlphaMask3dPrepass, DrawPrepass<M>>()
+            .add_render_command::<Transparent3dPrepass, DrawTransparentDepthPrepass<M>>()
             .add_render_command::<Opaque3dDeferred, DrawPrepass<M>>()
             .add_render_command::<AlphaMask3dDeferred, DrawPrepass<M>>()
             .add_systems(
@@ -364,6 +366,13 @@ where
             shader_defs.push("PREPASS_FRAGMENT".into());
         }
 
+        if key
+            .mesh_key
+            .contains(MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS)
+        {
+            shader_defs.push("DUAL_PARABOLOID_POINT_SHADOWS".into());
+        }
+
         if layout.contains(Mesh::ATTRIBUTE_UV_0) {
             shader_defs.push("VERTEX_UVS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_UV_0.at_shader_location(1));
@@ -401,6 +410,13 @@ where
             shader_defs.push("DEFERRED_PREPASS".into());
         }
 
+        if key
+            .mesh_key
+            .contains(MeshPipelineKey::DEFERRED_OPAQUE_SPLIT)
+        {
+            shader_defs.push("DEFERRED_OPAQUE_SPLIT".into());
+        }
+
         if layout.contains(Mesh::ATTRIBUTE_COLOR) {
             shader_defs.push("VERTEX_COLORS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(7));
@@ -683,6 +699,7 @@ pub fn prepare_prepass_view_bind_group<M: Material>(
 pub fn queue_prepass_material_meshes<M: Material>(
     opaque_draw_functions: Res<DrawFunctions<Opaque3dPrepass>>,
     alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3dPrepass>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3dPrepass>>,
     opaque_deferred_draw_functions: Res<DrawFunctions<Opaque3dDeferred>>,
     alpha_mask_deferred_draw_functions: Res<DrawFunctions<AlphaMask3dDeferred>>,
     prepass_pipeline: Res<PrepassPipeline<M>>,
@@ -700,12 +717,14 @@ pub fn queue_prepass_material_meshes<M: Material>(
             &VisibleEntities,
             Option<&mut RenderPhase<Opaque3dPrepass>>,
             Option<&mut RenderPhase<AlphaMask3dPrepass>>,
+            Option<&mut RenderPhase<Transparent3dPrepass>>,
             Option<&mut RenderPhase<Opaque3dDeferred>>,
             Option<&mut RenderPhase<AlphaMask3dDeferred>>,
             Option<&DepthPrepass>,
             Option<&NormalPrepass>,
             Option<&MotionVectorPrepass>,
             Option<&DeferredPrepass>,
+            Option<&FogSettings>,
         ),
         Or<(
             With<RenderPhase<Opaque3dPrepass>>,
@@ -725,6 +744,10 @@ pub fn queue_prepass_material_meshes<M: Material>(
         .read()
         .get_id::<DrawPrepass<M>>()
         .unwrap();
+    let transparent_draw_prepass = transparent_draw_functions
+        .read()
+        .get_id::<DrawTransparentDepthPrepass<M>>()
+        .unwrap();
     let opaque_draw_deferred = opaque_deferred_draw_functions
         .read()
         .get_id::<DrawPrepass<M>>()
@@ -738,12 +761,14 @@ pub fn queue_prepass_material_meshes<M: Material>(
         visible_entities,
         mut opaque_phase,
         mut alpha_mask_phase,
+        mut transparent_phase,
         mut opaque_deferred_phase,
         mut alpha_mask_deferred_phase,
         depth_prepass,
         normal_prepass,
         motion_vector_prepass,
         deferred_prepass,
+        fog,
     ) in &mut views
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
@@ -778,14 +803,41 @@ pub fn queue_prepass_material_meshes<M: Material>(
             if mesh.morph_targets.is_some() {
                 mesh_key |= MeshPipelineKey::MORPH_TARGETS;
             }
+            let forward = match material.properties.render_method {
+                OpaqueRendererMethod::Forward => true,
+                // Meshes left out of the fog are drawn forward, see `queue_material_meshes`.
+                OpaqueRendererMethod::Deferred => {
+                    fog.is_some_and(|fog| fog.excludes(&mesh_instance.render_layers))
+                }
+                OpaqueRendererMethod::Auto => unreachable!(),
+            };
+
             let alpha_mode = material.properties.alpha_mode;
+            // The opaque fragments of a split material are drawn like an alpha masked material.
+            let deferred_opaque_split = deferred_prepass.is_some()
+                && !forward
+                && material.properties.splits_deferred_opaque();
             match alpha_mode {
                 AlphaMode::Opaque => {}
                 AlphaMode::Mask(_) => mesh_key |= MeshPipelineKey::MAY_DISCARD,
+                _ if deferred_opaque_split => {
+                    mesh_key |=
+                        MeshPipelineKey::MAY_DISCARD | MeshPipelineKey::DEFERRED_OPAQUE_SPLIT;
+                }
                 AlphaMode::Blend
                 | AlphaMode::Premultiplied
                 | AlphaMode::Add
-                | AlphaMode::Multiply => continue,
+                | AlphaMode::Multiply => {
+                    if !material.properties.transparent_depth_prepass || transparent_phase.is_none()
+                    {
+                        continue;
+                    }
+                    // The transparent depth prepass has no color targets.
+                    mesh_key.remove(
+                        MeshPipelineKey::NORMAL_PREPASS | MeshPipelineKey::MOTION_VECTOR_PREPASS,
+                    );
+                    mesh_key |= MeshPipelineKey::MAY_DISCARD;
+                }
             }
 
             if material.properties.reads_view_transmission_texture {
@@ -794,13 +846,10 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 continue;
             }
 
-            let forward = match material.properties.render_method {
-                OpaqueRendererMethod::Forward => true,
-                OpaqueRendererMethod::Deferred => false,
-                OpaqueRendererMethod::Auto => unreachable!(),
-            };
-
-            let deferred = deferred_prepass.is_some() && !forward;
+            let deferred = deferred_opaque_split
+                || (deferred_prepass.is_some()
+                    && !forward
+                    && matches!(alpha_mode, AlphaMode::Opaque | AlphaMode::Mask(_)));
 
             if deferred {
                 mesh_key |= MeshPipelineKey::DEFERRED_PREPASS;
@@ -887,10 +936,41 @@ pub fn queue_prepass_material_meshes<M: Material>(
                         });
                     }
                 }
+                _ if deferred_opaque_split => {
+                    let distance = rangefinder
+                        .distance_translation(&mesh_instance.transforms.transform.translation)
+                        + material.properties.depth_bias;
+                    alpha_mask_deferred_phase
+                        .as_mut()
+                        .unwrap()
+                        .add(AlphaMask3dDeferred {
+                            entity: *visible_entity,
+                            draw_function: alpha_mask_draw_deferred,
+                            pipeline_id,
+                            distance,
+                            batch_range: 0..1,
+                            dynamic_offset: None,
+                        });
+                }
                 AlphaMode::Blend
                 | AlphaMode::Premultiplied
                 | AlphaMode::Add
-                | AlphaMode::Multiply => {}
+                | AlphaMode::Multiply => {
+                    let distance = rangefinder
+                        .distance_translation(&mesh_instance.transforms.transform.translation)
+                        + material.properties.depth_bias;
+                    transparent_phase
+                        .as_mut()
+                        .unwrap()
+                        .add(Transparent3dPrepass {
+                            entity: *visible_entity,
+                            draw_function: transparent_draw_prepass,
+                            pipeline_id,
+                            distance,
+                            batch_range: 0..1,
+                            dynamic_offset: None,
+                        });
+                }
             }
         }
     }
@@ -949,5 +1029,44 @@ pub type DrawPrepass<M> = (
     DrawMesh,
 );
 
+/// Binds the prepass view bind group without motion vectors, regardless of the view.
+///
+/// Used by the transparent depth prepass, whose pipelines never output motion vectors.
+pub struct SetPrepassViewNoMotionVectorsBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetPrepassViewNoMotionVectorsBindGroup<I> {
+    type Param = SRes<PrepassViewBindGroup>;
+    type ViewQuery = Read<ViewUniformOffset>;
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        view_uniform_offset: &'_ ViewUniformOffset,
+        _entity: Option<()>,
+        prepass_view_bind_group: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_bind_group(
+            I,
+            prepass_view_bind_group
+                .into_inner()
+                .no_motion_vectors
+                .as_ref()
+                .unwrap(),
+            &[view_uniform_offset.offset],
+        );
+
+        RenderCommandResult::Success
+    }
+}
+
+pub type DrawTransparentDepthPrepass<M> = (
+    SetItemPipeline,
+    SetPrepassViewNoMotionVectorsBindGroup<0>,
+    SetMeshBindGroup<1>,
+    SetMaterialBindGroup<M, 2>,
+    DrawMesh,
+);
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 struct PrepassLightsViewFlush;
@@ -32,6 +32,23 @@ fn morph_vertex(vertex_in: Vertex) -> Vertex {
 }
 #endif
 
+#ifdef DUAL_PARABOLOID_POINT_SHADOWS
+// Projects a world space position onto the paraboloid map of the hemisphere in front of
+// a point light shadow view, which looks along -Z in view space. Positions in the other
+// hemisphere get a negative depth so that they are clipped.
+// NOTE: Must match `fetch_point_shadow_dual_paraboloid` in `shadows.wgsl`.
+fn dual_paraboloid_clip_position(world_position: vec4<f32>) -> vec4<f32> {
+    let light_to_vertex = (view.inverse_view * world_position).xyz;
+    let distance = length(light_to_vertex);
+    let direction = light_to_vertex / distance;
+    let xy = direction.xy / max(1.0 - direction.z, 1e-4);
+    // The point light projection is an infinite reverse-z perspective, so the near plane is
+    // stored in projection[3][2] and depth falls off as near / distance.
+    let depth = select(view.projection[3][2] / distance, -direction.z, direction.z > 0.0);
+    return vec4(xy, depth, 1.0);
+}
+#endif // DUAL_PARABOLOID_POINT_SHADOWS
+
 @vertex
 fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
     var out: VertexOutput;
@@ -51,6 +68,11 @@ fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
 #endif // SKINNED
 
     out.position = mesh_functions::mesh_position_local_to_clip(model, vec4(vertex.position, 1.0));
+#ifdef DUAL_PARABOLOID_POINT_SHADOWS
+    out.position = dual_paraboloid_clip_position(
+        mesh_functions::mesh_position_local_to_world(model, vec4(vertex.position, 1.0))
+    );
+#endif // DUAL_PARABOLOID_POINT_SHADOWS
 #ifdef DEPTH_CLAMP_ORTHO
     out.clip_position_unclamped = out.position;
     out.position.z = min(out.position.z, 1.0);
@@ -117,7 +139,7 @@ fn fragment(in: VertexOutput) -> FragmentOutput {
     var out: FragmentOutput;
 
 #ifdef NORMAL_PREPASS
-    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), 1.0);
+    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), mesh_functions::prepass_normal_alpha(in.instance_index));
 #endif
 
 #ifdef DEPTH_CLAMP_ORTHO
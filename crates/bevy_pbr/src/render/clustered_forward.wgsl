@@ -2,7 +2,7 @@
 
 #import bevy_pbr::{
     mesh_view_bindings as bindings,
-    utils::hsv2rgb,
+    utils::{hsv2rgb, random1D},
 }
 
 // NOTE: Keep in sync with bevy_pbr/src/light.rs
@@ -60,13 +60,59 @@ fn get_light_id(index: u32) -> u32 {
 #endif
 }
 
+// The ranges of the cluster light index list holding the lights of one cluster. Each index `i`
+// in a range is turned into an index into `mesh_view_bindings::point_lights` with
+// `get_light_id(i)`. Point lights are in `point_light_start..spot_light_start`, and spot lights
+// are in `spot_light_start..spot_light_end`.
+struct ClusterLightRanges {
+    point_light_start: u32,
+    spot_light_start: u32,
+    spot_light_end: u32,
+}
+
+fn cluster_light_ranges(cluster_index: u32) -> ClusterLightRanges {
+    let offset_and_counts = unpack_offset_and_counts(cluster_index);
+    var ranges: ClusterLightRanges;
+    ranges.point_light_start = offset_and_counts[0];
+    ranges.spot_light_start = ranges.point_light_start + offset_and_counts[1];
+    ranges.spot_light_end = ranges.spot_light_start + offset_and_counts[2];
+    return ranges;
+}
+
+// Returns the index of the cluster containing a fragment of the current view, to be used with
+// `cluster_light_ranges`. This lets custom `Material` shaders walk the same clustered lights as
+// the PBR pass:
+//
+//     let cluster_index = view_fragment_cluster_index(in.position.xy, in.world_position, is_orthographic);
+//     let ranges = cluster_light_ranges(cluster_index);
+//     for (var i = ranges.point_light_start; i < ranges.spot_light_start; i += 1u) {
+//         let light = mesh_view_bindings::point_lights.data[get_light_id(i)];
+//         // ...
+//     }
+fn view_fragment_cluster_index(frag_coord: vec2<f32>, world_position: vec4<f32>, is_orthographic: bool) -> u32 {
+#ifdef SIMPLE_FORWARD_LIGHTING
+    // The view has a single cluster holding all the lights in view.
+    return 0u;
+#else
+    let view_z = dot(vec4<f32>(
+        bindings::view.inverse_view[0].z,
+        bindings::view.inverse_view[1].z,
+        bindings::view.inverse_view[2].z,
+        bindings::view.inverse_view[3].z
+    ), world_position);
+    return fragment_cluster_index(frag_coord, view_z, is_orthographic);
+#endif
+}
+
 fn cluster_debug_visualization(
-    output_color: vec4<f32>,
+    input_color: vec4<f32>,
     view_z: f32,
     is_orthographic: bool,
     offset_and_counts: vec3<u32>,
     cluster_index: u32,
 ) -> vec4<f32> {
+    var output_color = input_color;
+
     // Cluster allocation debug (using 'over' alpha blending)
 #ifdef CLUSTERED_FORWARD_DEBUG_Z_SLICES
     // NOTE: This debug mode visualises the z-slices
@@ -88,9 +134,9 @@ fn cluster_debug_visualization(
     let cluster_overlay_alpha = 0.1;
     let max_light_complexity_per_cluster = 64.0;
     output_color.r = (1.0 - cluster_overlay_alpha) * output_color.r
-        + cluster_overlay_alpha * smoothStep(0.0, max_light_complexity_per_cluster, f32(offset_and_counts[1] + offset_and_counts[2]));
+        + cluster_overlay_alpha * smoothstep(0.0, max_light_complexity_per_cluster, f32(offset_and_counts[1] + offset_and_counts[2]));
     output_color.g = (1.0 - cluster_overlay_alpha) * output_color.g
-        + cluster_overlay_alpha * (1.0 - smoothStep(0.0, max_light_complexity_per_cluster, f32(offset_and_counts[1] + offset_and_counts[2])));
+        + cluster_overlay_alpha * (1.0 - smoothstep(0.0, max_light_complexity_per_cluster, f32(offset_and_counts[1] + offset_and_counts[2])));
 #endif // CLUSTERED_FORWARD_DEBUG_CLUSTER_LIGHT_COMPLEXITY
 #ifdef CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY
     // NOTE: Visualizes the cluster to which the fragment belongs
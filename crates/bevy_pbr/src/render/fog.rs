@@ -1,5 +1,6 @@
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::SkyboxFog;
 use bevy_ecs::prelude::*;
 use bevy_math::{Vec3, Vec4};
 use bevy_render::{
@@ -29,6 +30,12 @@ pub struct GpuFog {
     bi: Vec3,
     /// Unsigned int representation of the active fog falloff mode
     mode: u32,
+    /// How much ambient occlusion thickens the fog
+    ambient_occlusion_density: f32,
+    /// Bit mask of the render layers covered by the fog
+    render_layers: u32,
+    /// The distance at which the end of the color gradient is reached
+    color_gradient_distance: f32,
 }
 
 // Important: These must be kept in sync with `mesh_view_types.wgsl`
@@ -62,7 +69,7 @@ pub fn prepare_fog(
     };
     for (entity, fog) in views_iter {
         let gpu_fog = if let Some(fog) = fog {
-            match &fog.falloff {
+            let gpu_fog = match &fog.falloff {
                 FogFalloff::Linear { start, end } => GpuFog {
                     mode: GPU_FOG_MODE_LINEAR,
                     base_color: fog.color.as_linear_rgba_f32().into(),
@@ -71,6 +78,7 @@ pub fn prepare_fog(
                         .as_linear_rgba_f32()
                         .into(),
                     directional_light_exponent: fog.directional_light_exponent,
+                    ambient_occlusion_density: fog.ambient_occlusion_density,
                     be: Vec3::new(*start, *end, 0.0),
                     ..Default::default()
                 },
@@ -82,7 +90,8 @@ pub fn prepare_fog(
                         .as_linear_rgba_f32()
                         .into(),
                     directional_light_exponent: fog.directional_light_exponent,
-                    be: Vec3::new(*density, 0.0, 0.0),
+                    ambient_occlusion_density: fog.ambient_occlusion_density,
+                    be: *density,
                     ..Default::default()
                 },
                 FogFalloff::ExponentialSquared { density } => GpuFog {
@@ -93,7 +102,8 @@ pub fn prepare_fog(
                         .as_linear_rgba_f32()
                         .into(),
                     directional_light_exponent: fog.directional_light_exponent,
-                    be: Vec3::new(*density, 0.0, 0.0),
+                    ambient_occlusion_density: fog.ambient_occlusion_density,
+                    be: *density,
                     ..Default::default()
                 },
                 FogFalloff::Atmospheric {
@@ -107,9 +117,16 @@ pub fn prepare_fog(
                         .as_linear_rgba_f32()
                         .into(),
                     directional_light_exponent: fog.directional_light_exponent,
+                    ambient_occlusion_density: fog.ambient_occlusion_density,
                     be: *extinction,
                     bi: *inscattering,
+                    ..Default::default()
                 },
+            };
+            GpuFog {
+                render_layers: fog.render_layers.bits(),
+                color_gradient_distance: fog.color_gradient_distance.max(f32::EPSILON),
+                ..gpu_fog
             }
         } else {
             // If no fog is added to a camera, by default it's off
@@ -139,12 +156,38 @@ pub const FOG_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(49135691933
 /// A plugin that consolidates fog extraction, preparation and related resources/assets
 pub struct FogPlugin;
 
+/// Keeps the [`SkyboxFog`] of each camera in sync with its [`FogSettings`], so that the
+/// skybox is fogged when [`FogSettings::affects_skybox`] is set.
+pub fn update_skybox_fog(
+    mut commands: Commands,
+    fogs: Query<(Entity, &FogSettings), Changed<FogSettings>>,
+    mut removed_fogs: RemovedComponents<FogSettings>,
+) {
+    for (entity, fog) in &fogs {
+        if fog.affects_skybox {
+            commands.entity(entity).insert(SkyboxFog {
+                color: fog.color,
+                blend: fog.skybox_blend,
+            });
+        } else {
+            commands.entity(entity).remove::<SkyboxFog>();
+        }
+    }
+
+    for entity in removed_fogs.read() {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<SkyboxFog>();
+        }
+    }
+}
+
 impl Plugin for FogPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, FOG_SHADER_HANDLE, "fog.wgsl", Shader::from_wgsl);
 
         app.register_type::<FogSettings>();
-        app.add_plugins(ExtractComponentPlugin::<FogSettings>::default());
+        app.add_plugins(ExtractComponentPlugin::<FogSettings>::default())
+            .add_systems(PostUpdate, update_skybox_fog);
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
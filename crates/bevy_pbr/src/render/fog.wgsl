@@ -44,10 +44,9 @@ fn exponential_fog(
     distance: f32,
     scattering: vec3<f32>,
 ) -> vec4<f32> {
-    var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let density = fog_params.be.x;
-    fog_color.a *= 1.0 - 1.0 / exp(distance * density);
-    return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_color.a), input_color.a);
+    let fog_color = scattering_adjusted_fog_color(fog_params, scattering);
+    let fog_factor = fog_color.a * (1.0 - 1.0 / exp(distance * fog_params.be));
+    return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_factor), input_color.a);
 }
 
 fn exponential_squared_fog(
@@ -56,10 +55,10 @@ fn exponential_squared_fog(
     distance: f32,
     scattering: vec3<f32>,
 ) -> vec4<f32> {
-    var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let distance_times_density = distance * fog_params.be.x;
-    fog_color.a *= 1.0 - 1.0 / exp(distance_times_density * distance_times_density);
-    return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_color.a), input_color.a);
+    let fog_color = scattering_adjusted_fog_color(fog_params, scattering);
+    let distance_times_density = distance * fog_params.be;
+    let fog_factor = fog_color.a * (1.0 - 1.0 / exp(distance_times_density * distance_times_density));
+    return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_factor), input_color.a);
 }
 
 fn atmospheric_fog(
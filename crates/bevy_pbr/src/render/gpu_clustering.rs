@@ -0,0 +1,393 @@
+//! Assignment of lights to clusters on the GPU.
+//!
+//! When [`ClusteringBackend::Gpu`] is selected together with
+//! [`LightingStrategy::Clustered`], [`assign_lights_to_clusters`]
+//! only culls lights against each view's frustum. The resulting candidate lights
+//! are extracted here and a compute shader tests them against the bounds of
+//! every cluster, writing the `cluster_offsets_and_counts` and
+//! `cluster_light_index_lists` storage buffers that `clustered_forward.wgsl`
+//! reads. Each cluster owns a fixed slice of the index list, as long as the
+//! view's candidate count up to [`GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER`], so no
+//! synchronization between clusters is needed.
+//!
+//! [`assign_lights_to_clusters`]: crate::assign_lights_to_clusters
+
+use bevy_asset::Handle;
+use bevy_core_pipeline::core_3d::Transparent3d;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    query::{QueryItem, With},
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, UVec2, UVec3, UVec4, Vec4};
+use bevy_render::{
+    camera::Camera,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_phase::RenderPhase,
+    render_resource::{
+        binding_types::{storage_buffer_read_only_sized, storage_buffer_sized, uniform_buffer},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, Buffer,
+        BufferDescriptor, BufferUsages, CachedComputePipelineId, ComputePassDescriptor,
+        ComputePipelineDescriptor, PipelineCache, Shader, ShaderStages, ShaderType, UniformBuffer,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    view::ExtractedView,
+    Extract,
+};
+use bevy_utils::warn_once;
+
+use crate::{
+    ClusteringBackend, Clusters, ExtractedPointLight, GlobalLightMeta, LightingStrategy,
+    ViewClusterBindings, VisiblePointLights,
+};
+
+pub const GPU_CLUSTERING_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(60981462841367510927348163202946185743);
+
+/// The maximum number of lights the GPU clustering backend assigns to a single
+/// cluster. Lights beyond this limit are dropped from the cluster.
+pub const GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER: u32 = 64;
+
+/// The number of clusters processed by each workgroup. Keep in sync with
+/// `gpu_clustering.wgsl`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Returns true if `render_device` can run the light clustering compute shader.
+pub(crate) fn gpu_clustering_is_supported(render_device: &RenderDevice) -> bool {
+    cfg!(not(target_arch = "wasm32"))
+        && render_device.limits().max_compute_invocations_per_workgroup >= WORKGROUP_SIZE
+}
+
+/// The cluster configuration of a view, as seen by the clustering shader.
+#[derive(Clone, Copy, Default, ShaderType)]
+struct GpuClusteringUniform {
+    inverse_projection: Mat4,
+    view_from_world: Mat4,
+    /// The cluster tile size in `xy` and the screen size in `zw`, in pixels.
+    tile_size_and_screen_size: Vec4,
+    /// The cluster counts in `xyz` and the per-cluster light capacity in `w`.
+    dimensions_and_capacity: UVec4,
+    near: f32,
+    far: f32,
+    /// The largest reciprocal of the view scale axes, used to scale light ranges
+    /// into view space.
+    view_inverse_scale_max: f32,
+    is_orthographic: u32,
+    point_light_count: u32,
+    spot_light_count: u32,
+}
+
+/// The lights and cluster configuration of a view whose lights are assigned to
+/// clusters on the GPU.
+#[derive(Component)]
+pub struct ExtractedGpuClusters {
+    lights: Vec<Entity>,
+    tile_size: UVec2,
+    dimensions: UVec3,
+    near: f32,
+    far: f32,
+}
+
+pub fn extract_gpu_clusters(
+    mut commands: Commands,
+    clustering_backend: Extract<Res<ClusteringBackend>>,
+    lighting_strategy: Extract<Res<LightingStrategy>>,
+    views: Extract<Query<(Entity, &Camera, &Clusters, &VisiblePointLights)>>,
+) {
+    // Simple forward views keep the single light list built on the CPU
+    if **clustering_backend != ClusteringBackend::Gpu
+        || **lighting_strategy == LightingStrategy::SimpleForward
+    {
+        return;
+    }
+
+    for (entity, camera, clusters, visible_lights) in &views {
+        if !camera.is_active {
+            continue;
+        }
+
+        commands.get_or_spawn(entity).insert(ExtractedGpuClusters {
+            lights: visible_lights.entities.clone(),
+            tile_size: clusters.tile_size,
+            dimensions: clusters.dimensions,
+            near: clusters.near,
+            far: clusters.far,
+        });
+    }
+}
+
+#[derive(Resource)]
+pub struct GpuClusteringPipeline {
+    bind_group_layout: BindGroupLayout,
+    pipeline_id: CachedComputePipelineId,
+}
+
+impl FromWorld for GpuClusteringPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "gpu_clustering_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<GpuClusteringUniform>(false),
+                    // point lights
+                    storage_buffer_read_only_sized(false, None),
+                    // candidate light indices
+                    storage_buffer_read_only_sized(false, None),
+                    // cluster offsets and counts
+                    storage_buffer_sized(false, None),
+                    // cluster light index lists
+                    storage_buffer_sized(false, None),
+                ),
+            ),
+        );
+
+        let pipeline_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("gpu_clustering_pipeline".into()),
+            layout: vec![bind_group_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: GPU_CLUSTERING_SHADER_HANDLE,
+            shader_defs: Vec::new(),
+            entry_point: "assign_lights".into(),
+        });
+
+        Self {
+            bind_group_layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// The storage buffers of the clustering pass, kept per view and only
+/// reallocated when a view needs more space than it had before.
+#[derive(Resource, Default)]
+pub struct GpuClusteringBuffers {
+    views: EntityHashMap<ViewGpuClusteringBuffers>,
+}
+
+struct ViewGpuClusteringBuffers {
+    uniform: UniformBuffer<GpuClusteringUniform>,
+    candidates: Buffer,
+    offsets_and_counts: Buffer,
+    index_lists: Buffer,
+}
+
+/// Returns `buffer` if it holds at least `size` bytes, or a new buffer that does.
+fn reserve_buffer(
+    render_device: &RenderDevice,
+    buffer: Option<Buffer>,
+    label: &'static str,
+    size: u64,
+    usage: BufferUsages,
+) -> Buffer {
+    match buffer {
+        Some(buffer) if buffer.size() >= size => buffer,
+        _ => render_device.create_buffer(&BufferDescriptor {
+            label: Some(label),
+            size: size.next_power_of_two(),
+            usage,
+            mapped_at_creation: false,
+        }),
+    }
+}
+
+/// The clustering pass of a view.
+#[derive(Component)]
+pub struct ViewGpuClusters {
+    bind_group: BindGroup,
+    workgroup_count: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_gpu_clusters(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline: Res<GpuClusteringPipeline>,
+    global_light_meta: Res<GlobalLightMeta>,
+    mut buffers: ResMut<GpuClusteringBuffers>,
+    point_lights: Query<&ExtractedPointLight>,
+    views: Query<(Entity, &ExtractedView, &ExtractedGpuClusters), With<RenderPhase<Transparent3d>>>,
+) {
+    // Drop the buffers of views that went away
+    buffers.views.retain(|entity, _| views.contains(*entity));
+
+    let Some(point_lights_binding) = global_light_meta.gpu_point_lights.binding() else {
+        return;
+    };
+
+    for (entity, view, clusters) in &views {
+        // Candidates are ordered by their index in the point light buffer, which
+        // places point lights before spot lights just like the CPU path does.
+        let mut candidates: Vec<u32> = clusters
+            .lights
+            .iter()
+            .filter_map(|light| global_light_meta.entity_to_index.get(light))
+            .map(|index| *index as u32)
+            .collect();
+        candidates.sort_unstable();
+        let spot_light_count = clusters
+            .lights
+            .iter()
+            .filter(|light| {
+                global_light_meta.entity_to_index.contains_key(*light)
+                    && point_lights
+                        .get(**light)
+                        .is_ok_and(|light| light.spot_light_angles.is_some())
+            })
+            .count() as u32;
+        let point_light_count = candidates.len() as u32 - spot_light_count;
+
+        if candidates.len() as u32 > GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER {
+            warn_once!(
+                "A view has {} lights, more than GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER ({}). \
+                Clusters touched by more lights than that will drop the excess.",
+                candidates.len(),
+                GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER
+            );
+        }
+        // No cluster can hold more lights than the view has, so there is no
+        // point in reserving more space than that.
+        let capacity = (candidates.len() as u32).clamp(1, GPU_CLUSTERING_MAX_LIGHTS_PER_CLUSTER);
+
+        // Storage buffers can't be empty.
+        if candidates.is_empty() {
+            candidates.push(0);
+        }
+
+        let cluster_count = clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z;
+
+        let view_inverse_scale = view.transform.compute_transform().scale.recip();
+        let uniform = GpuClusteringUniform {
+            inverse_projection: view.projection.inverse(),
+            view_from_world: view.transform.compute_matrix().inverse(),
+            tile_size_and_screen_size: clusters
+                .tile_size
+                .as_vec2()
+                .extend(view.viewport.z as f32)
+                .extend(view.viewport.w as f32),
+            dimensions_and_capacity: clusters.dimensions.extend(capacity),
+            near: clusters.near,
+            far: clusters.far,
+            view_inverse_scale_max: view_inverse_scale.abs().max_element(),
+            is_orthographic: (view.projection.w_axis.w == 1.0) as u32,
+            point_light_count,
+            spot_light_count,
+        };
+
+        let view_buffers = buffers.views.remove(&entity);
+        let (mut uniform_buffer, candidates_buffer, offsets_and_counts, index_lists) =
+            match view_buffers {
+                Some(view_buffers) => (
+                    view_buffers.uniform,
+                    Some(view_buffers.candidates),
+                    Some(view_buffers.offsets_and_counts),
+                    Some(view_buffers.index_lists),
+                ),
+                None => {
+                    let mut uniform_buffer = UniformBuffer::default();
+                    uniform_buffer.set_label(Some("gpu_clustering_uniform_buffer"));
+                    (uniform_buffer, None, None, None)
+                }
+            };
+
+        uniform_buffer.set(uniform);
+        uniform_buffer.write_buffer(&render_device, &render_queue);
+        let candidates_bytes: &[u8] = bytemuck::cast_slice(&candidates);
+        let candidates_buffer = reserve_buffer(
+            &render_device,
+            candidates_buffer,
+            "gpu_clustering_candidates_buffer",
+            candidates_bytes.len() as u64,
+            BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        );
+        render_queue.write_buffer(&candidates_buffer, 0, candidates_bytes);
+        let offsets_and_counts = reserve_buffer(
+            &render_device,
+            offsets_and_counts,
+            "gpu_clustering_offsets_and_counts_buffer",
+            cluster_count.max(1) as u64 * std::mem::size_of::<UVec4>() as u64,
+            BufferUsages::STORAGE,
+        );
+        let index_lists = reserve_buffer(
+            &render_device,
+            index_lists,
+            "gpu_clustering_index_lists_buffer",
+            (cluster_count.max(1) * capacity) as u64 * std::mem::size_of::<u32>() as u64,
+            BufferUsages::STORAGE,
+        );
+
+        if let Some(uniform_binding) = uniform_buffer.binding() {
+            let bind_group = render_device.create_bind_group(
+                "gpu_clustering_bind_group",
+                &pipeline.bind_group_layout,
+                &BindGroupEntries::sequential((
+                    uniform_binding,
+                    point_lights_binding.clone(),
+                    candidates_buffer.as_entire_binding(),
+                    offsets_and_counts.as_entire_binding(),
+                    index_lists.as_entire_binding(),
+                )),
+            );
+
+            commands.entity(entity).insert((
+                ViewClusterBindings::gpu(offsets_and_counts.clone(), index_lists.clone()),
+                ViewGpuClusters {
+                    bind_group,
+                    workgroup_count: cluster_count.div_ceil(WORKGROUP_SIZE),
+                },
+            ));
+        }
+
+        buffers.views.insert(
+            entity,
+            ViewGpuClusteringBuffers {
+                uniform: uniform_buffer,
+                candidates: candidates_buffer,
+                offsets_and_counts,
+                index_lists,
+            },
+        );
+    }
+}
+
+/// Assigns the lights of a view to its clusters.
+#[derive(Default)]
+pub struct GpuClusteringNode;
+
+impl ViewNode for GpuClusteringNode {
+    type ViewQuery = &'static ViewGpuClusters;
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        gpu_clusters: QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline = world.resource::<GpuClusteringPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+
+        let mut pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("gpu_clustering_pass"),
+                    timestamp_writes: None,
+                });
+        pass.set_pipeline(compute_pipeline);
+        pass.set_bind_group(0, &gpu_clusters.bind_group, &[]);
+        pass.dispatch_workgroups(gpu_clusters.workgroup_count, 1, 1);
+
+        Ok(())
+    }
+}
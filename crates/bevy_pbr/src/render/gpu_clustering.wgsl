@@ -0,0 +1,147 @@
+// Assigns point and spot lights to the clusters of a view.
+//
+// Produces the same `cluster_offsets_and_counts` and `cluster_light_index_lists`
+// storage buffers that `clustered_forward.wgsl` reads when the lights are
+// clustered on the CPU. Each cluster owns `dimensions_and_capacity.w` entries of
+// the index list, starting at `cluster_index * dimensions_and_capacity.w`.
+
+// NOTE: Keep in sync with `GpuPointLight` in `render/light.rs`. Only the position and range are
+// used here, but the layout must match for `point_lights` to have the same stride.
+struct PointLight {
+    light_custom_data: vec4<f32>,
+    color_inverse_square_range: vec4<f32>,
+    position_radius: vec4<f32>,
+    flags: u32,
+    shadow_depth_bias: f32,
+    shadow_normal_bias: f32,
+    spot_light_barn_doors: u32,
+};
+
+// NOTE: Keep in sync with `GpuClusteringUniform` in `render/gpu_clustering.rs`.
+struct GpuClustering {
+    inverse_projection: mat4x4<f32>,
+    view_from_world: mat4x4<f32>,
+    tile_size_and_screen_size: vec4<f32>,
+    dimensions_and_capacity: vec4<u32>,
+    near: f32,
+    far: f32,
+    view_inverse_scale_max: f32,
+    is_orthographic: u32,
+    point_light_count: u32,
+    spot_light_count: u32,
+};
+
+@group(0) @binding(0) var<uniform> clustering: GpuClustering;
+@group(0) @binding(1) var<storage> point_lights: array<PointLight>;
+// Indices into `point_lights` of the lights visible to the view, point lights first.
+@group(0) @binding(2) var<storage> candidate_lights: array<u32>;
+@group(0) @binding(3) var<storage, read_write> cluster_offsets_and_counts: array<vec4<u32>>;
+@group(0) @binding(4) var<storage, read_write> cluster_light_index_lists: array<u32>;
+
+struct Aabb {
+    min: vec3<f32>,
+    max: vec3<f32>,
+};
+
+fn screen_to_view(screen: vec2<f32>, ndc_z: f32) -> vec3<f32> {
+    let tex_coord = screen / clustering.tile_size_and_screen_size.zw;
+    let clip = vec4(tex_coord.x * 2.0 - 1.0, (1.0 - tex_coord.y) * 2.0 - 1.0, ndc_z, 1.0);
+    let view = clustering.inverse_projection * clip;
+    return view.xyz / view.w;
+}
+
+// Calculate the intersection of a ray from the eye through the view space position to a z plane
+fn line_intersection_to_z_plane(p: vec3<f32>, z: f32) -> vec3<f32> {
+    return p * (z / p.z);
+}
+
+// NOTE: Keep in sync with `compute_aabb_for_cluster` in `light.rs`.
+fn compute_aabb_for_cluster(ijk: vec3<u32>) -> Aabb {
+    let dimensions = clustering.dimensions_and_capacity.xyz;
+    let tile_size = clustering.tile_size_and_screen_size.xy;
+    let z_near = clustering.near;
+    let z_far = clustering.far;
+    let ijk_f = vec3<f32>(ijk);
+
+    // Calculate the minimum and maximum points in screen space
+    let screen_min = ijk_f.xy * tile_size;
+    let screen_max = screen_min + tile_size;
+
+    var aabb: Aabb;
+    if clustering.is_orthographic != 0u {
+        // Use linear depth slicing for orthographic
+        var p_min = screen_to_view(screen_min, 0.0);
+        var p_max = screen_to_view(screen_max, 0.0);
+
+        p_min.z = -z_near + (z_near - z_far) * ijk_f.z / f32(dimensions.z);
+        p_max.z = -z_near + (z_near - z_far) * (ijk_f.z + 1.0) / f32(dimensions.z);
+
+        aabb.min = min(p_min, p_max);
+        aabb.max = max(p_min, p_max);
+    } else {
+        // NOTE: 1.0 is the near plane due to using reverse z projections
+        let p_min = screen_to_view(screen_min, 1.0);
+        let p_max = screen_to_view(screen_max, 1.0);
+
+        let z_far_over_z_near = -z_far / -z_near;
+        var cluster_near = 0.0;
+        if ijk.z != 0u {
+            cluster_near = -z_near * pow(z_far_over_z_near, (ijk_f.z - 1.0) / f32(dimensions.z - 1u));
+        }
+        var cluster_far = -z_far;
+        if dimensions.z != 1u {
+            cluster_far = -z_near * pow(z_far_over_z_near, ijk_f.z / f32(dimensions.z - 1u));
+        }
+
+        let p_min_near = line_intersection_to_z_plane(p_min, cluster_near);
+        let p_min_far = line_intersection_to_z_plane(p_min, cluster_far);
+        let p_max_near = line_intersection_to_z_plane(p_max, cluster_near);
+        let p_max_far = line_intersection_to_z_plane(p_max, cluster_far);
+
+        aabb.min = min(min(p_min_near, p_min_far), min(p_max_near, p_max_far));
+        aabb.max = max(max(p_min_near, p_min_far), max(p_max_near, p_max_far));
+    }
+    return aabb;
+}
+
+fn sphere_intersects_aabb(center: vec3<f32>, radius: f32, aabb: Aabb) -> bool {
+    let offset = clamp(center, aabb.min, aabb.max) - center;
+    return dot(offset, offset) <= radius * radius;
+}
+
+@compute
+@workgroup_size(64, 1, 1)
+fn assign_lights(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let dimensions = clustering.dimensions_and_capacity.xyz;
+    let cluster_index = global_id.x;
+    if cluster_index >= dimensions.x * dimensions.y * dimensions.z {
+        return;
+    }
+
+    // NOTE: Inverse of `fragment_cluster_index` in `clustered_forward.wgsl`.
+    let z = cluster_index % dimensions.z;
+    let xy = cluster_index / dimensions.z;
+    let aabb = compute_aabb_for_cluster(vec3(xy % dimensions.x, xy / dimensions.x, z));
+
+    let capacity = clustering.dimensions_and_capacity.w;
+    let offset = cluster_index * capacity;
+    let candidate_count = clustering.point_light_count + clustering.spot_light_count;
+    var count = 0u;
+    var point_light_count = 0u;
+    for (var i = 0u; i < candidate_count && count < capacity; i += 1u) {
+        let light_index = candidate_lights[i];
+        let light = &point_lights[light_index];
+        let center = (clustering.view_from_world * vec4((*light).position_radius.xyz, 1.0)).xyz;
+        let range = inverseSqrt((*light).color_inverse_square_range.w) * clustering.view_inverse_scale_max;
+        if sphere_intersects_aabb(center, range, aabb) {
+            cluster_light_index_lists[offset + count] = light_index;
+            count += 1u;
+            if i < clustering.point_light_count {
+                point_light_count += 1u;
+            }
+        }
+    }
+
+    cluster_offsets_and_counts[cluster_index] =
+        vec4(offset, point_light_count, count - point_light_count, 0u);
+}
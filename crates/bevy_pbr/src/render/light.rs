@@ -1,12 +1,12 @@
 use bevy_core_pipeline::core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT};
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::prelude::*;
-use bevy_math::{Mat4, UVec3, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
+use bevy_math::{Mat4, UVec2, UVec3, UVec4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_render::{
     camera::Camera,
     color::LegacyColor,
     mesh::Mesh,
-    primitives::{CascadesFrusta, CubemapFrusta, Frustum},
+    primitives::{CascadesFrusta, CubemapFrusta, Frustum, HalfSpace},
     render_asset::RenderAssets,
     render_graph::{Node, NodeRunError, RenderGraphContext},
     render_phase::*,
@@ -16,14 +16,16 @@ use bevy_render::{
     view::{ExtractedView, RenderLayers, ViewVisibility, VisibleEntities},
     Extract,
 };
+use bevy_tasks::AsyncComputeTaskPool;
 use bevy_transform::{components::GlobalTransform, prelude::Transform};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 use bevy_utils::{
     nonmax::NonMaxU32,
     tracing::{error, warn},
+    HashMap,
 };
-use std::{hash::Hash, num::NonZeroU64, ops::Range};
+use std::{f32::consts::TAU, hash::Hash, num::NonZeroU64, ops::Range};
 
 use crate::*;
 
@@ -33,12 +35,18 @@ pub struct ExtractedPointLight {
     /// luminous intensity in lumens per steradian
     pub intensity: f32,
     pub range: f32,
+    pub range_smoothing: f32,
     pub radius: f32,
     pub transform: GlobalTransform,
     pub shadows_enabled: bool,
     pub shadow_depth_bias: f32,
     pub shadow_normal_bias: f32,
     pub spot_light_angles: Option<(f32, f32)>,
+    /// The barn door cut angles of a spot light, see [`SpotLight::barn_doors`].
+    pub spot_light_barn_doors: [f32; 4],
+    pub shadow_update_interval: u32,
+    pub affects_diffuse: bool,
+    pub affects_specular: bool,
 }
 
 #[derive(Component, Debug)]
@@ -53,19 +61,29 @@ pub struct ExtractedDirectionalLight {
     pub cascades: EntityHashMap<Vec<Cascade>>,
     pub frusta: EntityHashMap<Vec<Frustum>>,
     pub render_layers: RenderLayers,
+    pub affects_diffuse: bool,
+    pub affects_specular: bool,
 }
 
+// NOTE: Keep in sync with `PointLight` in `mesh_view_types.wgsl` and `gpu_clustering.wgsl`.
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuPointLight {
     // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
-    // For spot lights: 2 components of the direction (x,z), spot_scale and spot_offset
+    // For spot lights: 2 components of the direction (x,z), spot_scale and the tangent of the
+    // outer angle
     light_custom_data: Vec4,
     color_inverse_square_range: Vec4,
     position_radius: Vec4,
+    // The `PointLightFlags` in bits 0-7, the fraction of the range over which the light fades
+    // out as unorm8 in bits 8-15 and, for spot lights, the rotation of the light's local X axis
+    // around the spot direction relative to the right axis of the shadow map view as a unorm16
+    // fraction of a full turn in bits 16-31
     flags: u32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
-    spot_light_tan_angle: f32,
+    // For spot lights: the barn door cuts as unorm8 fractions of the outer angle, packed as
+    // left | right << 8 | bottom << 16 | top << 24
+    spot_light_barn_doors: u32,
 }
 
 #[derive(ShaderType)]
@@ -151,6 +169,8 @@ bitflags::bitflags! {
     struct PointLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
         const SPOT_LIGHT_Y_NEGATIVE      = 1 << 1;
+        const AFFECTS_DIFFUSE            = 1 << 2;
+        const AFFECTS_SPECULAR           = 1 << 3;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -161,6 +181,10 @@ pub struct GpuDirectionalCascade {
     view_projection: Mat4,
     texel_size: f32,
     far_bound: f32,
+    intensity: f32,
+    uv_scale: f32,
+    uv_offset: Vec2,
+    array_index: u32,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -173,8 +197,8 @@ pub struct GpuDirectionalLight {
     shadow_normal_bias: f32,
     num_cascades: u32,
     cascades_overlap_proportion: f32,
-    depth_texture_base_index: u32,
     render_layers: u32,
+    shadow_fade_distance: f32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -182,6 +206,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     struct DirectionalLightFlags: u32 {
         const SHADOWS_ENABLED            = 1 << 0;
+        const AFFECTS_DIFFUSE            = 1 << 1;
+        const AFFECTS_SPECULAR           = 1 << 2;
         const NONE                       = 0;
         const UNINITIALIZED              = 0xFFFF;
     }
@@ -191,6 +217,9 @@ bitflags::bitflags! {
 pub struct GpuLights {
     directional_lights: [GpuDirectionalLight; MAX_DIRECTIONAL_LIGHTS],
     ambient_color: Vec4,
+    // Equal to `ambient_color` unless the ambient light is a hemisphere light
+    ambient_ground_color: Vec4,
+    ambient_up: Vec3,
     // xyz are x/y/z cluster dimensions and w is the number of clusters
     cluster_dimensions: UVec4,
     // xy are vec2<f32>(cluster_dimensions.xy) / vec2<f32>(view.width, view.height)
@@ -200,6 +229,8 @@ pub struct GpuLights {
     n_directional_lights: u32,
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
+    // frames since the last `ShadowMapRefresh`, to rotate the shadow filter per frame
+    shadow_jitter_frame: u32,
 }
 
 // NOTE: this must be kept in sync with the same constants in pbr.frag
@@ -316,6 +347,8 @@ pub fn extract_lights(
     mut commands: Commands,
     point_light_shadow_map: Extract<Res<PointLightShadowMap>>,
     directional_light_shadow_map: Extract<Res<DirectionalLightShadowMap>>,
+    shadows_enabled: Extract<Res<ShadowsEnabled>>,
+    max_directional_lights: Extract<Res<MaxDirectionalLights>>,
     global_point_lights: Extract<Res<GlobalVisiblePointLights>>,
     point_lights: Extract<
         Query<(
@@ -362,6 +395,9 @@ pub fn extract_lights(
     if directional_light_shadow_map.is_changed() {
         commands.insert_resource(directional_light_shadow_map.clone());
     }
+    if max_directional_lights.is_changed() {
+        commands.insert_resource(**max_directional_lights);
+    }
     // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
     // world unit from the light.
     // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
@@ -369,7 +405,14 @@ pub fn extract_lights(
     // point_light_texel_size = 2.0 / cube face width in texels
     // NOTE: When using various PCF kernel sizes, this will need to be adjusted, according to:
     // https://catlikecoding.com/unity/tutorials/custom-srp/point-and-spot-shadows/
-    let point_light_texel_size = 2.0 / point_light_shadow_map.size as f32;
+    // A paraboloid map squeezes a hemisphere into the same width, so its texels are twice as large
+    // at the center of the map, where the angle covered per texel is largest.
+    let point_light_texel_size =
+        if point_light_shadow_map.mode == PointLightShadowMapMode::DualParaboloid {
+            4.0 / point_light_shadow_map.size as f32
+        } else {
+            2.0 / point_light_shadow_map.size as f32
+        };
 
     let mut point_lights_values = Vec::with_capacity(*previous_point_lights_len);
     for entity in global_point_lights.iter().copied() {
@@ -391,15 +434,20 @@ pub fn extract_lights(
             // for details.
             intensity: point_light.intensity / (4.0 * std::f32::consts::PI),
             range: point_light.range,
+            range_smoothing: point_light.range_smoothing,
             radius: point_light.radius,
             transform: *transform,
-            shadows_enabled: point_light.shadows_enabled,
+            shadows_enabled: point_light.shadows_enabled && shadows_enabled.point_lights,
             shadow_depth_bias: point_light.shadow_depth_bias,
             // The factor of SQRT_2 is for the worst-case diagonal offset
             shadow_normal_bias: point_light.shadow_normal_bias
                 * point_light_texel_size
                 * std::f32::consts::SQRT_2,
             spot_light_angles: None,
+            spot_light_barn_doors: [0.0; 4],
+            shadow_update_interval: point_light.shadow_update_interval,
+            affects_diffuse: point_light.affects_diffuse,
+            affects_specular: point_light.affects_specular,
         };
         point_lights_values.push((
             entity,
@@ -440,15 +488,20 @@ pub fn extract_lights(
                         // which seems least surprising for users
                         intensity: spot_light.intensity / (4.0 * std::f32::consts::PI),
                         range: spot_light.range,
+                        range_smoothing: spot_light.range_smoothing,
                         radius: spot_light.radius,
                         transform: *transform,
-                        shadows_enabled: spot_light.shadows_enabled,
+                        shadows_enabled: spot_light.shadows_enabled && shadows_enabled.spot_lights,
                         shadow_depth_bias: spot_light.shadow_depth_bias,
                         // The factor of SQRT_2 is for the worst-case diagonal offset
                         shadow_normal_bias: spot_light.shadow_normal_bias
                             * texel_size
                             * std::f32::consts::SQRT_2,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        spot_light_barn_doors: spot_light.barn_doors,
+                        shadow_update_interval: spot_light.shadow_update_interval,
+                        affects_diffuse: spot_light.affects_diffuse,
+                        affects_specular: spot_light.affects_specular,
                     },
                     render_visible_entities,
                     *frustum,
@@ -482,7 +535,8 @@ pub fn extract_lights(
                 color: directional_light.color,
                 illuminance: directional_light.illuminance,
                 transform: *transform,
-                shadows_enabled: directional_light.shadows_enabled,
+                shadows_enabled: directional_light.shadows_enabled
+                    && shadows_enabled.directional_lights,
                 shadow_depth_bias: directional_light.shadow_depth_bias,
                 // The factor of SQRT_2 is for the worst-case diagonal offset
                 shadow_normal_bias: directional_light.shadow_normal_bias * std::f32::consts::SQRT_2,
@@ -490,12 +544,29 @@ pub fn extract_lights(
                 cascades: cascades.cascades.clone(),
                 frusta: frusta.frusta.clone(),
                 render_layers: maybe_layers.copied().unwrap_or_default(),
+                affects_diffuse: directional_light.affects_diffuse,
+                affects_specular: directional_light.affects_specular,
             },
             render_visible_entities,
         ));
     }
 }
 
+/// Keeps the `max` directional lights with the highest illuminance, ordered from brightest to
+/// dimmest. Ties are broken by entity, so the selection is stable from frame to frame.
+fn keep_brightest_directional_lights(
+    directional_lights: &mut Vec<(Entity, &ExtractedDirectionalLight)>,
+    max: usize,
+) {
+    directional_lights.sort_by(|(entity_1, light_1), (entity_2, light_2)| {
+        light_2
+            .illuminance
+            .total_cmp(&light_1.illuminance)
+            .then_with(|| entity_1.cmp(entity_2))
+    });
+    directional_lights.truncate(max);
+}
+
 pub(crate) const POINT_LIGHT_NEAR_Z: f32 = 0.1f32;
 
 pub(crate) struct CubeMapFace {
@@ -543,6 +614,43 @@ pub(crate) const CUBE_MAP_FACES: [CubeMapFace; 6] = [
     },
 ];
 
+// Dual-paraboloid shadow maps are [-Z, +Z] hemispheres, each rendered by a view looking along the
+// hemisphere's axis. They are packed three lights to a cubemap, so that they can be bound and
+// sampled through the same cubemap array as regular point light shadows: the hemisphere of light
+// `i` lives in face `(i * 2 + hemisphere) % 6` of cubemap `(i * 2 + hemisphere) / 6`.
+// Keep in sync with `fetch_point_shadow_dual_paraboloid` in `shadows.wgsl`.
+pub(crate) const PARABOLOID_FACES: [CubeMapFace; 2] = [
+    // -Z
+    CubeMapFace {
+        target: Vec3::NEG_Z,
+        up: Vec3::Y,
+    },
+    // +Z
+    CubeMapFace {
+        target: Vec3::Z,
+        up: Vec3::Y,
+    },
+];
+
+/// Returns a frustum containing the hemisphere of radius `range` around `translation` that
+/// faces `forward`.
+fn paraboloid_hemisphere_frustum(translation: Vec3, forward: Vec3, range: f32) -> Frustum {
+    let forward_distance = forward.dot(translation);
+    // The side planes only need to keep everything in range, so they are pushed back behind
+    // the light.
+    let side = HalfSpace::new(forward.extend(range - forward_distance));
+    Frustum {
+        half_spaces: [
+            side,
+            side,
+            side,
+            side,
+            HalfSpace::new(forward.extend(-forward_distance)),
+            HalfSpace::new((-forward).extend(forward_distance + range)),
+        ],
+    }
+}
+
 fn face_index_to_name(face_index: usize) -> &'static str {
     match face_index {
         0 => "+x",
@@ -555,12 +663,239 @@ fn face_index_to_name(face_index: usize) -> &'static str {
     }
 }
 
+fn paraboloid_face_index_to_name(face_index: usize) -> &'static str {
+    match face_index {
+        0 => "-z",
+        1 => "+z",
+        _ => "invalid",
+    }
+}
+
 #[derive(Component)]
 pub struct ShadowView {
     pub depth_attachment: DepthAttachment,
     pub pass_name: String,
 }
 
+/// Everything a point or spot light shadow map depends on, other than its shadow casters.
+#[derive(PartialEq)]
+struct ShadowMapSignature {
+    texture: TextureId,
+    base_array_layer: u32,
+    array_layer_count: u32,
+    transform: GlobalTransform,
+    range: f32,
+    spot_light_angles: Option<(f32, f32)>,
+}
+
+/// Tracks the point and spot light shadow maps rendered for each view, to throttle them to their
+/// [`PointLight::shadow_update_interval`].
+#[derive(Default)]
+pub struct ShadowMapUpdates {
+    frame: u32,
+    refresh: bool,
+    previous: HashMap<(Entity, Entity), ShadowMapSignature>,
+    current: HashMap<(Entity, Entity), ShadowMapSignature>,
+}
+
+impl ShadowMapUpdates {
+    /// Starts tracking the next frame. With `refresh` set, every shadow map is rendered this
+    /// frame and the frame count restarts at zero, see [`ShadowMapRefresh`].
+    fn next_frame(&mut self, refresh: bool) {
+        self.frame = if refresh {
+            0
+        } else {
+            self.frame.wrapping_add(1)
+        };
+        self.refresh = refresh;
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Returns whether the shadow map of `light_entity` in `view_entity` has to be rendered this
+    /// frame, because it is due or it was last rendered with a different signature.
+    fn needs_update(
+        &mut self,
+        view_entity: Entity,
+        light_entity: Entity,
+        light: &ExtractedPointLight,
+        signature: ShadowMapSignature,
+    ) -> bool {
+        let key = (view_entity, light_entity);
+        // Offset by the entity so that lights with the same interval are staggered over frames.
+        let phase =
+            self.frame.wrapping_add(light_entity.index()) % light.shadow_update_interval.max(1);
+        let needs_update =
+            self.refresh || phase == 0 || self.previous.get(&key) != Some(&signature);
+        self.current.insert(key, signature);
+        needs_update
+    }
+}
+
+/// Whether a [`ShadowMapRefresh`] was requested for this frame, consumed by [`prepare_lights`].
+#[derive(Resource, Default)]
+pub struct ExtractedShadowMapRefresh {
+    requested: bool,
+}
+
+pub fn extract_shadow_map_refresh(
+    mut extracted: ResMut<ExtractedShadowMapRefresh>,
+    refresh: Extract<Res<ShadowMapRefresh>>,
+) {
+    extracted.requested |= refresh.take();
+}
+
+/// The [`DirectionalShadowMapReadbacks`] requests moved to the render world, waiting for their
+/// cascade to be prepared.
+#[derive(Resource, Default)]
+pub struct ExtractedDirectionalShadowMapReadbacks {
+    requests: Vec<DirectionalShadowMapReadbackRequest>,
+}
+
+pub fn extract_directional_shadow_map_readbacks(
+    mut extracted: ResMut<ExtractedDirectionalShadowMapReadbacks>,
+    readbacks: Extract<Res<DirectionalShadowMapReadbacks>>,
+) {
+    extracted
+        .requests
+        .append(&mut readbacks.requests.lock().unwrap());
+}
+
+/// Copies the depth of a directional light shadow cascade to a CPU-readable buffer after the
+/// [`ShadowPassNode`] renders it, see [`DirectionalShadowMapReadbacks`].
+///
+/// Depth textures can only be copied as a whole, so the cascade is rendered a second time into a
+/// texture of its own instead of being copied out of its tile of the shadow map array.
+#[derive(Component)]
+pub struct ShadowMapReadback {
+    texture: CachedTexture,
+    size: u32,
+    buffer: Buffer,
+    view_projection: Mat4,
+    callback: Option<CascadeShadowMapReadbackFn>,
+}
+
+impl ShadowMapReadback {
+    fn new(
+        render_device: &RenderDevice,
+        texture_cache: &mut TextureCache,
+        size: u32,
+        view_projection: Mat4,
+        callback: CascadeShadowMapReadbackFn,
+    ) -> Self {
+        let texture = texture_cache.get(render_device, Self::texture_descriptor(size));
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("directional_light_shadow_map_readback_buffer"),
+            size: Self::buffer_size(size),
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            texture,
+            size,
+            buffer,
+            view_projection,
+            callback: Some(callback),
+        }
+    }
+
+    fn texture_descriptor(size: u32) -> TextureDescriptor<'static> {
+        TextureDescriptor {
+            label: Some("directional_light_shadow_map_readback_texture"),
+            size: Self::copy_size(size),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: CORE_3D_DEPTH_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        }
+    }
+
+    /// The extent of the copy, which has to cover the whole readback texture.
+    fn copy_size(size: u32) -> Extent3d {
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        }
+    }
+
+    fn padded_bytes_per_row(size: u32) -> u32 {
+        RenderDevice::align_copy_bytes_per_row(size as usize * std::mem::size_of::<f32>()) as u32
+    }
+
+    fn buffer_size(size: u32) -> u64 {
+        Self::padded_bytes_per_row(size) as u64 * size as u64
+    }
+
+    fn copy_to_buffer(&self, command_encoder: &mut CommandEncoder) {
+        command_encoder.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &self.texture.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::DepthOnly,
+            },
+            ImageCopyBuffer {
+                buffer: &self.buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(Self::padded_bytes_per_row(self.size)),
+                    rows_per_image: None,
+                },
+            },
+            Self::copy_size(self.size),
+        );
+    }
+}
+
+/// Maps the buffers of the shadow map readbacks submitted this frame and hands their depth to the
+/// requesting callbacks once the GPU is done with them.
+pub fn collect_shadow_map_readbacks(mut readbacks: Query<&mut ShadowMapReadback>) {
+    for mut readback in &mut readbacks {
+        let Some(callback) = readback.callback.take() else {
+            continue;
+        };
+        let buffer = readback.buffer.clone();
+        let size = readback.size;
+        let view_projection = readback.view_projection;
+
+        let finish = async move {
+            let (tx, rx) = async_channel::bounded(1);
+            let buffer_slice = buffer.slice(..);
+            // The polling for this map call is done every frame when the command queue is submitted.
+            buffer_slice.map_async(MapMode::Read, move |result| {
+                if let Err(err) = result {
+                    panic!("{}", err.to_string());
+                }
+                tx.try_send(()).unwrap();
+            });
+            rx.recv().await.unwrap();
+
+            let padded_bytes_per_row = ShadowMapReadback::padded_bytes_per_row(size) as usize;
+            let data = buffer_slice.get_mapped_range();
+            let depth = data
+                .chunks_exact(padded_bytes_per_row)
+                .flat_map(|row| {
+                    row[..size as usize * std::mem::size_of::<f32>()]
+                        .chunks_exact(std::mem::size_of::<f32>())
+                        .map(|texel| f32::from_le_bytes(texel.try_into().unwrap()))
+                })
+                .collect();
+            drop(data);
+            buffer.unmap();
+
+            callback(CascadeShadowMapReadback {
+                view_projection,
+                size,
+                depth,
+            });
+        };
+
+        AsyncComputeTaskPool::get().spawn(finish).detach();
+    }
+}
+
 #[derive(Component)]
 pub struct ViewShadowBindings {
     pub point_light_depth_texture: Texture,
@@ -676,6 +1011,79 @@ pub(crate) fn spot_light_projection_matrix(angle: f32) -> Mat4 {
     Mat4::perspective_infinite_reverse_rh(angle * 2.0, 1.0, POINT_LIGHT_NEAR_Z)
 }
 
+/// Where a directional light cascade is rendered in the shadow map texture array.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CascadeTile {
+    layer: u32,
+    offset: UVec2,
+    size: u32,
+}
+
+/// Packs the square shadow maps of directional light cascades into layers of `layer_size` texels.
+///
+/// Each size is rounded up to `layer_size` divided by a power of two. Placing the cascades from
+/// largest to smallest in Z-order then fills each layer without gaps, so cascades smaller than a
+/// layer share it instead of each taking a whole one. Returns the tile of each cascade, in the
+/// order of `sizes`, and the number of layers used.
+fn pack_cascade_tiles(sizes: &[u32], layer_size: u32) -> (Vec<CascadeTile>, u32) {
+    // How many times each cascade's tile halves the layer size
+    let levels: Vec<u32> = sizes
+        .iter()
+        .map(|&size| {
+            let mut level = 0;
+            while (layer_size >> (level + 1)) >= size.max(1) {
+                level += 1;
+            }
+            level
+        })
+        .collect();
+    let Some(&max_level) = levels.iter().max() else {
+        return (Vec::new(), 0);
+    };
+
+    // Positions are counted in cells the size of the smallest tile
+    let cells_per_layer = 1u64 << (2 * max_level);
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&index| levels[index]);
+
+    let mut tiles = vec![
+        CascadeTile {
+            layer: 0,
+            offset: UVec2::ZERO,
+            size: 0,
+        };
+        sizes.len()
+    ];
+    let (mut layer, mut cell) = (0, 0);
+    for index in order {
+        let level = levels[index];
+        let tile_cells = 1u64 << (2 * (max_level - level));
+        if cell + tile_cells > cells_per_layer {
+            layer += 1;
+            cell = 0;
+        }
+        // Tiles are placed from largest to smallest, so each one starts at a multiple of its own
+        // cell count and `z_index` is its position on the Z-order curve of tiles of its size.
+        let z_index = cell / tile_cells;
+        let size = layer_size >> level;
+        tiles[index] = CascadeTile {
+            layer,
+            offset: UVec2::new(compact_even_bits(z_index), compact_even_bits(z_index >> 1)) * size,
+            size,
+        };
+        cell += tile_cells;
+    }
+
+    (tiles, layer + 1)
+}
+
+/// Gathers the even bits of `bits` into the low half of the result.
+fn compact_even_bits(bits: u64) -> u32 {
+    (0..32).fold(0, |compacted, bit| {
+        compacted | (((bits >> (2 * bit)) & 1) as u32) << bit
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn prepare_lights(
     mut commands: Commands,
@@ -685,20 +1093,33 @@ pub fn prepare_lights(
     mut global_light_meta: ResMut<GlobalLightMeta>,
     mut light_meta: ResMut<LightMeta>,
     views: Query<
-        (Entity, &ExtractedView, &ExtractedClusterConfig),
+        (
+            Entity,
+            &ExtractedView,
+            &ExtractedClusterConfig,
+            Option<&AmbientLight>,
+        ),
         With<RenderPhase<Transparent3d>>,
     >,
     ambient_light: Res<AmbientLight>,
     point_light_shadow_map: Res<PointLightShadowMap>,
     directional_light_shadow_map: Res<DirectionalLightShadowMap>,
-    mut max_directional_lights_warning_emitted: Local<bool>,
-    mut max_cascades_per_light_warning_emitted: Local<bool>,
+    max_directional_lights: Res<MaxDirectionalLights>,
+    (mut max_directional_lights_warning_emitted, mut max_cascades_per_light_warning_emitted): (
+        Local<bool>,
+        Local<bool>,
+    ),
     point_lights: Query<(
         Entity,
         &ExtractedPointLight,
         AnyOf<(&CubemapFrusta, &Frustum)>,
     )>,
     directional_lights: Query<(Entity, &ExtractedDirectionalLight)>,
+    mut shadow_map_readbacks: ResMut<ExtractedDirectionalShadowMapReadbacks>,
+    (mut shadow_map_updates, mut shadow_map_refresh): (
+        Local<ShadowMapUpdates>,
+        ResMut<ExtractedShadowMapRefresh>,
+    ),
 ) {
     let views_iter = views.iter();
     let views_count = views_iter.len();
@@ -717,8 +1138,19 @@ pub fn prepare_lights(
         .iter()
         .map(|CubeMapFace { target, up }| Transform::IDENTITY.looking_at(*target, *up))
         .collect::<Vec<_>>();
+    let paraboloid_face_rotations = PARABOLOID_FACES
+        .iter()
+        .map(|CubeMapFace { target, up }| Transform::IDENTITY.looking_at(*target, *up))
+        .collect::<Vec<_>>();
+    let dual_paraboloid = point_light_shadow_map.mode == PointLightShadowMapMode::DualParaboloid;
+    let point_light_faces = if dual_paraboloid {
+        PARABOLOID_FACES.len()
+    } else {
+        CUBE_MAP_FACES.len()
+    };
 
     global_light_meta.entity_to_index.clear();
+    shadow_map_updates.next_frame(std::mem::take(&mut shadow_map_refresh.requested));
 
     let mut point_lights: Vec<_> = point_lights.iter().collect::<Vec<_>>();
     let mut directional_lights: Vec<_> = directional_lights.iter().collect::<Vec<_>>();
@@ -740,14 +1172,19 @@ pub fn prepare_lights(
     #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
     let max_texture_cubes = 1;
 
-    if !*max_directional_lights_warning_emitted && directional_lights.len() > MAX_DIRECTIONAL_LIGHTS
-    {
-        warn!(
-            "The amount of directional lights of {} is exceeding the supported limit of {}.",
-            directional_lights.len(),
-            MAX_DIRECTIONAL_LIGHTS
-        );
-        *max_directional_lights_warning_emitted = true;
+    // Keep the brightest directional lights if there are too many, see `MaxDirectionalLights`.
+    let max_directional_lights = max_directional_lights.0.min(MAX_DIRECTIONAL_LIGHTS);
+    if directional_lights.len() > max_directional_lights {
+        if !*max_directional_lights_warning_emitted {
+            warn!(
+                "The amount of directional lights of {} is exceeding the limit of {}. Only the brightest {} will be used.",
+                directional_lights.len(),
+                max_directional_lights,
+                max_directional_lights,
+            );
+            *max_directional_lights_warning_emitted = true;
+        }
+        keep_brightest_directional_lights(&mut directional_lights, max_directional_lights);
     }
 
     if !*max_cascades_per_light_warning_emitted
@@ -771,7 +1208,7 @@ pub fn prepare_lights(
         .iter()
         .filter(|light| light.1.shadows_enabled && light.1.spot_light_angles.is_none())
         .count()
-        .min(max_texture_cubes);
+        .min(max_texture_cubes * CUBE_MAP_FACES.len() / point_light_faces);
 
     let directional_shadow_enabled_count = directional_lights
         .iter()
@@ -835,22 +1272,40 @@ pub fn prepare_lights(
         {
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
+        if light.affects_diffuse {
+            flags |= PointLightFlags::AFFECTS_DIFFUSE;
+        }
+        if light.affects_specular {
+            flags |= PointLightFlags::AFFECTS_SPECULAR;
+        }
 
-        let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
+        let (light_custom_data, spot_light_barn_doors, spot_light_roll) = match light
+            .spot_light_angles
+        {
             Some((inner, outer)) => {
                 let light_direction = light.transform.forward();
                 if light_direction.y.is_sign_negative() {
                     flags |= PointLightFlags::SPOT_LIGHT_Y_NEGATIVE;
                 }
 
-                let cos_outer = outer.cos();
-                let spot_scale = 1.0 / f32::max(inner.cos() - cos_outer, 1e-4);
-                let spot_offset = -cos_outer * spot_scale;
+                let spot_scale = 1.0 / f32::max(inner.cos() - outer.cos(), 1e-4);
+
+                let barn_doors = light
+                    .spot_light_barn_doors
+                    .map(|cut| ((cut / outer).clamp(0.0, 1.0) * u8::MAX as f32).round() as u32);
+                let view = spot_light_view_matrix(&light.transform);
+                let light_right = light.transform.right();
+                let roll = f32::atan2(
+                    light_right.dot(view.y_axis.xyz()),
+                    light_right.dot(view.x_axis.xyz()),
+                );
 
                 (
-                    // For spot lights: the direction (x,z), spot_scale and spot_offset
-                    light_direction.xz().extend(spot_scale).extend(spot_offset),
-                    outer.tan(),
+                    // For spot lights: the direction (x,z), spot_scale and the tangent of
+                    // the outer angle
+                    light_direction.xz().extend(spot_scale).extend(outer.tan()),
+                    barn_doors[0] | barn_doors[1] << 8 | barn_doors[2] << 16 | barn_doors[3] << 24,
+                    ((roll / TAU).rem_euclid(1.0) * u16::MAX as f32).round() as u32,
                 )
             }
             None => {
@@ -863,7 +1318,8 @@ pub fn prepare_lights(
                         cube_face_projection.w_axis.w,
                     ),
                     // unused
-                    0.0,
+                    0,
+                    0,
                 )
             }
         };
@@ -877,14 +1333,34 @@ pub fn prepare_lights(
                 .xyz()
                 .extend(1.0 / (light.range * light.range)),
             position_radius: light.transform.translation().extend(light.radius),
-            flags: flags.bits(),
+            flags: flags.bits()
+                | ((light.range_smoothing.clamp(0.0, 1.0) * u8::MAX as f32).round() as u32) << 8
+                | spot_light_roll << 16,
             shadow_depth_bias: light.shadow_depth_bias,
             shadow_normal_bias: light.shadow_normal_bias,
-            spot_light_tan_angle,
+            spot_light_barn_doors,
         });
         global_light_meta.entity_to_index.insert(entity, index);
     }
 
+    let directional_light_layer_size = (directional_light_shadow_map.size as u32)
+        .min(render_device.limits().max_texture_dimension_2d);
+    // The cascades of all lights with shadows share the layers of the shadow map texture array
+    let shadow_map = &*directional_light_shadow_map;
+    let (directional_cascade_tiles, num_directional_layers) = pack_cascade_tiles(
+        &directional_lights
+            .iter()
+            .take(directional_shadow_enabled_count)
+            .flat_map(|(_, light)| {
+                let config = &light.cascade_shadow_config;
+                (0..config.bounds.len().min(MAX_CASCADES_PER_LIGHT))
+                    .map(move |index| config.cascade_size(index, shadow_map) as u32)
+            })
+            .collect::<Vec<_>>(),
+        directional_light_layer_size,
+    );
+    let num_directional_layers = num_directional_layers as usize;
+
     let mut gpu_directional_lights = [GpuDirectionalLight::default(); MAX_DIRECTIONAL_LIGHTS];
     let mut num_directional_cascades_enabled = 0usize;
     for (index, (_light_entity, light)) in directional_lights
@@ -898,15 +1374,43 @@ pub fn prepare_lights(
         if light.shadows_enabled && (index < directional_shadow_enabled_count) {
             flags |= DirectionalLightFlags::SHADOWS_ENABLED;
         }
+        if light.affects_diffuse {
+            flags |= DirectionalLightFlags::AFFECTS_DIFFUSE;
+        }
+        if light.affects_specular {
+            flags |= DirectionalLightFlags::AFFECTS_SPECULAR;
+        }
 
         let num_cascades = light
             .cascade_shadow_config
             .bounds
             .len()
             .min(MAX_CASCADES_PER_LIGHT);
+        // The view projection and texel size are filled in later, for lights with shadows.
+        let mut cascades = [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT];
+        for (cascade_index, (cascade, bound)) in cascades
+            .iter_mut()
+            .zip(&light.cascade_shadow_config.bounds)
+            .enumerate()
+        {
+            cascade.far_bound = *bound;
+            cascade.intensity = light
+                .cascade_shadow_config
+                .intensity_multipliers
+                .get(cascade_index)
+                .copied()
+                .unwrap_or(1.0);
+        }
+        if index < directional_shadow_enabled_count {
+            let tiles = &directional_cascade_tiles[num_directional_cascades_enabled..];
+            for (cascade, tile) in cascades.iter_mut().zip(tiles).take(num_cascades) {
+                cascade.array_index = tile.layer;
+                cascade.uv_offset = tile.offset.as_vec2() / directional_light_layer_size as f32;
+                cascade.uv_scale = tile.size as f32 / directional_light_layer_size as f32;
+            }
+        }
         gpu_directional_lights[index] = GpuDirectionalLight {
-            // Filled in later.
-            cascades: [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT],
+            cascades,
             // premultiply color by illuminance
             // we don't use the alpha at all, so no reason to multiply only [0..3]
             color: Vec4::from_slice(&light.color.as_linear_rgba_f32()) * light.illuminance,
@@ -917,8 +1421,8 @@ pub fn prepare_lights(
             shadow_normal_bias: light.shadow_normal_bias,
             num_cascades: num_cascades as u32,
             cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
-            depth_texture_base_index: num_directional_cascades_enabled as u32,
             render_layers: light.render_layers.bits(),
+            shadow_fade_distance: light.cascade_shadow_config.fade_distance,
         };
         if index < directional_shadow_enabled_count {
             num_directional_cascades_enabled += num_cascades;
@@ -931,14 +1435,19 @@ pub fn prepare_lights(
         .write_buffer(&render_device, &render_queue);
 
     // set up light data for each view
-    for (entity, extracted_view, clusters) in &views {
+    for (entity, extracted_view, clusters, view_ambient_light) in &views {
+        // A per-view `AmbientLight` overrides the global resource
+        let ambient_light = view_ambient_light.unwrap_or(&ambient_light);
         let point_light_depth_texture = texture_cache.get(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
                     width: point_light_shadow_map.size as u32,
                     height: point_light_shadow_map.size as u32,
-                    depth_or_array_layers: point_light_shadow_maps_count.max(1) as u32 * 6,
+                    depth_or_array_layers: ((point_light_shadow_maps_count * point_light_faces)
+                        .div_ceil(CUBE_MAP_FACES.len())
+                        .max(1)
+                        * CUBE_MAP_FACES.len()) as u32,
                 },
                 mip_level_count: 1,
                 sample_count: 1,
@@ -953,12 +1462,9 @@ pub fn prepare_lights(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
-                    width: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
-                    height: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
-                    depth_or_array_layers: (num_directional_cascades_enabled
-                        + spot_light_shadow_maps_count)
+                    width: directional_light_layer_size,
+                    height: directional_light_layer_size,
+                    depth_or_array_layers: (num_directional_layers + spot_light_shadow_maps_count)
                         .max(1) as u32,
                 },
                 mip_level_count: 1,
@@ -985,6 +1491,13 @@ pub fn prepare_lights(
             directional_lights: gpu_directional_lights,
             ambient_color: Vec4::from_slice(&ambient_light.color.as_linear_rgba_f32())
                 * ambient_light.brightness,
+            ambient_ground_color: Vec4::from_slice(
+                &ambient_light
+                    .ground_color
+                    .unwrap_or(ambient_light.color)
+                    .as_linear_rgba_f32(),
+            ) * ambient_light.brightness,
+            ambient_up: ambient_light.up.normalize_or_zero(),
             cluster_factors: Vec4::new(
                 clusters.dimensions.x as f32 / extracted_view.viewport.z as f32,
                 clusters.dimensions.y as f32 / extracted_view.viewport.w as f32,
@@ -993,11 +1506,11 @@ pub fn prepare_lights(
             ),
             cluster_dimensions: clusters.dimensions.extend(n_clusters),
             n_directional_lights: directional_lights.iter().len() as u32,
-            // spotlight shadow maps are stored in the directional light array, starting at num_directional_cascades_enabled.
+            // spotlight shadow maps are stored in the directional light array, starting at num_directional_layers.
             // the spot lights themselves start in the light array at point_light_count. so to go from light
-            // index to shadow map index, we need to subtract point light count and add directional shadowmap count.
-            spot_light_shadowmap_offset: num_directional_cascades_enabled as i32
-                - point_light_count as i32,
+            // index to shadow map index, we need to subtract point light count and add directional layer count.
+            spot_light_shadowmap_offset: num_directional_layers as i32 - point_light_count as i32,
+            shadow_jitter_frame: shadow_map_updates.frame,
         };
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
@@ -1011,16 +1524,51 @@ pub fn prepare_lights(
                 .entity_to_index
                 .get(&light_entity)
                 .unwrap();
+            if !shadow_map_updates.needs_update(
+                entity,
+                light_entity,
+                light,
+                ShadowMapSignature {
+                    texture: point_light_depth_texture.texture.id(),
+                    base_array_layer: (light_index * point_light_faces) as u32,
+                    array_layer_count: point_light_faces as u32,
+                    transform: light.transform,
+                    range: light.range,
+                    spot_light_angles: None,
+                },
+            ) {
+                continue;
+            }
+
             // ignore scale because we don't want to effectively scale light radius and range
             // by applying those as a view transform to shadow map rendering of objects
             // and ignore rotation because we want the shadow map projections to align with the axes
             let view_translation = GlobalTransform::from_translation(light.transform.translation());
 
-            for (face_index, (view_rotation, frustum)) in cube_face_rotations
-                .iter()
-                .zip(&point_light_frusta.unwrap().frusta)
-                .enumerate()
-            {
+            let faces: Vec<(Transform, Frustum)> = if dual_paraboloid {
+                paraboloid_face_rotations
+                    .iter()
+                    .zip(&PARABOLOID_FACES)
+                    .map(|(view_rotation, face)| {
+                        (
+                            *view_rotation,
+                            paraboloid_hemisphere_frustum(
+                                light.transform.translation(),
+                                face.target,
+                                light.range,
+                            ),
+                        )
+                    })
+                    .collect()
+            } else {
+                cube_face_rotations
+                    .iter()
+                    .copied()
+                    .zip(point_light_frusta.unwrap().frusta.iter().copied())
+                    .collect()
+            };
+
+            for (face_index, (view_rotation, frustum)) in faces.iter().enumerate() {
                 let depth_texture_view =
                     point_light_depth_texture
                         .texture
@@ -1031,7 +1579,7 @@ pub fn prepare_lights(
                             aspect: TextureAspect::All,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: (light_index * 6 + face_index) as u32,
+                            base_array_layer: (light_index * point_light_faces + face_index) as u32,
                             array_layer_count: Some(1u32),
                         });
 
@@ -1042,7 +1590,11 @@ pub fn prepare_lights(
                             pass_name: format!(
                                 "shadow pass point light {} {}",
                                 light_index,
-                                face_index_to_name(face_index)
+                                if dual_paraboloid {
+                                    paraboloid_face_index_to_name(face_index)
+                                } else {
+                                    face_index_to_name(face_index)
+                                }
                             ),
                         },
                         ExtractedView {
@@ -1077,6 +1629,22 @@ pub fn prepare_lights(
             .take(spot_light_shadow_maps_count)
             .enumerate()
         {
+            if !shadow_map_updates.needs_update(
+                entity,
+                light_entity,
+                light,
+                ShadowMapSignature {
+                    texture: directional_light_depth_texture.texture.id(),
+                    base_array_layer: (num_directional_layers + light_index) as u32,
+                    array_layer_count: 1,
+                    transform: light.transform,
+                    range: light.range,
+                    spot_light_angles: light.spot_light_angles,
+                },
+            ) {
+                continue;
+            }
+
             let spot_view_matrix = spot_light_view_matrix(&light.transform);
             let spot_view_transform = spot_view_matrix.into();
 
@@ -1094,7 +1662,7 @@ pub fn prepare_lights(
                         aspect: TextureAspect::All,
                         base_mip_level: 0,
                         mip_level_count: None,
-                        base_array_layer: (num_directional_cascades_enabled + light_index) as u32,
+                        base_array_layer: (num_directional_layers + light_index) as u32,
                         array_layer_count: Some(1u32),
                     });
 
@@ -1108,8 +1676,8 @@ pub fn prepare_lights(
                         viewport: UVec4::new(
                             0,
                             0,
-                            directional_light_shadow_map.size as u32,
-                            directional_light_shadow_map.size as u32,
+                            directional_light_layer_size,
+                            directional_light_layer_size,
                         ),
                         transform: spot_view_transform,
                         projection: spot_projection,
@@ -1127,7 +1695,26 @@ pub fn prepare_lights(
         }
 
         // directional lights
-        let mut directional_depth_texture_array_index = 0u32;
+        // Cascades sharing a layer share its attachment, so that only the first one clears it
+        let directional_layer_attachments: Vec<_> = (0..num_directional_layers as u32)
+            .map(|layer| {
+                let depth_texture_view =
+                    directional_light_depth_texture
+                        .texture
+                        .create_view(&TextureViewDescriptor {
+                            label: Some("directional_light_shadow_map_array_texture_view"),
+                            format: None,
+                            dimension: Some(TextureViewDimension::D2),
+                            aspect: TextureAspect::All,
+                            base_mip_level: 0,
+                            mip_level_count: None,
+                            base_array_layer: layer,
+                            array_layer_count: Some(1u32),
+                        });
+                DepthAttachment::new(depth_texture_view, Some(0.0))
+            })
+            .collect();
+        let mut directional_cascade_index = 0;
         for (light_index, &(light_entity, light)) in directional_lights
             .iter()
             .enumerate()
@@ -1145,53 +1732,55 @@ pub fn prepare_lights(
                 .unwrap()
                 .iter()
                 .take(MAX_CASCADES_PER_LIGHT);
-            for (cascade_index, ((cascade, frusta), bound)) in cascades
-                .zip(frusta)
-                .zip(&light.cascade_shadow_config.bounds)
-                .enumerate()
-            {
-                gpu_lights.directional_lights[light_index].cascades[cascade_index] =
-                    GpuDirectionalCascade {
-                        view_projection: cascade.view_projection,
-                        texel_size: cascade.texel_size,
-                        far_bound: *bound,
-                    };
-
-                let depth_texture_view =
-                    directional_light_depth_texture
-                        .texture
-                        .create_view(&TextureViewDescriptor {
-                            label: Some("directional_light_shadow_map_array_texture_view"),
-                            format: None,
-                            dimension: Some(TextureViewDimension::D2),
-                            aspect: TextureAspect::All,
-                            base_mip_level: 0,
-                            mip_level_count: None,
-                            base_array_layer: directional_depth_texture_array_index,
-                            array_layer_count: Some(1u32),
-                        });
-                directional_depth_texture_array_index += 1;
+            for (cascade_index, (cascade, frusta)) in cascades.zip(frusta).enumerate() {
+                let gpu_cascade =
+                    &mut gpu_lights.directional_lights[light_index].cascades[cascade_index];
+                gpu_cascade.view_projection = cascade.view_projection;
+                gpu_cascade.texel_size = cascade.texel_size;
+                let tile = directional_cascade_tiles[directional_cascade_index];
+                directional_cascade_index += 1;
+
+                let readback = shadow_map_readbacks
+                    .requests
+                    .iter()
+                    .position(|request| {
+                        request.light == light_entity
+                            && request.view == entity
+                            && request.cascade_index == cascade_index
+                    })
+                    .map(|index| {
+                        let request = shadow_map_readbacks.requests.swap_remove(index);
+                        ShadowMapReadback::new(
+                            &render_device,
+                            &mut texture_cache,
+                            tile.size,
+                            cascade.view_projection,
+                            request.callback,
+                        )
+                    });
+                let cascade_view = |viewport| ExtractedView {
+                    viewport,
+                    transform: GlobalTransform::from(cascade.view_transform),
+                    projection: cascade.projection,
+                    view_projection: Some(cascade.view_projection),
+                    hdr: false,
+                    color_grading: Default::default(),
+                };
 
                 let view_light_entity = commands
                     .spawn((
                         ShadowView {
-                            depth_attachment: DepthAttachment::new(depth_texture_view, Some(0.0)),
+                            depth_attachment: directional_layer_attachments[tile.layer as usize]
+                                .clone(),
                             pass_name: format!(
                                 "shadow pass directional light {light_index} cascade {cascade_index}"),
                         },
-                        ExtractedView {
-                            viewport: UVec4::new(
-                                0,
-                                0,
-                                directional_light_shadow_map.size as u32,
-                                directional_light_shadow_map.size as u32,
-                            ),
-                            transform: GlobalTransform::from(cascade.view_transform),
-                            projection: cascade.projection,
-                            view_projection: Some(cascade.view_projection),
-                            hdr: false,
-                            color_grading: Default::default(),
-                        },
+                        cascade_view(UVec4::new(
+                            tile.offset.x,
+                            tile.offset.y,
+                            tile.size,
+                            tile.size,
+                        )),
                        *frusta,
                         RenderPhase::<Shadow>::default(),
                         LightEntity::Directional {
@@ -1201,6 +1790,31 @@ pub fn prepare_lights(
                     ))
                     .id();
                 view_lights.push(view_light_entity);
+
+                if let Some(readback) = readback {
+                    let readback_view_entity = commands
+                        .spawn((
+                            ShadowView {
+                                depth_attachment: DepthAttachment::new(
+                                    readback.texture.default_view.clone(),
+                                    Some(0.0),
+                                ),
+                                pass_name: format!(
+                                    "shadow readback directional light {light_index} cascade {cascade_index}"
+                                ),
+                            },
+                            cascade_view(UVec4::new(0, 0, tile.size, tile.size)),
+                            *frusta,
+                            RenderPhase::<Shadow>::default(),
+                            LightEntity::Directional {
+                                light_entity,
+                                cascade_index,
+                            },
+                            readback,
+                        ))
+                        .id();
+                    view_lights.push(readback_view_entity);
+                }
             }
         }
 
@@ -1267,6 +1881,15 @@ pub fn prepare_lights(
             },
         ));
     }
+
+    for request in shadow_map_readbacks.requests.drain(..) {
+        warn!(
+            "Dropping shadow map readback of cascade {} of directional light {:?} for view {:?}: \
+            the cascade was not rendered. Make sure the light has shadows enabled and the view \
+            is an active 3d camera.",
+            request.cascade_index, request.light, request.view
+        );
+    }
 }
 
 // this must match CLUSTER_COUNT_SIZE in pbr.wgsl
@@ -1349,6 +1972,11 @@ enum ViewClusterBuffers {
         cluster_light_index_lists: StorageBuffer<GpuClusterLightIndexListsStorage>,
         cluster_offsets_and_counts: StorageBuffer<GpuClusterOffsetsAndCountsStorage>,
     },
+    /// Storage buffers filled by the clustering compute shader, see [`ClusteringBackend::Gpu`].
+    Gpu {
+        cluster_light_index_lists: Buffer,
+        cluster_offsets_and_counts: Buffer,
+    },
 }
 
 impl ViewClusterBuffers {
@@ -1374,6 +2002,12 @@ impl ViewClusterBuffers {
     }
 }
 
+/// The clustered light buffers of a view, bound at bindings 7 and 8 of the mesh view bind group.
+///
+/// Custom render nodes can bind the same buffers via [`Self::light_index_lists_binding`] and
+/// [`Self::offsets_and_counts_binding`], and walk them in WGSL with the helpers in
+/// `bevy_pbr::clustered_forward` (`view_fragment_cluster_index`, `cluster_light_ranges` and
+/// `get_light_id`). `Material` shaders get these bindings through the mesh view bind group.
 #[derive(Component)]
 pub struct ViewClusterBindings {
     n_indices: usize,
@@ -1394,6 +2028,21 @@ impl ViewClusterBindings {
         }
     }
 
+    /// Creates bindings for buffers that are written on the GPU, see [`ClusteringBackend::Gpu`].
+    pub(crate) fn gpu(
+        cluster_offsets_and_counts: Buffer,
+        cluster_light_index_lists: Buffer,
+    ) -> Self {
+        Self {
+            n_indices: 0,
+            n_offsets: 0,
+            buffers: ViewClusterBuffers::Gpu {
+                cluster_light_index_lists,
+                cluster_offsets_and_counts,
+            },
+        }
+    }
+
     pub fn clear(&mut self) {
         match &mut self.buffers {
             ViewClusterBuffers::Uniform {
@@ -1411,6 +2060,7 @@ impl ViewClusterBindings {
                 cluster_light_index_lists.get_mut().data.clear();
                 cluster_offsets_and_counts.get_mut().data.clear();
             }
+            ViewClusterBuffers::Gpu { .. } => {}
         }
     }
 
@@ -1441,6 +2091,7 @@ impl ViewClusterBindings {
                     0,
                 ));
             }
+            ViewClusterBuffers::Gpu { .. } => return,
         }
 
         self.n_offsets += 1;
@@ -1470,6 +2121,7 @@ impl ViewClusterBindings {
             } => {
                 cluster_light_index_lists.get_mut().data.push(index as u32);
             }
+            ViewClusterBuffers::Gpu { .. } => return,
         }
 
         self.n_indices += 1;
@@ -1491,9 +2143,11 @@ impl ViewClusterBindings {
                 cluster_light_index_lists.write_buffer(render_device, render_queue);
                 cluster_offsets_and_counts.write_buffer(render_device, render_queue);
             }
+            ViewClusterBuffers::Gpu { .. } => {}
         }
     }
 
+    /// The list of light indices of all clusters, read with `get_light_id` in WGSL.
     pub fn light_index_lists_binding(&self) -> Option<BindingResource> {
         match &self.buffers {
             ViewClusterBuffers::Uniform {
@@ -1504,9 +2158,15 @@ impl ViewClusterBindings {
                 cluster_light_index_lists,
                 ..
             } => cluster_light_index_lists.binding(),
+            ViewClusterBuffers::Gpu {
+                cluster_light_index_lists,
+                ..
+            } => Some(cluster_light_index_lists.as_entire_binding()),
         }
     }
 
+    /// The offset into the light index list and the point and spot light counts of each
+    /// cluster, read with `cluster_light_ranges` in WGSL.
     pub fn offsets_and_counts_binding(&self) -> Option<BindingResource> {
         match &self.buffers {
             ViewClusterBuffers::Uniform {
@@ -1517,6 +2177,10 @@ impl ViewClusterBindings {
                 cluster_offsets_and_counts,
                 ..
             } => cluster_offsets_and_counts.binding(),
+            ViewClusterBuffers::Gpu {
+                cluster_offsets_and_counts,
+                ..
+            } => Some(cluster_offsets_and_counts.as_entire_binding()),
         }
     }
 
@@ -1545,7 +2209,13 @@ pub fn prepare_clusters(
     render_queue: Res<RenderQueue>,
     mesh_pipeline: Res<MeshPipeline>,
     global_light_meta: Res<GlobalLightMeta>,
-    views: Query<(Entity, &ExtractedClustersPointLights), With<RenderPhase<Transparent3d>>>,
+    views: Query<
+        (Entity, &ExtractedClustersPointLights),
+        (
+            With<RenderPhase<Transparent3d>>,
+            Without<ExtractedGpuClusters>,
+        ),
+    >,
 ) {
     let render_device = render_device.into_inner();
     let supports_storage_buffers = matches!(
@@ -1606,15 +2276,20 @@ pub fn queue_shadows<M: Material>(
     point_light_entities: Query<&CubemapVisibleEntities, With<ExtractedPointLight>>,
     directional_light_entities: Query<&CascadesVisibleEntities, With<ExtractedDirectionalLight>>,
     spot_light_entities: Query<&VisibleEntities, With<ExtractedPointLight>>,
+    point_light_shadow_map: Res<PointLightShadowMap>,
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
+    let dual_paraboloid = point_light_shadow_map.mode == PointLightShadowMapMode::DualParaboloid;
     for (entity, view_lights) in &view_lights {
         let draw_shadow_mesh = shadow_draw_functions.read().id::<DrawPrepass<M>>();
         for view_light_entity in view_lights.lights.iter().copied() {
             let (light_entity, mut shadow_phase) =
                 view_light_shadow_phases.get_mut(view_light_entity).unwrap();
             let is_directional_light = matches!(light_entity, LightEntity::Directional { .. });
+            let is_paraboloid_face =
+                dual_paraboloid && matches!(light_entity, LightEntity::Point { .. });
+            let mut hemisphere_entities = VisibleEntities::default();
             let visible_entities = match light_entity {
                 LightEntity::Directional {
                     light_entity,
@@ -1630,10 +2305,25 @@ pub fn queue_shadows<M: Material>(
                 LightEntity::Point {
                     light_entity,
                     face_index,
-                } => point_light_entities
-                    .get(*light_entity)
-                    .expect("Failed to get point light visible entities")
-                    .get(*face_index),
+                } => {
+                    let cubemap_visible_entities = point_light_entities
+                        .get(*light_entity)
+                        .expect("Failed to get point light visible entities");
+                    if is_paraboloid_face {
+                        // A hemisphere overlaps five of the cubemap faces, so just draw every
+                        // caster in range and let the vertex shader clip away the other half.
+                        hemisphere_entities.entities = cubemap_visible_entities
+                            .iter()
+                            .flat_map(VisibleEntities::iter)
+                            .copied()
+                            .collect();
+                        hemisphere_entities.entities.sort_unstable();
+                        hemisphere_entities.entities.dedup();
+                        &hemisphere_entities
+                    } else {
+                        cubemap_visible_entities.get(*face_index)
+                    }
+                }
                 LightEntity::Spot { light_entity } => spot_light_entities
                     .get(*light_entity)
                     .expect("Failed to get spot light visible entities"),
@@ -1666,6 +2356,9 @@ pub fn queue_shadows<M: Material>(
                 if is_directional_light {
                     mesh_key |= MeshPipelineKey::DEPTH_CLAMP_ORTHO;
                 }
+                if is_paraboloid_face {
+                    mesh_key |= MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS;
+                }
 
                 // Even though we don't use the lightmap in the shadow map, the
                 // `SetMeshBindGroup` render command will bind the data for it. So
@@ -1676,6 +2369,8 @@ pub fn queue_shadows<M: Material>(
                     mesh_key |= MeshPipelineKey::LIGHTMAPPED;
                 }
 
+                // Casters that may discard fragments run the prepass fragment shader so that
+                // their shadows follow the alpha cutout; opaque casters stay depth-only.
                 mesh_key |= match material.properties.alpha_mode {
                     AlphaMode::Mask(_)
                     | AlphaMode::Blend
@@ -1783,7 +2478,12 @@ impl CachedRenderPipelinePhaseItem for Shadow {
 
 pub struct ShadowPassNode {
     main_view_query: QueryState<&'static ViewLightEntities>,
-    view_light_query: QueryState<(&'static ShadowView, &'static RenderPhase<Shadow>)>,
+    view_light_query: QueryState<(
+        &'static ShadowView,
+        &'static ExtractedView,
+        &'static RenderPhase<Shadow>,
+        Option<&'static ShadowMapReadback>,
+    )>,
 }
 
 impl ShadowPassNode {
@@ -1810,7 +2510,7 @@ impl Node for ShadowPassNode {
         let view_entity = graph.view_entity();
         if let Ok(view_lights) = self.main_view_query.get_manual(world, view_entity) {
             for view_light_entity in view_lights.lights.iter().copied() {
-                let (view_light, shadow_phase) = self
+                let (view_light, extracted_view, shadow_phase, readback) = self
                     .view_light_query
                     .get_manual(world, view_light_entity)
                     .unwrap();
@@ -1835,10 +2535,17 @@ impl Node for ShadowPassNode {
                         occlusion_query_set: None,
                     });
                     let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
+                    // Directional light cascades may only cover part of their layer
+                    let viewport = extracted_view.viewport.as_vec4();
+                    render_pass
+                        .set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
 
                     shadow_phase.render(&mut render_pass, world, view_light_entity);
 
                     drop(render_pass);
+                    if let Some(readback) = readback {
+                        readback.copy_to_buffer(&mut command_encoder);
+                    }
                     command_encoder.finish()
                 });
             }
@@ -1847,3 +2554,165 @@ impl Node for ShadowPassNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn directional_light(illuminance: f32) -> ExtractedDirectionalLight {
+        ExtractedDirectionalLight {
+            color: LegacyColor::WHITE,
+            illuminance,
+            transform: GlobalTransform::IDENTITY,
+            shadows_enabled: false,
+            shadow_depth_bias: 0.0,
+            shadow_normal_bias: 0.0,
+            cascade_shadow_config: CascadeShadowConfig::default(),
+            cascades: EntityHashMap::default(),
+            frusta: EntityHashMap::default(),
+            render_layers: RenderLayers::default(),
+            affects_diffuse: true,
+            affects_specular: true,
+        }
+    }
+
+    #[test]
+    fn keeps_brightest_directional_lights() {
+        let lights = [1.0, 5.0, 3.0, 5.0, 4.0, 2.0].map(directional_light);
+        let entities = (0..lights.len() as u32).map(Entity::from_raw);
+        let all_lights: Vec<_> = entities.zip(lights.iter()).collect();
+
+        // The order the lights are queried in doesn't change which ones are kept.
+        for reversed in [false, true] {
+            let mut directional_lights = all_lights.clone();
+            if reversed {
+                directional_lights.reverse();
+            }
+            keep_brightest_directional_lights(&mut directional_lights, 3);
+            let kept: Vec<_> = directional_lights
+                .iter()
+                .map(|(entity, light)| (entity.index(), light.illuminance))
+                .collect();
+            // Equally bright lights are ordered by entity.
+            assert_eq!(kept, [(1, 5.0), (3, 5.0), (4, 4.0)]);
+        }
+    }
+
+    #[test]
+    fn packs_smaller_cascades_into_shared_layers() {
+        let tile = |layer, x, y, size| CascadeTile {
+            layer,
+            offset: UVec2::new(x, y),
+            size,
+        };
+
+        // Sizes are rounded up to the layer size divided by a power of two.
+        let (tiles, layers) = pack_cascade_tiles(&[2048, 1000, 1024, 300], 2048);
+        assert_eq!(layers, 2);
+        assert_eq!(
+            tiles,
+            [
+                tile(0, 0, 0, 2048),
+                tile(1, 0, 0, 1024),
+                tile(1, 1024, 0, 1024),
+                tile(1, 0, 1024, 512),
+            ]
+        );
+
+        // Larger cascades are placed first, whatever their order.
+        let (tiles, layers) = pack_cascade_tiles(&[256, 512, 256, 512, 512, 512], 1024);
+        assert_eq!(layers, 2);
+        assert_eq!(
+            tiles,
+            [
+                tile(1, 0, 0, 256),
+                tile(0, 0, 0, 512),
+                tile(1, 256, 0, 256),
+                tile(0, 512, 0, 512),
+                tile(0, 0, 512, 512),
+                tile(0, 512, 512, 512),
+            ]
+        );
+
+        assert_eq!(pack_cascade_tiles(&[], 2048), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn shadow_map_refresh_updates_throttled_lights() {
+        let light = ExtractedPointLight {
+            color: LegacyColor::WHITE,
+            intensity: 1.0,
+            range: 10.0,
+            range_smoothing: 0.0,
+            radius: 0.0,
+            transform: GlobalTransform::IDENTITY,
+            shadows_enabled: true,
+            shadow_depth_bias: 0.0,
+            shadow_normal_bias: 0.0,
+            spot_light_angles: None,
+            spot_light_barn_doors: [0.0; 4],
+            shadow_update_interval: 4,
+            affects_diffuse: true,
+            affects_specular: true,
+        };
+        let texture = TextureId::new();
+        let (view_entity, light_entity) = (Entity::from_raw(0), Entity::from_raw(1));
+
+        let mut shadow_map_updates = ShadowMapUpdates::default();
+        let mut frame = |refresh| {
+            shadow_map_updates.next_frame(refresh);
+            let needs_update = shadow_map_updates.needs_update(
+                view_entity,
+                light_entity,
+                &light,
+                ShadowMapSignature {
+                    texture,
+                    base_array_layer: 0,
+                    array_layer_count: 6,
+                    transform: light.transform,
+                    range: light.range,
+                    spot_light_angles: None,
+                },
+            );
+            (needs_update, shadow_map_updates.frame)
+        };
+
+        // The first frame renders the shadow map, then it is only updated every 4th frame.
+        let updates: Vec<_> = (0..5).map(|_| frame(false).0).collect();
+        assert_eq!(updates, [true, false, true, false, false]);
+
+        // A refresh updates it right away and restarts the frame count for the shadow jitter.
+        assert_eq!(frame(true), (true, 0));
+        assert_eq!(frame(false), (false, 1));
+    }
+
+    #[test]
+    fn shadow_map_readback_copies_whole_texture() {
+        for size in [1, 100, 2048] {
+            // Depth textures can only be copied as a whole, including all their layers.
+            let descriptor = ShadowMapReadback::texture_descriptor(size);
+            assert_eq!(ShadowMapReadback::copy_size(size), descriptor.size);
+            assert_eq!(descriptor.size.depth_or_array_layers, 1);
+            assert_eq!(descriptor.mip_level_count, 1);
+
+            // Rows of a texture to buffer copy are aligned to 256 bytes.
+            let bytes_per_row = ShadowMapReadback::padded_bytes_per_row(size);
+            assert_eq!(bytes_per_row % 256, 0);
+            assert!(bytes_per_row >= size * 4);
+            assert_eq!(
+                ShadowMapReadback::buffer_size(size),
+                bytes_per_row as u64 * size as u64
+            );
+        }
+    }
+
+    #[test]
+    fn gpu_point_lights_fit_in_uniform_buffer() {
+        assert_eq!(GpuPointLight::min_size().get(), 64);
+        assert_eq!(
+            GpuPointLightsUniform::min_size().get(),
+            MAX_UNIFORM_BUFFER_POINT_LIGHTS as u64 * 64
+        );
+        assert!(GpuPointLightsUniform::min_size().get() <= 16384);
+    }
+}
@@ -0,0 +1,59 @@
+#import bevy_pbr::{
+    mesh_functions::get_model_matrix,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_core_pipeline::tonemapping::{screen_space_dither, powsafe, tone_mapping}
+
+struct LightBillboardMaterial {
+    color: vec4<f32>,
+    size: f32,
+};
+
+@group(2) @binding(0) var<uniform> material: LightBillboardMaterial;
+@group(2) @binding(1) var billboard_texture: texture_2d<f32>;
+@group(2) @binding(2) var billboard_sampler: sampler;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    // Only the translation of the light is used, the quad always faces the camera.
+    let center = get_model_matrix(vertex.instance_index)[3].xyz;
+    let offset = vertex.position.xy * material.size;
+    let world_position = center + view.view[0].xyz * offset.x + view.view[1].xyz * offset.y;
+
+    var out: VertexOutput;
+    out.position = position_world_to_clip(world_position);
+    out.uv = vertex.uv;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture_color = textureSample(billboard_texture, billboard_sampler, in.uv);
+    var output_color = vec4(material.color.rgb * texture_color.rgb * view.exposure, texture_color.a);
+
+#ifdef TONEMAP_IN_SHADER
+    output_color = tone_mapping(output_color, view.color_grading);
+#ifdef DEBAND_DITHER
+    var output_rgb = output_color.rgb;
+    output_rgb = powsafe(output_rgb, 1.0 / 2.2);
+    output_rgb += screen_space_dither(in.position.xy);
+    output_rgb = powsafe(output_rgb, 2.2);
+    output_color = vec4(output_rgb, output_color.a);
+#endif
+#endif
+
+    // Blended additively: premultiply and leave the destination alpha untouched.
+    return vec4(output_color.rgb * output_color.a, 0.0);
+}
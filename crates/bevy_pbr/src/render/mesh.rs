@@ -1,8 +1,8 @@
 use crate::{
-    AtomicMaterialBindGroupId, MaterialBindGroupId, NotShadowCaster, NotShadowReceiver,
-    PreviousGlobalTransform, Shadow, ViewFogUniformOffset, ViewLightProbesUniformOffset,
-    ViewLightsUniformOffset, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_CASCADES_PER_LIGHT,
-    MAX_DIRECTIONAL_LIGHTS,
+    AlphaMaskThreshold, AtomicMaterialBindGroupId, MaterialBindGroupId, NotShadowCaster,
+    NotShadowReceiver, ParallaxDepthScale, PreviousGlobalTransform, Shadow, ViewFogUniformOffset,
+    ViewLightProbesUniformOffset, ViewLightsUniformOffset, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    MAX_CASCADES_PER_LIGHT, MAX_DIRECTIONAL_LIGHTS,
 };
 use bevy_app::{Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, AssetId, Handle};
@@ -31,7 +31,7 @@ use bevy_render::{
     texture::{
         BevyDefault, DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo,
     },
-    view::{ViewTarget, ViewUniformOffset, ViewVisibility},
+    view::{RenderLayers, ViewTarget, ViewUniformOffset, ViewVisibility},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
 use bevy_transform::components::GlobalTransform;
@@ -51,7 +51,9 @@ use crate::render::{
 };
 use crate::*;
 
-use self::irradiance_volume::IRRADIANCE_VOLUMES_ARE_USABLE;
+use self::{
+    irradiance_volume::IRRADIANCE_VOLUMES_ARE_USABLE, skybox_ambient::SKYBOX_AMBIENT_IS_USABLE,
+};
 
 use super::skin::SkinIndices;
 
@@ -218,19 +220,41 @@ pub struct MeshUniform {
     //
     // (MSB: most significant bit; LSB: least significant bit.)
     pub lightmap_uv_rect: UVec2,
+    // Only used if `MeshFlags::PARALLAX_DEPTH_SCALE` is set.
+    pub parallax_depth_scale: f32,
+    // Only used if `MeshFlags::ALPHA_MASK_THRESHOLD` is set.
+    pub alpha_mask_threshold: f32,
+    // The `RenderLayers` of the mesh, as a bit mask.
+    pub render_layers: u32,
 }
 
 impl MeshUniform {
-    pub fn new(mesh_transforms: &MeshTransforms, maybe_lightmap_uv_rect: Option<Rect>) -> Self {
+    pub fn new(
+        mesh_transforms: &MeshTransforms,
+        maybe_lightmap_uv_rect: Option<Rect>,
+        maybe_parallax_depth_scale: Option<f32>,
+        maybe_alpha_mask_threshold: Option<f32>,
+        render_layers: u32,
+    ) -> Self {
         let (inverse_transpose_model_a, inverse_transpose_model_b) =
             mesh_transforms.transform.inverse_transpose_3x3();
+        let mut flags = mesh_transforms.flags;
+        if maybe_parallax_depth_scale.is_some() {
+            flags |= MeshFlags::PARALLAX_DEPTH_SCALE.bits();
+        }
+        if maybe_alpha_mask_threshold.is_some() {
+            flags |= MeshFlags::ALPHA_MASK_THRESHOLD.bits();
+        }
         Self {
             transform: mesh_transforms.transform.to_transpose(),
             previous_transform: mesh_transforms.previous_transform.to_transpose(),
             lightmap_uv_rect: lightmap::pack_lightmap_uv_rect(maybe_lightmap_uv_rect),
             inverse_transpose_model_a,
             inverse_transpose_model_b,
-            flags: mesh_transforms.flags,
+            flags,
+            parallax_depth_scale: maybe_parallax_depth_scale.unwrap_or_default(),
+            alpha_mask_threshold: maybe_alpha_mask_threshold.unwrap_or_default(),
+            render_layers,
         }
     }
 }
@@ -241,6 +265,12 @@ bitflags::bitflags! {
     pub struct MeshFlags: u32 {
         const SHADOW_RECEIVER             = 1 << 0;
         const TRANSMITTED_SHADOW_RECEIVER = 1 << 1;
+        // The mesh overrides the parallax depth scale of its material.
+        const PARALLAX_DEPTH_SCALE        = 1 << 2;
+        // The mesh overrides the alpha mask threshold of its material.
+        const ALPHA_MASK_THRESHOLD        = 1 << 3;
+        // The mesh is part of the baked SSAO, see `ScreenSpaceAmbientOcclusionStatic`.
+        const SSAO_STATIC                 = 1 << 4;
         // Indicates the sign of the determinant of the 3x3 model matrix. If the sign is positive,
         // then the flag should be set, else it should not be set.
         const SIGN_DETERMINANT_MODEL_3X3  = 1 << 31;
@@ -255,6 +285,9 @@ pub struct RenderMeshInstance {
     pub material_bind_group_id: AtomicMaterialBindGroupId,
     pub shadow_caster: bool,
     pub automatic_batching: bool,
+    pub parallax_depth_scale: Option<f32>,
+    pub alpha_mask_threshold: Option<f32>,
+    pub render_layers: RenderLayers,
 }
 
 impl RenderMeshInstance {
@@ -280,6 +313,10 @@ pub fn extract_meshes(
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
+            Has<ScreenSpaceAmbientOcclusionStatic>,
+            Option<&ParallaxDepthScale>,
+            Option<&AlphaMaskThreshold>,
+            Option<&RenderLayers>,
         )>,
     >,
 ) {
@@ -294,6 +331,10 @@ pub fn extract_meshes(
             transmitted_receiver,
             not_shadow_caster,
             no_automatic_batching,
+            ssao_static,
+            parallax_depth_scale,
+            alpha_mask_threshold,
+            render_layers,
         )| {
             if !view_visibility.get() {
                 return;
@@ -308,6 +349,9 @@ pub fn extract_meshes(
             if transmitted_receiver {
                 flags |= MeshFlags::TRANSMITTED_SHADOW_RECEIVER;
             }
+            if ssao_static {
+                flags |= MeshFlags::SSAO_STATIC;
+            }
             if transform.matrix3.determinant().is_sign_positive() {
                 flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
             }
@@ -326,6 +370,9 @@ pub fn extract_meshes(
                     shadow_caster: !not_shadow_caster,
                     material_bind_group_id: AtomicMaterialBindGroupId::default(),
                     automatic_batching: !no_automatic_batching,
+                    parallax_depth_scale: parallax_depth_scale.map(|scale| scale.0),
+                    alpha_mask_threshold: alpha_mask_threshold.map(|threshold| threshold.0),
+                    render_layers: render_layers.copied().unwrap_or_default(),
                 },
             ));
             tls.set(queue);
@@ -474,6 +521,9 @@ impl GetBatchData for MeshPipeline {
             MeshUniform::new(
                 &mesh_instance.transforms,
                 maybe_lightmap.map(|lightmap| lightmap.uv_rect),
+                mesh_instance.parallax_depth_scale,
+                mesh_instance.alpha_mask_threshold,
+                mesh_instance.render_layers.bits(),
             ),
             mesh_instance.should_batch().then_some((
                 mesh_instance.material_bind_group_id.get(),
@@ -489,7 +539,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     // NOTE: Apparently quadro drivers support up to 64x MSAA.
     /// MSAA uses the highest 3 bits for the MSAA log2(sample count) to support up to 128x MSAA.
-    pub struct MeshPipelineKey: u32 {
+    pub struct MeshPipelineKey: u64 {
         const NONE                              = 0;
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
@@ -508,6 +558,14 @@ bitflags::bitflags! {
         const READS_VIEW_TRANSMISSION_TEXTURE   = 1 << 13;
         const LIGHTMAPPED                       = 1 << 14;
         const IRRADIANCE_VOLUME                 = 1 << 15;
+        const POINT_LIGHT_SHADOW_FILTER_PCF     = 1 << 16;
+        const DUAL_PARABOLOID_POINT_SHADOWS     = 1 << 17;
+        const SHADOW_FILTER_JITTER              = 1 << 18;
+        const DEFERRED_OPAQUE_SPLIT             = 1 << 19;
+        const SIMPLE_FORWARD_LIGHTING           = 1 << 20;
+        const DEPTH_WRITE_OVERRIDE              = 1 << 21; // Depth writes follow DEPTH_WRITE_ENABLED instead of the blend state
+        const DEPTH_WRITE_ENABLED               = 1 << 22;
+        const FOG_COLOR_GRADIENT                = 1 << 23;
         const BLEND_RESERVED_BITS               = Self::BLEND_MASK_BITS << Self::BLEND_SHIFT_BITS; // ← Bitmask reserving bits for the blend state
         const BLEND_OPAQUE                      = 0 << Self::BLEND_SHIFT_BITS;                   // ← Values are just sequential within the mask, and can range from 0 to 3
         const BLEND_PREMULTIPLIED_ALPHA         = 1 << Self::BLEND_SHIFT_BITS;                   //
@@ -528,6 +586,10 @@ bitflags::bitflags! {
         const SHADOW_FILTER_METHOD_HARDWARE_2X2  = 0 << Self::SHADOW_FILTER_METHOD_SHIFT_BITS;
         const SHADOW_FILTER_METHOD_CASTANO_13    = 1 << Self::SHADOW_FILTER_METHOD_SHIFT_BITS;
         const SHADOW_FILTER_METHOD_JIMENEZ_14    = 2 << Self::SHADOW_FILTER_METHOD_SHIFT_BITS;
+        const SHADOW_FILTER_QUALITY_RESERVED_BITS = Self::SHADOW_FILTER_QUALITY_MASK_BITS << Self::SHADOW_FILTER_QUALITY_SHIFT_BITS;
+        const SHADOW_FILTER_QUALITY_MEDIUM       = 0 << Self::SHADOW_FILTER_QUALITY_SHIFT_BITS;
+        const SHADOW_FILTER_QUALITY_LOW          = 1 << Self::SHADOW_FILTER_QUALITY_SHIFT_BITS;
+        const SHADOW_FILTER_QUALITY_HIGH         = 2 << Self::SHADOW_FILTER_QUALITY_SHIFT_BITS;
         const VIEW_PROJECTION_RESERVED_BITS     = Self::VIEW_PROJECTION_MASK_BITS << Self::VIEW_PROJECTION_SHIFT_BITS;
         const VIEW_PROJECTION_NONSTANDARD       = 0 << Self::VIEW_PROJECTION_SHIFT_BITS;
         const VIEW_PROJECTION_PERSPECTIVE       = 1 << Self::VIEW_PROJECTION_SHIFT_BITS;
@@ -538,40 +600,57 @@ bitflags::bitflags! {
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM = 1 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH = 2 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA = 3 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+        const PBR_DEBUG_RESERVED_BITS           = Self::PBR_DEBUG_MASK_BITS << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_NONE                    = 0 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_SHADOW_CASCADES         = 1 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_CLUSTER_Z_SLICES        = 2 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_CLUSTER_LIGHT_COMPLEXITY = 3 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_CLUSTER_COHERENCY       = 4 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_DIRECT_LIGHT            = 5 << Self::PBR_DEBUG_SHIFT_BITS;
+        const PBR_DEBUG_INDIRECT_LIGHT          = 6 << Self::PBR_DEBUG_SHIFT_BITS;
     }
 }
 
 impl MeshPipelineKey {
-    const MSAA_MASK_BITS: u32 = 0b111;
-    const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
+    const MSAA_MASK_BITS: u64 = 0b111;
+    const MSAA_SHIFT_BITS: u32 = 64 - Self::MSAA_MASK_BITS.count_ones();
 
-    const PRIMITIVE_TOPOLOGY_MASK_BITS: u32 = 0b111;
+    const PRIMITIVE_TOPOLOGY_MASK_BITS: u64 = 0b111;
     const PRIMITIVE_TOPOLOGY_SHIFT_BITS: u32 =
         Self::MSAA_SHIFT_BITS - Self::PRIMITIVE_TOPOLOGY_MASK_BITS.count_ones();
 
-    const BLEND_MASK_BITS: u32 = 0b11;
+    const BLEND_MASK_BITS: u64 = 0b11;
     const BLEND_SHIFT_BITS: u32 =
         Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS - Self::BLEND_MASK_BITS.count_ones();
 
-    const TONEMAP_METHOD_MASK_BITS: u32 = 0b111;
+    const TONEMAP_METHOD_MASK_BITS: u64 = 0b111;
     const TONEMAP_METHOD_SHIFT_BITS: u32 =
         Self::BLEND_SHIFT_BITS - Self::TONEMAP_METHOD_MASK_BITS.count_ones();
 
-    const SHADOW_FILTER_METHOD_MASK_BITS: u32 = 0b11;
+    const SHADOW_FILTER_METHOD_MASK_BITS: u64 = 0b11;
     const SHADOW_FILTER_METHOD_SHIFT_BITS: u32 =
         Self::TONEMAP_METHOD_SHIFT_BITS - Self::SHADOW_FILTER_METHOD_MASK_BITS.count_ones();
 
-    const VIEW_PROJECTION_MASK_BITS: u32 = 0b11;
+    const VIEW_PROJECTION_MASK_BITS: u64 = 0b11;
     const VIEW_PROJECTION_SHIFT_BITS: u32 =
         Self::SHADOW_FILTER_METHOD_SHIFT_BITS - Self::VIEW_PROJECTION_MASK_BITS.count_ones();
 
-    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u32 = 0b11;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u64 = 0b11;
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u32 = Self::VIEW_PROJECTION_SHIFT_BITS
         - Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS.count_ones();
 
+    const SHADOW_FILTER_QUALITY_MASK_BITS: u64 = 0b11;
+    const SHADOW_FILTER_QUALITY_SHIFT_BITS: u32 =
+        Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS
+            - Self::SHADOW_FILTER_QUALITY_MASK_BITS.count_ones();
+
+    const PBR_DEBUG_MASK_BITS: u64 = 0b111;
+    const PBR_DEBUG_SHIFT_BITS: u32 =
+        Self::SHADOW_FILTER_QUALITY_SHIFT_BITS - Self::PBR_DEBUG_MASK_BITS.count_ones();
+
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
-            (msaa_samples.trailing_zeros() & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
+            (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
         Self::from_bits_retain(msaa_bits)
     }
 
@@ -584,11 +663,11 @@ impl MeshPipelineKey {
     }
 
     pub fn msaa_samples(&self) -> u32 {
-        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
+        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS) as u32
     }
 
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
-        let primitive_topology_bits = ((primitive_topology as u32)
+        let primitive_topology_bits = ((primitive_topology as u64)
             & Self::PRIMITIVE_TOPOLOGY_MASK_BITS)
             << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
         Self::from_bits_retain(primitive_topology_bits)
@@ -598,14 +677,47 @@ impl MeshPipelineKey {
         let primitive_topology_bits = (self.bits() >> Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS)
             & Self::PRIMITIVE_TOPOLOGY_MASK_BITS;
         match primitive_topology_bits {
-            x if x == PrimitiveTopology::PointList as u32 => PrimitiveTopology::PointList,
-            x if x == PrimitiveTopology::LineList as u32 => PrimitiveTopology::LineList,
-            x if x == PrimitiveTopology::LineStrip as u32 => PrimitiveTopology::LineStrip,
-            x if x == PrimitiveTopology::TriangleList as u32 => PrimitiveTopology::TriangleList,
-            x if x == PrimitiveTopology::TriangleStrip as u32 => PrimitiveTopology::TriangleStrip,
+            x if x == PrimitiveTopology::PointList as u64 => PrimitiveTopology::PointList,
+            x if x == PrimitiveTopology::LineList as u64 => PrimitiveTopology::LineList,
+            x if x == PrimitiveTopology::LineStrip as u64 => PrimitiveTopology::LineStrip,
+            x if x == PrimitiveTopology::TriangleList as u64 => PrimitiveTopology::TriangleList,
+            x if x == PrimitiveTopology::TriangleStrip as u64 => PrimitiveTopology::TriangleStrip,
             _ => PrimitiveTopology::default(),
         }
     }
+
+    pub fn from_pbr_debug_visualization(visualization: PbrDebugVisualization) -> Self {
+        match visualization {
+            PbrDebugVisualization::None => MeshPipelineKey::PBR_DEBUG_NONE,
+            PbrDebugVisualization::ShadowCascades => MeshPipelineKey::PBR_DEBUG_SHADOW_CASCADES,
+            PbrDebugVisualization::ClusterZSlices => MeshPipelineKey::PBR_DEBUG_CLUSTER_Z_SLICES,
+            PbrDebugVisualization::ClusterLightComplexity => {
+                MeshPipelineKey::PBR_DEBUG_CLUSTER_LIGHT_COMPLEXITY
+            }
+            PbrDebugVisualization::ClusterCoherency => MeshPipelineKey::PBR_DEBUG_CLUSTER_COHERENCY,
+            PbrDebugVisualization::DirectLight => MeshPipelineKey::PBR_DEBUG_DIRECT_LIGHT,
+            PbrDebugVisualization::IndirectLight => MeshPipelineKey::PBR_DEBUG_INDIRECT_LIGHT,
+        }
+    }
+
+    /// The shader def enabling the [`PbrDebugVisualization`] of this key, if any.
+    pub fn pbr_debug_shader_def(&self) -> Option<&'static str> {
+        match self.intersection(MeshPipelineKey::PBR_DEBUG_RESERVED_BITS) {
+            MeshPipelineKey::PBR_DEBUG_SHADOW_CASCADES => {
+                Some("DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES")
+            }
+            MeshPipelineKey::PBR_DEBUG_CLUSTER_Z_SLICES => Some("CLUSTERED_FORWARD_DEBUG_Z_SLICES"),
+            MeshPipelineKey::PBR_DEBUG_CLUSTER_LIGHT_COMPLEXITY => {
+                Some("CLUSTERED_FORWARD_DEBUG_CLUSTER_LIGHT_COMPLEXITY")
+            }
+            MeshPipelineKey::PBR_DEBUG_CLUSTER_COHERENCY => {
+                Some("CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY")
+            }
+            MeshPipelineKey::PBR_DEBUG_DIRECT_LIGHT => Some("PBR_DEBUG_DIRECT_LIGHT"),
+            MeshPipelineKey::PBR_DEBUG_INDIRECT_LIGHT => Some("PBR_DEBUG_INDIRECT_LIGHT"),
+            _ => None,
+        }
+    }
 }
 
 fn is_skinned(layout: &Hashed<InnerMeshVertexBufferLayout>) -> bool {
@@ -695,6 +807,14 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("PBR_TRANSMISSION_TEXTURES_SUPPORTED".into());
         }
 
+        if cfg!(feature = "pbr_reflectance_textures") {
+            shader_defs.push("PBR_REFLECTANCE_TEXTURES_SUPPORTED".into());
+        }
+
+        if cfg!(feature = "pbr_specular_glossiness_textures") {
+            shader_defs.push("PBR_SPECULAR_GLOSSINESS_TEXTURES_SUPPORTED".into());
+        }
+
         let mut bind_group_layout = vec![self.get_view_layout(key.into()).clone()];
 
         if key.msaa_samples() > 1 {
@@ -832,6 +952,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("IRRADIANCE_VOLUME".into());
         }
 
+        if key.contains(MeshPipelineKey::FOG_COLOR_GRADIENT) {
+            shader_defs.push("FOG_COLOR_GRADIENT".into());
+        }
+
         if key.contains(MeshPipelineKey::LIGHTMAPPED) {
             shader_defs.push("LIGHTMAP".into());
         }
@@ -850,6 +974,40 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("SHADOW_FILTER_METHOD_JIMENEZ_14".into());
         }
 
+        if key.contains(MeshPipelineKey::POINT_LIGHT_SHADOW_FILTER_PCF) {
+            shader_defs.push("POINT_LIGHT_SHADOW_FILTER_PCF".into());
+        }
+
+        if key.contains(MeshPipelineKey::SHADOW_FILTER_JITTER) {
+            shader_defs.push("SHADOW_FILTER_JITTER".into());
+        }
+
+        if key.contains(MeshPipelineKey::DUAL_PARABOLOID_POINT_SHADOWS) {
+            shader_defs.push("DUAL_PARABOLOID_POINT_SHADOWS".into());
+        }
+
+        if key.contains(MeshPipelineKey::SIMPLE_FORWARD_LIGHTING) {
+            shader_defs.push("SIMPLE_FORWARD_LIGHTING".into());
+        }
+
+        if key.contains(MeshPipelineKey::DEFERRED_OPAQUE_SPLIT) {
+            shader_defs.push("DEFERRED_OPAQUE_SPLIT".into());
+        }
+
+        if let Some(pbr_debug_shader_def) = key.pbr_debug_shader_def() {
+            shader_defs.push(pbr_debug_shader_def.into());
+        }
+
+        let shadow_filter_quality =
+            key.intersection(MeshPipelineKey::SHADOW_FILTER_QUALITY_RESERVED_BITS);
+        if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_LOW {
+            shader_defs.push("SHADOW_FILTER_QUALITY_LOW".into());
+        } else if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_MEDIUM {
+            shader_defs.push("SHADOW_FILTER_QUALITY_MEDIUM".into());
+        } else if shadow_filter_quality == MeshPipelineKey::SHADOW_FILTER_QUALITY_HIGH {
+            shader_defs.push("SHADOW_FILTER_QUALITY_HIGH".into());
+        }
+
         let blur_quality =
             key.intersection(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS);
 
@@ -872,6 +1030,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("IRRADIANCE_VOLUMES_ARE_USABLE".into());
         }
 
+        if SKYBOX_AMBIENT_IS_USABLE {
+            shader_defs.push("SKYBOX_AMBIENT_IS_USABLE".into());
+        }
+
         let format = if key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -3,7 +3,7 @@
 #import bevy_pbr::{
     mesh_view_bindings::view,
     mesh_bindings::mesh,
-    mesh_types::MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT,
+    mesh_types::{MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT, MESH_FLAGS_SSAO_STATIC_BIT},
     view_transformations::position_world_to_clip,
 }
 #import bevy_render::maths::{affine3_to_square, mat2x4_f32_to_mat3x3_unpack}
@@ -58,6 +58,12 @@ fn sign_determinant_model_3x3m(instance_index: u32) -> f32 {
     return f32(bool(mesh[instance_index].flags & MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT)) * 2.0 - 1.0;
 }
 
+// The alpha written to the normal prepass texture. Meshes that are part of the baked SSAO
+// (`ScreenSpaceAmbientOcclusionStatic`) write 0.0, everything else writes 1.0.
+fn prepass_normal_alpha(instance_index: u32) -> f32 {
+    return select(1.0, 0.0, (mesh[instance_index].flags & MESH_FLAGS_SSAO_STATIC_BIT) != 0u);
+}
+
 fn mesh_tangent_local_to_world(model: mat4x4<f32>, vertex_tangent: vec4<f32>, instance_index: u32) -> vec4<f32> {
     // NOTE: The mikktspace method of normal mapping requires that the world tangent is
     // re-normalized in the vertex shader to match the way mikktspace bakes vertex tangents
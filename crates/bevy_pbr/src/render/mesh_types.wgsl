@@ -15,6 +15,12 @@ struct Mesh {
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     lightmap_uv_rect: vec2<u32>,
+    // Only used if MESH_FLAGS_PARALLAX_DEPTH_SCALE_BIT is set.
+    parallax_depth_scale: f32,
+    // Only used if MESH_FLAGS_ALPHA_MASK_THRESHOLD_BIT is set.
+    alpha_mask_threshold: f32,
+    // The `RenderLayers` of the mesh, as a bit mask.
+    render_layers: u32,
 };
 
 #ifdef SKINNED
@@ -31,5 +37,8 @@ struct MorphWeights {
 
 const MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
 const MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT: u32 = 2u;
+const MESH_FLAGS_PARALLAX_DEPTH_SCALE_BIT: u32 = 4u;
+const MESH_FLAGS_ALPHA_MASK_THRESHOLD_BIT: u32 = 8u;
+const MESH_FLAGS_SSAO_STATIC_BIT: u32 = 16u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 const MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
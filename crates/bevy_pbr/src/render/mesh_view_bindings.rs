@@ -37,7 +37,11 @@ use crate::{
         self, IrradianceVolume, RenderViewIrradianceVolumeBindGroupEntries,
         IRRADIANCE_VOLUMES_ARE_USABLE,
     },
-    prepass, FogMeta, GlobalLightMeta, GpuFog, GpuLights, GpuPointLights, LightMeta,
+    prepass,
+    skybox_ambient::{
+        GpuSkyboxAmbient, SkyboxAmbientFallbackBuffer, ViewSkyboxAmbient, SKYBOX_AMBIENT_IS_USABLE,
+    },
+    FogMeta, FogSettings, GlobalLightMeta, GpuFog, GpuLights, GpuPointLights, LightMeta,
     LightProbesBuffer, LightProbesUniform, MeshPipeline, MeshPipelineKey, RenderViewLightProbes,
     ScreenSpaceAmbientOcclusionTextures, ShadowSamplers, ViewClusterBindings, ViewShadowBindings,
 };
@@ -64,6 +68,7 @@ bitflags::bitflags! {
         const NORMAL_PREPASS              = 1 << 2;
         const MOTION_VECTOR_PREPASS       = 1 << 3;
         const DEFERRED_PREPASS            = 1 << 4;
+        const FOG_COLOR_GRADIENT          = 1 << 5;
     }
 }
 
@@ -76,7 +81,7 @@ impl MeshPipelineViewLayoutKey {
         use MeshPipelineViewLayoutKey as Key;
 
         format!(
-            "mesh_view_layout{}{}{}{}{}",
+            "mesh_view_layout{}{}{}{}{}{}",
             self.contains(Key::MULTISAMPLED)
                 .then_some("_multisampled")
                 .unwrap_or_default(),
@@ -92,6 +97,9 @@ impl MeshPipelineViewLayoutKey {
             self.contains(Key::DEFERRED_PREPASS)
                 .then_some("_deferred")
                 .unwrap_or_default(),
+            self.contains(Key::FOG_COLOR_GRADIENT)
+                .then_some("_fog_gradient")
+                .unwrap_or_default(),
         )
     }
 }
@@ -115,6 +123,9 @@ impl From<MeshPipelineKey> for MeshPipelineViewLayoutKey {
         if value.contains(MeshPipelineKey::DEFERRED_PREPASS) {
             result |= MeshPipelineViewLayoutKey::DEFERRED_PREPASS;
         }
+        if value.contains(MeshPipelineKey::FOG_COLOR_GRADIENT) {
+            result |= MeshPipelineViewLayoutKey::FOG_COLOR_GRADIENT;
+        }
 
         result
     }
@@ -321,6 +332,22 @@ fn layout_entries(
         (25, sampler(SamplerBindingType::Filtering)),
     ));
 
+    // Skybox ambient light
+    if SKYBOX_AMBIENT_IS_USABLE {
+        entries = entries.extend_with_indices(((26, uniform_buffer::<GpuSkyboxAmbient>(false)),));
+    }
+
+    // Fog color gradient
+    if layout_key.contains(MeshPipelineViewLayoutKey::FOG_COLOR_GRADIENT) {
+        entries = entries.extend_with_indices((
+            (
+                27,
+                texture_2d(TextureSampleType::Float { filterable: true }),
+            ),
+            (28, sampler(SamplerBindingType::Filtering)),
+        ));
+    }
+
     entries.to_vec()
 }
 
@@ -374,6 +401,8 @@ pub fn prepare_mesh_view_bind_groups(
         &Tonemapping,
         Option<&RenderViewLightProbes<EnvironmentMapLight>>,
         Option<&RenderViewLightProbes<IrradianceVolume>>,
+        Option<&ViewSkyboxAmbient>,
+        Option<&FogSettings>,
     )>,
     (images, mut fallback_images, fallback_image, fallback_image_zero): (
         Res<RenderAssets<Image>>,
@@ -385,6 +414,7 @@ pub fn prepare_mesh_view_bind_groups(
     globals_buffer: Res<GlobalsBuffer>,
     tonemapping_luts: Res<TonemappingLuts>,
     light_probes_buffer: Res<LightProbesBuffer>,
+    skybox_ambient_fallback_buffer: Option<Res<SkyboxAmbientFallbackBuffer>>,
 ) {
     if let (
         Some(view_binding),
@@ -411,6 +441,8 @@ pub fn prepare_mesh_view_bind_groups(
             tonemapping,
             render_view_environment_maps,
             render_view_irradiance_volumes,
+            skybox_ambient,
+            fog_settings,
         ) in &views
         {
             let fallback_ssao = fallback_images
@@ -421,10 +453,14 @@ pub fn prepare_mesh_view_bind_groups(
                 .map(|t| &t.screen_space_ambient_occlusion_texture.default_view)
                 .unwrap_or(&fallback_ssao);
 
-            let layout = &mesh_pipeline.get_view_layout(
-                MeshPipelineViewLayoutKey::from(*msaa)
-                    | MeshPipelineViewLayoutKey::from(prepass_textures),
-            );
+            let fog_color_gradient = fog_settings.and_then(|fog| fog.color_gradient.as_ref());
+
+            let mut layout_key = MeshPipelineViewLayoutKey::from(*msaa)
+                | MeshPipelineViewLayoutKey::from(prepass_textures);
+            if fog_color_gradient.is_some() {
+                layout_key |= MeshPipelineViewLayoutKey::FOG_COLOR_GRADIENT;
+            }
+            let layout = &mesh_pipeline.get_view_layout(layout_key);
 
             let mut entries = DynamicBindGroupEntries::new_with_indices((
                 (0, view_binding.clone()),
@@ -531,6 +567,25 @@ pub fn prepare_mesh_view_bind_groups(
             entries =
                 entries.extend_with_indices(((24, transmission_view), (25, transmission_sampler)));
 
+            if let Some(fallback_buffer) = &skybox_ambient_fallback_buffer {
+                let skybox_ambient_buffer = skybox_ambient
+                    .map(|skybox_ambient| &skybox_ambient.buffer)
+                    .unwrap_or(&fallback_buffer.0);
+                entries =
+                    entries.extend_with_indices(((26, skybox_ambient_buffer.as_entire_binding()),));
+            }
+
+            if let Some(fog_color_gradient) = fog_color_gradient {
+                // The gradient may not be loaded yet, so fall back to white, which leaves the fog
+                // color unchanged
+                let fog_color_gradient =
+                    images.get(fog_color_gradient).unwrap_or(&fallback_image.d2);
+                entries = entries.extend_with_indices((
+                    (27, &fog_color_gradient.texture_view),
+                    (28, &fog_color_gradient.sampler),
+                ));
+            }
+
             commands.entity(entity).insert(MeshViewBindGroup {
                 value: render_device.create_bind_group("mesh_view_bind_group", layout, &entries),
             });
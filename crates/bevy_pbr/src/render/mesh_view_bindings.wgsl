@@ -90,3 +90,12 @@
 
 @group(0) @binding(24) var view_transmission_texture: texture_2d<f32>;
 @group(0) @binding(25) var view_transmission_sampler: sampler;
+
+#ifdef SKYBOX_AMBIENT_IS_USABLE
+@group(0) @binding(26) var<uniform> skybox_ambient: types::SkyboxAmbient;
+#endif
+
+#ifdef FOG_COLOR_GRADIENT
+@group(0) @binding(27) var fog_color_gradient: texture_2d<f32>;
+@group(0) @binding(28) var fog_color_gradient_sampler: sampler;
+#endif
@@ -2,24 +2,38 @@
 
 struct PointLight {
     // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
-    // For spot lights: the direction (x,z), spot_scale and spot_offset
+    // For spot lights: the direction (x,z), spot_scale and the tangent of the outer angle
     light_custom_data: vec4<f32>,
     color_inverse_square_range: vec4<f32>,
     position_radius: vec4<f32>,
-    // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
+    // 'flags' is a bit field indicating various options in bits 0-7. Bits 8-15 hold the fraction
+    // of the range over which the light fades out as unorm8, and bits 16-31 the rotation of a
+    // spot light's local X axis around the spot direction, relative to the right axis of the
+    // shadow map view, as a unorm16 fraction of a full turn.
     flags: u32,
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
-    spot_light_tan_angle: f32,
+    // For spot lights: the barn door cuts as unorm8 fractions of the outer angle, packed as
+    // left | right << 8 | bottom << 16 | top << 24
+    spot_light_barn_doors: u32,
 };
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+const POINT_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT: u32   = 4u;
+const POINT_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT: u32  = 8u;
 
 struct DirectionalCascade {
     view_projection: mat4x4<f32>,
     texel_size: f32,
     far_bound: f32,
+    intensity: f32,
+    // The fraction of the shadow map layer covered by the cascade.
+    uv_scale: f32,
+    // The top left corner of the cascade within its shadow map layer.
+    uv_offset: vec2<f32>,
+    // The shadow map layer containing the cascade.
+    array_index: u32,
 }
 
 struct DirectionalLight {
@@ -32,16 +46,21 @@ struct DirectionalLight {
     shadow_normal_bias: f32,
     num_cascades: u32,
     cascades_overlap_proportion: f32,
-    depth_texture_base_index: u32,
     render_layers: u32,
+    shadow_fade_distance: f32,
 };
 
-const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32 = 1u;
+const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32  = 1u;
+const DIRECTIONAL_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT: u32  = 2u;
+const DIRECTIONAL_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT: u32 = 4u;
 
 struct Lights {
     // NOTE: this array size must be kept in sync with the constants defined in bevy_pbr/src/render/light.rs
     directional_lights: array<DirectionalLight, #{MAX_DIRECTIONAL_LIGHTS}u>,
     ambient_color: vec4<f32>,
+    // Equal to `ambient_color` unless the ambient light is a hemisphere light
+    ambient_ground_color: vec4<f32>,
+    ambient_up: vec3<f32>,
     // x/y/z dimensions and n_clusters in w
     cluster_dimensions: vec4<u32>,
     // xy are vec2<f32>(cluster_dimensions.xy) / vec2<f32>(view.width, view.height)
@@ -57,10 +76,21 @@ struct Lights {
     cluster_factors: vec4<f32>,
     n_directional_lights: u32,
     spot_light_shadowmap_offset: i32,
+    // Frames since the last `ShadowMapRefresh`
+    shadow_jitter_frame: u32,
     environment_map_smallest_specular_mip_level: u32,
     environment_map_intensity: f32,
 };
 
+// The spherical harmonics projection of the skybox, used for ambient light when
+// the view has no environment map.
+struct SkyboxAmbient {
+    // The nine L2 coefficients of the skybox radiance. `w` is unused.
+    coefficients: array<vec4<f32>, 9>,
+    // Nonzero if `coefficients` is valid.
+    enabled: u32,
+};
+
 struct Fog {
     base_color: vec4<f32>,
     directional_light_color: vec4<f32>,
@@ -69,7 +99,7 @@ struct Fog {
     // For Linear Fog:
     //     be.x = start, be.y = end
     // For Exponential and ExponentialSquared Fog:
-    //     be.x = density
+    //     be = per-channel density
     // For Atmospheric Fog:
     //     be = per-channel extinction density
     //     bi = per-channel inscattering density
@@ -77,6 +107,11 @@ struct Fog {
     directional_light_exponent: f32,
     bi: vec3<f32>,
     mode: u32,
+    ambient_occlusion_density: f32,
+    // Only meshes on at least one of these `RenderLayers` are fogged
+    render_layers: u32,
+    // The distance at which `fog_color_gradient` is sampled at its right edge
+    color_gradient_distance: f32,
 }
 
 // Important: These must be kept in sync with `fog.rs`
@@ -117,6 +152,9 @@ struct LightProbe {
     inverse_transpose_transform: mat3x4<f32>,
     cubemap_index: i32,
     intensity: f32,
+    // The rotation from world space to the space the textures are sampled in,
+    // as a quaternion.
+    rotation: vec4<f32>,
 };
 
 struct LightProbes {
@@ -133,4 +171,7 @@ struct LightProbes {
     smallest_specular_mip_level_for_view: u32,
     // The intensity of the environment map associated with the view.
     intensity_for_view: f32,
+    // The rotation from world space to the space the environment map
+    // associated with the view is sampled in, as a quaternion.
+    rotation_for_view: vec4<f32>,
 };
@@ -1,4 +1,5 @@
 mod fog;
+mod gpu_clustering;
 mod light;
 pub(crate) mod mesh;
 mod mesh_bindings;
@@ -7,6 +8,7 @@ mod morph;
 mod skin;
 
 pub use fog::*;
+pub use gpu_clustering::*;
 pub use light::*;
 pub use mesh::*;
 pub use mesh_bindings::MeshLayouts;
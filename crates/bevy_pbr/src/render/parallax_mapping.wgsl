@@ -1,6 +1,10 @@
 #define_import_path bevy_pbr::parallax_mapping
 
-#import bevy_pbr::pbr_bindings::{depth_map_texture, depth_map_sampler}
+#import bevy_pbr::{
+    pbr_bindings::{material, depth_map_texture, depth_map_sampler},
+    mesh_bindings::mesh,
+    mesh_types::MESH_FLAGS_PARALLAX_DEPTH_SCALE_BIT,
+}
 
 fn sample_depth_map(uv: vec2<f32>) -> f32 {
     // We use `textureSampleLevel` over `textureSample` because the wgpu DX12
@@ -116,3 +120,116 @@ fn parallaxed_uv(
     // for light computation later on in future improvements of the pbr shader.
     return uv;
 }
+
+// Applies the parallax mapping of the `StandardMaterial` to `uv`, given the tangent frame of the
+// surface and `V`, the direction from the fragment to the camera.
+//
+// This is shared by the main pass and the prepass, so that both see the same displaced surface.
+fn standard_material_parallaxed_uv(
+    uv: vec2<f32>,
+    V: vec3<f32>,
+    N: vec3<f32>,
+    T: vec3<f32>,
+    B: vec3<f32>,
+    instance_index: u32,
+) -> vec2<f32> {
+    // Transform V from fragment to camera in world space to tangent space.
+    let Vt = vec3(dot(V, T), dot(V, B), dot(V, N));
+    return parallaxed_uv(
+        standard_material_parallax_depth_scale(instance_index),
+        material.max_parallax_layer_count,
+        material.max_relief_mapping_search_steps,
+        uv,
+        // Flip the direction of Vt to go toward the surface to make the
+        // parallax mapping algorithm easier to understand and reason
+        // about.
+        -Vt,
+    );
+}
+
+// Returns the depth scale of the `StandardMaterial`, or the per-mesh override if there is one.
+fn standard_material_parallax_depth_scale(instance_index: u32) -> f32 {
+    if ((mesh[instance_index].flags & MESH_FLAGS_PARALLAX_DEPTH_SCALE_BIT) != 0u) {
+        return mesh[instance_index].parallax_depth_scale;
+    }
+    return material.parallax_depth_scale;
+}
+
+// Returns how much of the light arriving from `Lt`, the direction from the fragment to the light
+// in tangent space, reaches the depth map surface at the parallax mapped `uv`, from `0.0` (fully
+// shadowed) to `1.0` (fully lit).
+//
+// The depth map is marched from the surface toward the light. Every sample above the ray darkens
+// the light by how far it rises above the ray, and samples closer to the fragment darken it more,
+// which gives soft shadows that sharpen toward their occluders.
+// See the self-shadowing section of the parallax occlusion mapping reference above.
+fn parallax_self_shadow(
+    depth_scale: f32,
+    max_layer_count: f32,
+    uv: vec2<f32>,
+    Lt: vec3<f32>,
+) -> f32 {
+    let surface_depth = sample_depth_map(uv);
+    // Lights behind the surface don't light it anyway, and nothing lies above the top of the
+    // depth map.
+    if Lt.z <= 0.0 || surface_depth <= 0.0 || max_layer_count < 1.0 {
+        return 1.0;
+    }
+
+    // Like the view ray in `parallaxed_uv`, use more layers for grazing light.
+    let layer_count = mix(max_layer_count, 1.0, Lt.z);
+    let layer_depth = surface_depth / layer_count;
+    let delta_uv = depth_scale * layer_depth * Lt.xy * vec2(1.0, -1.0) / Lt.z;
+
+    var occlusion = 0.0;
+    var ray_depth = surface_depth - layer_depth;
+    var sample_uv = uv + delta_uv;
+    for (var i: i32 = 1; ray_depth > 0.0 && i <= i32(layer_count); i++) {
+        let texture_depth = sample_depth_map(sample_uv);
+        // texture_depth < ray_depth means the depth map surface is above the ray toward the light
+        if texture_depth < ray_depth {
+            occlusion = max(occlusion, (ray_depth - texture_depth) * (1.0 - f32(i) / layer_count));
+        }
+        ray_depth -= layer_depth;
+        sample_uv += delta_uv;
+    }
+
+    // `occlusion` is in units of the depth map's range, so a sample rising a tenth of that range
+    // above the ray fully shadows the fragment.
+    return 1.0 - saturate(occlusion * 10.0);
+}
+
+// Applies `parallax_self_shadow` with the `StandardMaterial` parameters, given the parallax mapped
+// `uv`, the tangent frame (T, B, N) of the surface and `L`, the direction from the fragment to the
+// light in world space.
+fn standard_material_parallax_self_shadow(
+    uv: vec2<f32>,
+    tangent_frame: mat3x3<f32>,
+    depth_scale: f32,
+    L: vec3<f32>,
+) -> f32 {
+    return parallax_self_shadow(
+        depth_scale,
+        material.max_parallax_layer_count,
+        uv,
+        L * tangent_frame,
+    );
+}
+
+// Returns the tangent and bitangent of the surface at `world_position`, derived from the
+// screen-space derivatives of the position and of `uv`, with the same orientation as the vertex
+// tangents. Used where the vertex tangents aren't available, like in shadow passes.
+// See Christian Schüler, "Normal Mapping Without Precomputed Tangents".
+fn derivative_tangent_frame(world_position: vec3<f32>, N: vec3<f32>, uv: vec2<f32>) -> mat2x3<f32> {
+    let dp1 = dpdx(world_position);
+    let dp2 = dpdy(world_position);
+    let duv1 = dpdx(uv);
+    let duv2 = dpdy(uv);
+    let dp2perp = cross(dp2, N);
+    let dp1perp = cross(N, dp1);
+    let T = dp2perp * duv1.x + dp1perp * duv2.x;
+    // Vertex bitangents point toward decreasing v.
+    let B = -(dp2perp * duv1.y + dp1perp * duv2.y);
+    let inverse_length = inverseSqrt(max(max(dot(T, T), dot(B, B)), 1e-20));
+    return mat2x3(T * inverse_length, B * inverse_length);
+}
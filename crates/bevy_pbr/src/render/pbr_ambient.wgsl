@@ -5,6 +5,31 @@
     mesh_view_bindings::lights,
 }
 
+#ifdef SKYBOX_AMBIENT_IS_USABLE
+// Returns the radiance reflected by a white diffuse surface with normal `N` lit by
+// the skybox, evaluated from its spherical harmonics projection. Each band is
+// convolved with a clamped cosine lobe and divided by π.
+fn skybox_ambient_radiance(world_normal: vec3<f32>) -> vec3<f32> {
+    let c = bevy_pbr::mesh_view_bindings::skybox_ambient.coefficients;
+    // The coefficients were projected from cubemap directions, and cubemaps are
+    // sampled with z flipped, like in `skybox.wgsl`.
+    let N = vec3(world_normal.xy, -world_normal.z);
+    var radiance = c[0].rgb * 0.282095;
+    radiance += (c[1].rgb * N.y + c[2].rgb * N.z + c[3].rgb * N.x) * (0.488603 * 2.0 / 3.0);
+    radiance += (c[4].rgb * N.x * N.y + c[5].rgb * N.y * N.z + c[7].rgb * N.x * N.z) * (1.092548 * 0.25);
+    radiance += c[6].rgb * (0.315392 * 0.25) * (3.0 * N.z * N.z - 1.0);
+    radiance += c[8].rgb * (0.546274 * 0.25) * (N.x * N.x - N.y * N.y);
+    return max(radiance, vec3(0.0));
+}
+#endif // SKYBOX_AMBIENT_IS_USABLE
+
+// Returns the ambient light color arriving from around `direction`, blending
+// from the ground color below to the sky color above.
+fn hemisphere_ambient_color(direction: vec3<f32>) -> vec3<f32> {
+    let sky_weight = dot(direction, lights.ambient_up) * 0.5 + 0.5;
+    return mix(lights.ambient_ground_color.rgb, lights.ambient_color.rgb, sky_weight);
+}
+
 // A precomputed `NdotV` is provided because it is computed regardless,
 // but `world_normal` and the view vector `V` are provided separately for more advanced uses.
 fn ambient_light(
@@ -25,5 +50,17 @@ fn ambient_light(
     // See: https://google.github.io/filament/Filament.html#specularocclusion
     let specular_occlusion = saturate(dot(specular_color, vec3(50.0 * 0.33)));
 
-    return (diffuse_ambient + specular_ambient * specular_occlusion) * lights.ambient_color.rgb * occlusion;
+#ifdef SKYBOX_AMBIENT_IS_USABLE
+    // If the view has a skybox but no environment map, light with the skybox
+    // instead of the flat ambient color.
+    if bevy_pbr::mesh_view_bindings::skybox_ambient.enabled != 0u {
+        let diffuse_radiance = skybox_ambient_radiance(world_normal);
+        let specular_radiance = skybox_ambient_radiance(reflect(-V, world_normal));
+        return (diffuse_ambient * diffuse_radiance + specular_ambient * specular_occlusion * specular_radiance) * occlusion;
+    }
+#endif // SKYBOX_AMBIENT_IS_USABLE
+
+    let diffuse_color_ambient = hemisphere_ambient_color(world_normal);
+    let specular_color_ambient = hemisphere_ambient_color(reflect(-V, world_normal));
+    return (diffuse_ambient * diffuse_color_ambient + specular_ambient * specular_occlusion * specular_color_ambient) * occlusion;
 }
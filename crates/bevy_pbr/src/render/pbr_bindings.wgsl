@@ -23,3 +23,11 @@
 @group(2) @binding(17) var diffuse_transmission_texture: texture_2d<f32>;
 @group(2) @binding(18) var diffuse_transmission_sampler: sampler;
 #endif
+#ifdef PBR_REFLECTANCE_TEXTURES_SUPPORTED
+@group(2) @binding(19) var reflectance_texture: texture_2d<f32>;
+@group(2) @binding(20) var reflectance_sampler: sampler;
+#endif
+#ifdef PBR_SPECULAR_GLOSSINESS_TEXTURES_SUPPORTED
+@group(2) @binding(21) var specular_glossiness_texture: texture_2d<f32>;
+@group(2) @binding(22) var specular_glossiness_sampler: sampler;
+#endif
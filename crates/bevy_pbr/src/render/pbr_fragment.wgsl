@@ -7,8 +7,9 @@
     prepass_utils,
     lighting,
     mesh_bindings::mesh,
+    mesh_types,
     mesh_view_bindings::view,
-    parallax_mapping::parallaxed_uv,
+    parallax_mapping::{standard_material_parallaxed_uv, standard_material_parallax_depth_scale},
     lightmap::lightmap,
 }
 #import bevy_render::maths::affine2_to_square
@@ -42,8 +43,14 @@ fn pbr_input_from_vertex_output(
     pbr_input.material.base_color = in.color;
 #endif
 
+#ifdef STANDARD_MATERIAL_FLAT_SHADING
+    let world_normal = pbr_functions::flat_world_normal(in.world_position.xyz, in.world_normal);
+#else
+    let world_normal = in.world_normal;
+#endif
+
     pbr_input.world_normal = pbr_functions::prepare_world_normal(
-        in.world_normal,
+        world_normal,
         double_sided,
         is_front,
     );
@@ -57,6 +64,42 @@ fn pbr_input_from_vertex_output(
     return pbr_input;
 }
 
+// Converts a diffuse and specular color into a base color (rgb) and metallic value (a).
+// Based on the conversion from the `KHR_materials_pbrSpecularGlossiness` glTF extension samples:
+// https://github.com/KhronosGroup/glTF/tree/main/extensions/2.0/Archived/KHR_materials_pbrSpecularGlossiness
+fn specular_glossiness_to_metallic(diffuse: vec3<f32>, specular: vec3<f32>) -> vec4<f32> {
+    let dielectric_specular = 0.04;
+    let epsilon = 1e-6;
+
+    let specular_strength = max(specular.r, max(specular.g, specular.b));
+    let one_minus_specular_strength = 1.0 - specular_strength;
+
+    // Solve the quadratic relating the perceived brightness of the diffuse and specular colors
+    // to the metallic value.
+    var metallic = 0.0;
+    let specular_brightness = perceived_brightness(specular);
+    if (specular_brightness >= dielectric_specular) {
+        let diffuse_brightness = perceived_brightness(diffuse);
+        let a = dielectric_specular;
+        let b = diffuse_brightness * one_minus_specular_strength / (1.0 - dielectric_specular)
+            + specular_brightness - 2.0 * dielectric_specular;
+        let c = dielectric_specular - specular_brightness;
+        let d = max(b * b - 4.0 * a * c, 0.0);
+        metallic = saturate((-b + sqrt(d)) / (2.0 * a));
+    }
+
+    let base_color_from_diffuse = diffuse
+        * (one_minus_specular_strength / (1.0 - dielectric_specular) / max(1.0 - metallic, epsilon));
+    let base_color_from_specular = (specular - dielectric_specular * (1.0 - metallic))
+        / max(metallic, epsilon);
+    let base_color = saturate(mix(base_color_from_diffuse, base_color_from_specular, metallic * metallic));
+    return vec4(base_color, metallic);
+}
+
+fn perceived_brightness(color: vec3<f32>) -> f32 {
+    return sqrt(dot(color * color, vec3(0.299, 0.587, 0.114)));
+}
+
 // Prepare a full PbrInput by sampling all textures to resolve
 // the material members
 fn pbr_input_from_standard_material(
@@ -70,6 +113,11 @@ fn pbr_input_from_standard_material(
     pbr_input.material.base_color *= pbr_bindings::material.base_color;
     pbr_input.material.deferred_lighting_pass_id = pbr_bindings::material.deferred_lighting_pass_id;
 
+    pbr_input.material.alpha_cutoff = pbr_bindings::material.alpha_cutoff;
+    if ((mesh[in.instance_index].flags & mesh_types::MESH_FLAGS_ALPHA_MASK_THRESHOLD_BIT) != 0u) {
+        pbr_input.material.alpha_cutoff = mesh[in.instance_index].alpha_mask_threshold;
+    }
+
     // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
     let NdotV = max(dot(pbr_input.N, pbr_input.V), 0.0001);
 
@@ -79,48 +127,69 @@ fn pbr_input_from_standard_material(
 
 #ifdef VERTEX_TANGENTS
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT) != 0u) {
-        let V = pbr_input.V;
         let N = in.world_normal;
         let T = in.world_tangent.xyz;
         let B = in.world_tangent.w * cross(N, T);
-        // Transform V from fragment to camera in world space to tangent space.
-        let Vt = vec3(dot(V, T), dot(V, B), dot(V, N));
-        uv = parallaxed_uv(
-            pbr_bindings::material.parallax_depth_scale,
-            pbr_bindings::material.max_parallax_layer_count,
-            pbr_bindings::material.max_relief_mapping_search_steps,
-            uv,
-            // Flip the direction of Vt to go toward the surface to make the
-            // parallax mapping algorithm easier to understand and reason
-            // about.
-            -Vt,
-        );
+        uv = standard_material_parallaxed_uv(uv, pbr_input.V, N, T, B, in.instance_index);
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+        pbr_input.parallax_uv = uv;
+        pbr_input.parallax_tangent_frame = mat3x3(T, B, N);
+        pbr_input.material.parallax_depth_scale = standard_material_parallax_depth_scale(in.instance_index);
+#endif
     }
 #endif // VERTEX_TANGENTS
 
+#ifndef STANDARD_MATERIAL_WORLD_UV
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
         pbr_input.material.base_color *= textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, uv, view.mip_bias);
     }
+#endif
 #endif // VERTEX_UVS
 
+#ifdef STANDARD_MATERIAL_WORLD_UV
+    // Only the base color is projected from world space, so that it works on meshes without UVs.
+    // The other textures keep the mesh UVs, which the tangents of the normal map follow.
+    if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
+        let world_uv = (affine2_to_square(pbr_bindings::material.uv_transform) * vec3(in.world_position.xz * pbr_bindings::material.world_uv_scale, 1.0)).xy;
+        pbr_input.material.base_color *= textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, world_uv, view.mip_bias);
+    }
+#endif
+
     pbr_input.material.flags = pbr_bindings::material.flags;
+#ifndef PREPASS_PIPELINE
+    // Only fog meshes that share a layer with `FogSettings::render_layers`
+    if ((mesh[in.instance_index].render_layers & bevy_pbr::mesh_view_bindings::fog.render_layers) == 0u) {
+        pbr_input.material.flags &= ~pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT;
+    }
+#endif
 
     // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
         pbr_input.material.reflectance = pbr_bindings::material.reflectance;
+#ifdef VERTEX_UVS
+#ifdef STANDARD_MATERIAL_REFLECTANCE_TEXTURE
+        pbr_input.material.reflectance *= textureSampleBias(pbr_bindings::reflectance_texture, pbr_bindings::reflectance_sampler, uv, view.mip_bias).r;
+#endif
+#endif
         pbr_input.material.ior = pbr_bindings::material.ior;
         pbr_input.material.attenuation_color = pbr_bindings::material.attenuation_color;
         pbr_input.material.attenuation_distance = pbr_bindings::material.attenuation_distance;
-        pbr_input.material.alpha_cutoff = pbr_bindings::material.alpha_cutoff;
 
         // emissive
         // TODO use .a for exposure compensation in HDR
         var emissive: vec4<f32> = pbr_bindings::material.emissive;
 #ifdef VERTEX_UVS
         if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_EMISSIVE_TEXTURE_BIT) != 0u) {
-            emissive = vec4<f32>(emissive.rgb * textureSampleBias(pbr_bindings::emissive_texture, pbr_bindings::emissive_sampler, uv, view.mip_bias).rgb, 1.0);
+#ifdef PREPASS_PIPELINE
+            let time = bevy_pbr::prepass_bindings::globals.time;
+#else
+            let time = bevy_pbr::mesh_view_bindings::globals.time;
+#endif
+            let emissive_uv = uv + time * pbr_bindings::material.emissive_uv_speed;
+            emissive = vec4<f32>(emissive.rgb * textureSampleBias(pbr_bindings::emissive_texture, pbr_bindings::emissive_sampler, emissive_uv, view.mip_bias).rgb, 1.0);
         }
 #endif
+        emissive = vec4<f32>(emissive.rgb * pbr_bindings::material.emissive_intensity, emissive.a);
         pbr_input.material.emissive = emissive;
 
         // metallic and perceptual roughness
@@ -135,6 +204,26 @@ fn pbr_input_from_standard_material(
             perceptual_roughness *= metallic_roughness.g;
         }
 #endif
+
+        if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_SPECULAR_GLOSSINESS_BIT) != 0u) {
+            var specular = pbr_bindings::material.specular_color.rgb;
+            var glossiness = pbr_bindings::material.glossiness;
+#ifdef VERTEX_UVS
+#ifdef PBR_SPECULAR_GLOSSINESS_TEXTURES_SUPPORTED
+            if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_SPECULAR_GLOSSINESS_TEXTURE_BIT) != 0u) {
+                let specular_glossiness = textureSampleBias(pbr_bindings::specular_glossiness_texture, pbr_bindings::specular_glossiness_sampler, uv, view.mip_bias);
+                specular *= specular_glossiness.rgb;
+                glossiness *= specular_glossiness.a;
+            }
+#endif
+#endif
+            // The base color holds the diffuse color in this workflow.
+            let base_color_metallic = specular_glossiness_to_metallic(pbr_input.material.base_color.rgb, specular);
+            pbr_input.material.base_color = vec4(base_color_metallic.rgb, pbr_input.material.base_color.a);
+            metallic = base_color_metallic.a;
+            perceptual_roughness = 1.0 - glossiness;
+        }
+
         pbr_input.material.metallic = metallic;
         pbr_input.material.perceptual_roughness = perceptual_roughness;
 
@@ -184,6 +273,10 @@ fn pbr_input_from_standard_material(
         pbr_input.diffuse_occlusion = diffuse_occlusion;
         pbr_input.specular_occlusion = specular_occlusion;
 
+        if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_OCCLUDE_EMISSIVE_BIT) != 0u) {
+            pbr_input.material.emissive = vec4(pbr_input.material.emissive.rgb * diffuse_occlusion, pbr_input.material.emissive.a);
+        }
+
         // N (normal vector)
 #ifndef LOAD_PREPASS_NORMALS
         pbr_input.N = pbr_functions::apply_normal_mapping(
@@ -203,6 +296,13 @@ fn pbr_input_from_standard_material(
         );
 #endif
 
+        if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_SPECULAR_ANTIALIASING_BIT) != 0u) {
+            pbr_input.material.perceptual_roughness = pbr_functions::geometric_specular_antialiasing(
+                pbr_input.material.perceptual_roughness,
+                pbr_input.N,
+            );
+        }
+
 #ifdef LIGHTMAP
         pbr_input.lightmap_light = lightmap(
             in.uv_b,
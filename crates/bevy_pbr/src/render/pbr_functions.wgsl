@@ -19,6 +19,10 @@
 #import bevy_pbr::environment_map
 #endif
 
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+#import bevy_pbr::parallax_mapping
+#endif
+
 #import bevy_core_pipeline::tonemapping::{screen_space_dither, powsafe, tone_mapping}
 
 fn alpha_discard(material: pbr_types::StandardMaterial, output_color: vec4<f32>) -> vec4<f32> {
@@ -41,6 +45,14 @@ fn alpha_discard(material: pbr_types::StandardMaterial, output_color: vec4<f32>)
     }
 #endif
 
+#ifdef DEFERRED_OPAQUE_SPLIT
+    // The fragments at or above the cutoff were written to the G-buffer and lit by the deferred
+    // lighting pass, only the rest is blended here.
+    if color.a >= material.alpha_cutoff {
+        discard;
+    }
+#endif
+
     return color;
 }
 
@@ -60,6 +72,13 @@ fn prepare_world_normal(
     return output;
 }
 
+// Returns the normal of the face containing the fragment at `world_position`, derived from its
+// screen-space derivatives and oriented to the same side as the interpolated `world_normal`.
+fn flat_world_normal(world_position: vec3<f32>, world_normal: vec3<f32>) -> vec3<f32> {
+    let face_normal = normalize(cross(dpdy(world_position), dpdx(world_position)));
+    return select(face_normal, -face_normal, dot(face_normal, world_normal) < 0.0);
+}
+
 fn apply_normal_mapping(
     standard_material_flags: u32,
     world_normal: vec3<f32>,
@@ -128,6 +147,38 @@ fn apply_normal_mapping(
     return normalize(N);
 }
 
+// Geometric specular anti-aliasing: widens the specular lobe by the screen-space variance of the
+// normal so that sub-pixel highlights don't flicker. Must only be called from fragment shaders.
+// See Tokuyoshi and Kaplanyan 2019, "Improved Geometric Specular Antialiasing", and
+// https://google.github.io/filament/Filament.html#toc5.5.1
+fn geometric_specular_antialiasing(perceptual_roughness: f32, N: vec3<f32>) -> f32 {
+    let du = dpdx(N);
+    let dv = dpdy(N);
+    // Screen-space variance of the normal (sigma^2), clamped so the kernel can't wash
+    // highlights out completely.
+    let variance = 0.25 * (dot(du, du) + dot(dv, dv));
+    let kernel_roughness = min(2.0 * variance, 0.18);
+    let roughness = perceptual_roughness * perceptual_roughness;
+    let squared_roughness = saturate(roughness * roughness + kernel_roughness);
+    return sqrt(sqrt(squared_roughness));
+}
+
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+// Returns how much of the direct light arriving from `L`, the direction from the fragment to the
+// light, isn't shadowed by the depth map of the material.
+fn parallax_self_shadow(in: pbr_types::PbrInput, L: vec3<f32>) -> f32 {
+    if ((in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT) == 0u) {
+        return 1.0;
+    }
+    return parallax_mapping::standard_material_parallax_self_shadow(
+        in.parallax_uv,
+        in.parallax_tangent_frame,
+        in.material.parallax_depth_scale,
+        L,
+    );
+}
+#endif // STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+
 // NOTE: Correctly calculates the view vector depending on whether
 // the projection is orthographic or perspective.
 fn calculate_view(
@@ -157,7 +208,10 @@ fn apply_pbr_lighting(
     // calculate non-linear roughness from linear perceptualRoughness
     let metallic = in.material.metallic;
     let perceptual_roughness = in.material.perceptual_roughness;
-    let roughness = lighting::perceptualRoughnessToRoughness(perceptual_roughness);
+    var roughness = lighting::perceptualRoughnessToRoughness(perceptual_roughness);
+    if ((in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_UNCLAMPED_ROUGHNESS_BIT) != 0u) {
+        roughness = lighting::perceptualRoughnessToRoughnessUnclamped(perceptual_roughness);
+    }
     let ior = in.material.ior;
     let thickness = in.material.thickness;
     let diffuse_transmission = in.material.diffuse_transmission;
@@ -174,7 +228,12 @@ fn apply_pbr_lighting(
     // Remapping [0,1] reflectance to F0
     // See https://google.github.io/filament/Filament.html#materialsystem/parameterization/remapping
     let reflectance = in.material.reflectance;
-    let F0 = 0.16 * reflectance * reflectance * (1.0 - metallic) + output_color.rgb * metallic;
+    var F0 = 0.16 * reflectance * reflectance * (1.0 - metallic) + output_color.rgb * metallic;
+    // With `F0 = 0`, the fresnel term and the specular occlusion derived from it vanish, which
+    // removes the specular lobe of all direct and indirect lights.
+    if ((in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_NO_SPECULAR_BIT) != 0u) {
+        F0 = vec3<f32>(0.0);
+    }
 
     // Diffuse strength is inversely related to metallicity, specular and diffuse transmission
     let diffuse_color = output_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission) * (1.0 - diffuse_transmission);
@@ -183,6 +242,8 @@ fn apply_pbr_lighting(
     let diffuse_transmissive_color = output_color.rgb * (1.0 - metallic) * (1.0 - specular_transmission) * diffuse_transmission;
 
     // Calculate the world position of the second Lambertian lobe used for diffuse transmission, by subtracting material thickness
+    // NOTE: For double-sided materials, `in.N` and `in.world_normal` were already flipped towards the viewer on back faces
+    // (see `prepare_world_normal`), so the lobe always lies behind the visible side of the surface.
     let diffuse_transmissive_lobe_world_position = in.world_position - vec4<f32>(in.world_normal, 0.0) * thickness;
 
     let R = reflect(-in.V, in.N);
@@ -200,17 +261,20 @@ fn apply_pbr_lighting(
         view_bindings::view.inverse_view[2].z,
         view_bindings::view.inverse_view[3].z
     ), in.world_position);
-    let cluster_index = clustering::fragment_cluster_index(in.frag_coord.xy, view_z, in.is_orthographic);
-    let offset_and_counts = clustering::unpack_offset_and_counts(cluster_index);
+    let cluster_index = clustering::view_fragment_cluster_index(in.frag_coord.xy, in.world_position, in.is_orthographic);
+    let light_ranges = clustering::cluster_light_ranges(cluster_index);
 
     // Point lights (direct)
-    for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
+    for (var i: u32 = light_ranges.point_light_start; i < light_ranges.spot_light_start; i = i + 1u) {
         let light_id = clustering::get_light_id(i);
         var shadow: f32 = 1.0;
         if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_point_shadow(light_id, in.world_position, in.world_normal);
         }
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+        shadow *= parallax_self_shadow(in, normalize(view_bindings::point_lights.data[light_id].position_radius.xyz - in.world_position.xyz));
+#endif
         let light_contrib = lighting::point_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
 
@@ -235,7 +299,7 @@ fn apply_pbr_lighting(
     }
 
     // Spot lights (direct)
-    for (var i: u32 = offset_and_counts[0] + offset_and_counts[1]; i < offset_and_counts[0] + offset_and_counts[1] + offset_and_counts[2]; i = i + 1u) {
+    for (var i: u32 = light_ranges.spot_light_start; i < light_ranges.spot_light_end; i = i + 1u) {
         let light_id = clustering::get_light_id(i);
 
         var shadow: f32 = 1.0;
@@ -243,6 +307,9 @@ fn apply_pbr_lighting(
                 && (view_bindings::point_lights.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_spot_shadow(light_id, in.world_position, in.world_normal);
         }
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+        shadow *= parallax_self_shadow(in, normalize(view_bindings::point_lights.data[light_id].position_radius.xyz - in.world_position.xyz));
+#endif
         let light_contrib = lighting::spot_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
 
@@ -281,7 +348,11 @@ fn apply_pbr_lighting(
                 && (view_bindings::lights.directional_lights[i].flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
         }
-        var light_contrib = lighting::directional_light(i, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+        shadow *= parallax_self_shadow(in, (*light).direction_to_light.xyz);
+#endif
+        let cascade_intensity = shadows::directional_cascade_intensity(i, view_z);
+        var light_contrib = lighting::directional_light(i, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color) * cascade_intensity;
 #ifdef DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES
         light_contrib = shadows::cascade_debug_visualization(light_contrib, i, view_z);
 #endif
@@ -303,7 +374,7 @@ fn apply_pbr_lighting(
             transmitted_shadow = shadows::fetch_directional_shadow(i, diffuse_transmissive_lobe_world_position, -in.world_normal, view_z);
         }
         let transmitted_light_contrib = lighting::directional_light(i, 1.0, 1.0, -in.N, -in.V, vec3<f32>(0.0), vec3<f32>(0.0), vec2<f32>(0.1), diffuse_transmissive_color);
-        transmitted_light += transmitted_light_contrib * transmitted_shadow;
+        transmitted_light += transmitted_light_contrib * transmitted_shadow * cascade_intensity;
 #endif
     }
 
@@ -340,7 +411,8 @@ fn apply_pbr_lighting(
 
 #ifdef IRRADIANCE_VOLUME {
     // Irradiance volume light (indirect)
-    if (all(indirect_light == vec3(0.0f))) {
+    if (all(indirect_light == vec3(0.0f)) &&
+            (in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT) == 0u) {
         let irradiance_volume_light = irradiance_volume::irradiance_volume_light(
             in.world_position.xyz, in.N);
         indirect_light += irradiance_volume_light * diffuse_color * diffuse_occlusion;
@@ -364,8 +436,12 @@ fn apply_pbr_lighting(
         in.world_position.xyz,
         any(indirect_light != vec3(0.0f)));
 
-    indirect_light += environment_light.diffuse * diffuse_occlusion +
-        environment_light.specular * specular_occlusion;
+    if ((in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT) == 0u) {
+        indirect_light += environment_light.diffuse * diffuse_occlusion;
+    }
+    if ((in.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_NO_ENV_SPECULAR_BIT) == 0u) {
+        indirect_light += environment_light.specular * specular_occlusion * in.material.reflectance_env_scale;
+    }
 
     // we'll use the specular component of the transmitted environment
     // light in the call to `specular_transmissive_light()` below
@@ -417,7 +493,7 @@ fn apply_pbr_lighting(
     // Ambient light (indirect)
     indirect_light += ambient::ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, diffuse_occlusion);
 
-    let emissive_light = emissive.rgb * output_color.a;
+    var emissive_light = emissive.rgb * output_color.a;
 
 #ifdef STANDARD_MATERIAL_SPECULAR_TRANSMISSION
     transmitted_light += transmission::specular_transmissive_light(in.world_position, in.frag_coord.xyz, view_z, in.N, in.V, F0, ior, thickness, perceptual_roughness, specular_transmissive_color, specular_transmitted_environment_light).rgb;
@@ -438,6 +514,17 @@ fn apply_pbr_lighting(
     }
 #endif
 
+#ifdef PBR_DEBUG_DIRECT_LIGHT
+    transmitted_light = vec3(0.0);
+    indirect_light = vec3(0.0);
+    emissive_light = vec3(0.0);
+#endif
+#ifdef PBR_DEBUG_INDIRECT_LIGHT
+    transmitted_light = vec3(0.0);
+    direct_light = vec3(0.0);
+    emissive_light = vec3(0.0);
+#endif
+
     // Total light
     output_color = vec4<f32>(
         view_bindings::view.exposure * (transmitted_light + direct_light + indirect_light + emissive_light),
@@ -448,7 +535,7 @@ fn apply_pbr_lighting(
         output_color,
         view_z,
         in.is_orthographic,
-        offset_and_counts,
+        clustering::unpack_offset_and_counts(cluster_index),
         cluster_index,
     );
 
@@ -465,6 +552,18 @@ fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_
     // fog shape that looks a bit fake
     let distance = length(view_to_world);
 
+    var fog = fog_params;
+#ifdef FOG_COLOR_GRADIENT
+    // Tint the fog color by `FogSettings::color_gradient`
+    let gradient_uv = vec2<f32>(saturate(distance / fog_params.color_gradient_distance), 0.5);
+    fog.base_color *= textureSampleLevel(
+        view_bindings::fog_color_gradient,
+        view_bindings::fog_color_gradient_sampler,
+        gradient_uv,
+        0.0
+    );
+#endif // FOG_COLOR_GRADIENT
+
     var scattering = vec3<f32>(0.0);
     if fog_params.directional_light_color.a > 0.0 {
         let view_to_world_normalized = view_to_world / distance;
@@ -482,13 +581,13 @@ fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_
     }
 
     if fog_params.mode == mesh_view_types::FOG_MODE_LINEAR {
-        return bevy_pbr::fog::linear_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::linear_fog(fog, input_color, distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL {
-        return bevy_pbr::fog::exponential_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::exponential_fog(fog, input_color, distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL_SQUARED {
-        return bevy_pbr::fog::exponential_squared_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::exponential_squared_fog(fog, input_color, distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_ATMOSPHERIC {
-        return bevy_pbr::fog::atmospheric_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::atmospheric_fog(fog, input_color, distance, scattering);
     } else {
         return input_color;
     }
@@ -556,7 +655,21 @@ fn main_pass_post_lighting_processing(
 
     // fog
     if (view_bindings::fog.mode != mesh_view_types::FOG_MODE_OFF && (pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) {
-        output_color = apply_fog(view_bindings::fog, output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz);
+        var fog_world_position = pbr_input.world_position.xyz;
+#ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
+        // Thicken the fog in occluded areas by pushing the fragment further away from the view.
+        if (view_bindings::fog.ambient_occlusion_density > 0.0) {
+            let ssao = textureLoad(view_bindings::screen_space_ambient_occlusion_texture, vec2<i32>(pbr_input.frag_coord.xy), 0i).r;
+            let distance_scale = 1.0 + view_bindings::fog.ambient_occlusion_density * (1.0 - ssao);
+            fog_world_position = mix(view_bindings::view.world_position.xyz, fog_world_position, distance_scale);
+        }
+#endif
+        let fogged_color = apply_fog(view_bindings::fog, output_color, fog_world_position, view_bindings::view.world_position.xyz);
+#ifdef STANDARD_MATERIAL_FOG_INTENSITY
+        output_color = mix(output_color, fogged_color, pbr_input.material.fog_intensity);
+#else
+        output_color = fogged_color;
+#endif
     }
 
 #ifdef TONEMAP_IN_SHADER
@@ -2,7 +2,13 @@
 
 #import bevy_pbr::{
     utils::PI,
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{
+        POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+        POINT_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT,
+        POINT_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT,
+        DIRECTIONAL_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT,
+        DIRECTIONAL_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT,
+    },
     mesh_view_bindings as view_bindings,
 }
 
@@ -174,6 +180,13 @@ fn perceptualRoughnessToRoughness(perceptualRoughness: f32) -> f32 {
     return clampedPerceptualRoughness * clampedPerceptualRoughness;
 }
 
+// Like `perceptualRoughnessToRoughness`, for materials that opted out of the clamp. Only keeps
+// the roughness above zero, where the GGX distribution would divide by zero.
+fn perceptualRoughnessToRoughnessUnclamped(perceptualRoughness: f32) -> f32 {
+    let saturatedPerceptualRoughness = saturate(perceptualRoughness);
+    return max(saturatedPerceptualRoughness * saturatedPerceptualRoughness, 1e-4);
+}
+
 fn point_light(
     world_position: vec3<f32>,
     light_id: u32,
@@ -189,7 +202,13 @@ fn point_light(
     let light = &view_bindings::point_lights.data[light_id];
     let light_to_frag = (*light).position_radius.xyz - world_position.xyz;
     let distance_square = dot(light_to_frag, light_to_frag);
-    let rangeAttenuation = getDistanceAttenuation(distance_square, (*light).color_inverse_square_range.w);
+    var rangeAttenuation = getDistanceAttenuation(distance_square, (*light).color_inverse_square_range.w);
+    let range_smoothing = f32(((*light).flags >> 8u) & 0xffu) / 255.0;
+    if range_smoothing > 0.0 {
+        // Fade out over the last `range_smoothing` fraction of the range.
+        let range_fraction = sqrt(distance_square * (*light).color_inverse_square_range.w);
+        rangeAttenuation *= 1.0 - smoothstep(1.0 - range_smoothing, 1.0, range_fraction);
+    }
 
     // Specular.
     // Representative Point Area Lights.
@@ -207,7 +226,10 @@ fn point_light(
     var NoH: f32 = saturate(dot(N, H));
     var LoH: f32 = saturate(dot(L, H));
 
-    let specular_light = specular(F0, roughness, H, NdotV, NoL, NoH, LoH, specularIntensity, f_ab);
+    var specular_light = specular(F0, roughness, H, NdotV, NoL, NoH, LoH, specularIntensity, f_ab);
+    if ((*light).flags & POINT_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT) == 0u {
+        specular_light = vec3(0.0);
+    }
 
     // Diffuse.
     // Comes after specular since its NoL is used in the lighting equation.
@@ -217,7 +239,10 @@ fn point_light(
     NoH = saturate(dot(N, H));
     LoH = saturate(dot(L, H));
 
-    let diffuse = diffuseColor * Fd_Burley(roughness, NdotV, NoL, LoH);
+    var diffuse = diffuseColor * Fd_Burley(roughness, NdotV, NoL, LoH);
+    if ((*light).flags & POINT_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT) == 0u {
+        diffuse = vec3(0.0);
+    }
 
     // See https://google.github.io/filament/Filament.html#mjx-eqn-pointLightLuminanceEquation
     // Lout = f(v,l) Φ / { 4 π d^2 }⟨n⋅l⟩
@@ -261,11 +286,48 @@ fn spot_light(
     let light_to_frag = (*light).position_radius.xyz - world_position.xyz;
 
     // calculate attenuation based on filament formula https://google.github.io/filament/Filament.html#listing_glslpunctuallight
-    // spot_scale and spot_offset have been precomputed
+    // spot_scale has been precomputed, spot_offset is derived from the tangent of the outer angle
     // note we normalize here to get "l" from the filament listing. spot_dir is already normalized
+    let tan_outer = (*light).light_custom_data.w;
+    let spot_scale = (*light).light_custom_data.z;
+    let spot_offset = -inverseSqrt(1.0 + tan_outer * tan_outer) * spot_scale;
     let cd = dot(-spot_dir, normalize(light_to_frag));
-    let attenuation = saturate(cd * (*light).light_custom_data.z + (*light).light_custom_data.w);
-    let spot_attenuation = attenuation * attenuation;
+    let attenuation = saturate(cd * spot_scale + spot_offset);
+    var spot_attenuation = attenuation * attenuation;
+
+    // Cut the cone with the barn doors, if any.
+    let barn_doors = (*light).spot_light_barn_doors;
+    if (barn_doors != 0u) {
+        // the construction of the up and right vectors needs to precisely mirror the code
+        // in render/light.rs:spot_light_view_matrix
+        let fwd = -spot_dir;
+        var sign = -1.0;
+        if (fwd.z >= 0.0) {
+            sign = 1.0;
+        }
+        let a = -1.0 / (fwd.z + sign);
+        let b = fwd.x * fwd.y * a;
+        let up_dir = vec3<f32>(1.0 + sign * fwd.x * fwd.x * a, sign * b, -sign * fwd.x);
+        let right_dir = vec3<f32>(-b, -sign - fwd.y * fwd.y * a, fwd.y);
+
+        // Position of the fragment in the light's local space, with the roll of the light applied.
+        let view_xy = vec2(dot(-light_to_frag, right_dir), dot(-light_to_frag, up_dir));
+        let roll = f32((*light).flags >> 16u) / 65535.0 * 6.283185307179586;
+        let local_xy = vec2(
+            cos(roll) * view_xy.x + sin(roll) * view_xy.y,
+            -sin(roll) * view_xy.x + cos(roll) * view_xy.y,
+        );
+        let tan_xy = local_xy / max(dot(light_to_frag, fwd), 0.0001);
+
+        // Each door limits the tangent of the angle to the spot direction on its side.
+        let outer_angle = atan(tan_outer);
+        let cuts = unpack4x8unorm(barn_doors);
+        let tan_limits = tan(outer_angle * (1.0 - cuts));
+        if (-tan_xy.x > tan_limits.x || tan_xy.x > tan_limits.y ||
+                -tan_xy.y > tan_limits.z || tan_xy.y > tan_limits.w) {
+            spot_attenuation = 0.0;
+        }
+    }
 
     return point_light * spot_attenuation;
 }
@@ -280,9 +342,15 @@ fn directional_light(light_id: u32, roughness: f32, NdotV: f32, normal: vec3<f32
     let NoH = saturate(dot(normal, half_vector));
     let LoH = saturate(dot(incident_light, half_vector));
 
-    let diffuse = diffuseColor * Fd_Burley(roughness, NdotV, NoL, LoH);
+    var diffuse = diffuseColor * Fd_Burley(roughness, NdotV, NoL, LoH);
+    if ((*light).flags & DIRECTIONAL_LIGHT_FLAGS_AFFECTS_DIFFUSE_BIT) == 0u {
+        diffuse = vec3(0.0);
+    }
     let specularIntensity = 1.0;
-    let specular_light = specular(F0, roughness, half_vector, NdotV, NoL, NoH, LoH, specularIntensity, f_ab);
+    var specular_light = specular(F0, roughness, half_vector, NdotV, NoL, NoH, LoH, specularIntensity, f_ab);
+    if ((*light).flags & DIRECTIONAL_LIGHT_FLAGS_AFFECTS_SPECULAR_BIT) == 0u {
+        specular_light = vec3(0.0);
+    }
 
     return (specular_light + diffuse) * (*light).color.rgb * NoL;
 }
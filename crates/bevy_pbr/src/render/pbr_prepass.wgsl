@@ -3,6 +3,7 @@
     pbr_bindings::material,
     pbr_types,
     pbr_functions,
+    mesh_functions,
     prepass_io,
     mesh_view_bindings::view,
 }
@@ -26,8 +27,14 @@ fn fragment(
     if (material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u {
         let double_sided = (material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT) != 0u;
 
+#ifdef STANDARD_MATERIAL_FLAT_SHADING
+        let vertex_normal = pbr_functions::flat_world_normal(in.world_position.xyz, in.world_normal);
+#else
+        let vertex_normal = in.world_normal;
+#endif
+
         let world_normal = pbr_functions::prepare_world_normal(
-            in.world_normal,
+            vertex_normal,
             double_sided,
             is_front,
         );
@@ -43,14 +50,14 @@ fn fragment(
 #endif // STANDARD_MATERIAL_NORMAL_MAP
 #endif // VERTEX_TANGENTS
 #ifdef VERTEX_UVS
-            in.uv,
+            pbr_prepass_functions::prepass_uv(in),
 #endif // VERTEX_UVS
             view.mip_bias,
         );
 
-        out.normal = vec4(normal * 0.5 + vec3(0.5), 1.0);
+        out.normal = vec4(normal * 0.5 + vec3(0.5), mesh_functions::prepass_normal_alpha(in.instance_index));
     } else {
-        out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), 1.0);
+        out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), mesh_functions::prepass_normal_alpha(in.instance_index));
     }
 #endif // NORMAL_PREPASS
 
@@ -6,29 +6,88 @@
     mesh_view_bindings::view,
     pbr_bindings,
     pbr_types,
+    pbr_functions,
+    mesh_bindings::mesh,
+    mesh_types,
+    parallax_mapping::{standard_material_parallaxed_uv, derivative_tangent_frame},
 }
 #import bevy_render::maths::affine2_to_square
 
 // Cutoff used for the premultiplied alpha modes BLEND and ADD.
 const PREMULTIPLIED_ALPHA_CUTOFF = 0.05;
 
+#ifdef VERTEX_UVS
+// Returns the UV used to sample the material in the prepass. With `STANDARD_MATERIAL_PARALLAX_PREPASS`,
+// the parallax mapping of the main pass is applied too, as seen from the view of this prepass.
+// Only the texture lookups are displaced, the depth written is still the one of the mesh.
+fn prepass_uv(in: VertexOutput) -> vec2<f32> {
+    let uv_transform = affine2_to_square(pbr_bindings::material.uv_transform);
+    var uv = (uv_transform * vec3(in.uv, 1.0)).xy;
+
+#ifdef STANDARD_MATERIAL_PARALLAX_PREPASS
+    if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT) != 0u) {
+        let V = pbr_functions::calculate_view(in.world_position, view.projection[3].w == 1.0);
+#ifdef VERTEX_TANGENTS
+        let N = in.world_normal;
+        let T = in.world_tangent.xyz;
+        let B = in.world_tangent.w * cross(N, T);
+#else
+        // Shadow and depth-only passes don't have the vertex normals and tangents.
+        let face_normal = normalize(cross(dpdy(in.world_position.xyz), dpdx(in.world_position.xyz)));
+        let N = select(face_normal, -face_normal, dot(face_normal, V) < 0.0);
+        let tangent_frame = derivative_tangent_frame(in.world_position.xyz, N, uv);
+        let T = tangent_frame[0];
+        let B = tangent_frame[1];
+#endif // VERTEX_TANGENTS
+        uv = standard_material_parallaxed_uv(uv, V, N, T, B, in.instance_index);
+    }
+#endif // STANDARD_MATERIAL_PARALLAX_PREPASS
+
+    return uv;
+}
+#endif // VERTEX_UVS
+
 // We can use a simplified version of alpha_discard() here since we only need to handle the alpha_cutoff
 fn prepass_alpha_discard(in: VertexOutput) {
 
 #ifdef MAY_DISCARD
     var output_color: vec4<f32> = pbr_bindings::material.base_color;
 
-#ifdef VERTEX_UVS
-    let uv_transform = affine2_to_square(pbr_bindings::material.uv_transform);
-    let uv = (uv_transform * vec3(in.uv, 1.0)).xy;
+#ifdef VERTEX_COLORS
+    // Match the main pass, which multiplies the base color by the vertex color, so that the
+    // cutout of alpha-masked shadow casters lines up with what is rendered.
+    output_color = output_color * in.color;
+#endif // VERTEX_COLORS
+
+#ifdef STANDARD_MATERIAL_WORLD_UV
+    // Match the main pass, which projects the base color from world space.
+    if (pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
+        let world_uv = (affine2_to_square(pbr_bindings::material.uv_transform) * vec3(in.world_position.xz * pbr_bindings::material.world_uv_scale, 1.0)).xy;
+        output_color = output_color * textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, world_uv, view.mip_bias);
+    }
+#else ifdef VERTEX_UVS
+    let uv = prepass_uv(in);
     if (pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
         output_color = output_color * textureSampleBias(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, uv, view.mip_bias);
     }
-#endif // VERTEX_UVS
+#endif
+
+    var alpha_cutoff = pbr_bindings::material.alpha_cutoff;
+    if ((mesh[in.instance_index].flags & mesh_types::MESH_FLAGS_ALPHA_MASK_THRESHOLD_BIT) != 0u) {
+        alpha_cutoff = mesh[in.instance_index].alpha_mask_threshold;
+    }
+
+#ifdef DEFERRED_OPAQUE_SPLIT
+    // Only the fragments at or above the cutoff are written to the G-buffer, the rest is blended
+    // in the forward transparent pass.
+    if output_color.a < alpha_cutoff {
+        discard;
+    }
+#endif
 
     let alpha_mode = pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_RESERVED_BITS;
     if alpha_mode == pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK {
-        if output_color.a < pbr_bindings::material.alpha_cutoff {
+        if output_color.a < alpha_cutoff {
             discard;
         }
     } else if (alpha_mode == pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_BLEND || alpha_mode == pbr_types::STANDARD_MATERIAL_FLAGS_ALPHA_MODE_ADD) {
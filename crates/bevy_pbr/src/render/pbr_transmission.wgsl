@@ -16,13 +16,17 @@ fn specular_transmissive_light(world_position: vec4<f32>, frag_coord: vec3<f32>,
     // Calculate the ratio between refaction indexes. Assume air/vacuum for the space outside the mesh
     let eta = 1.0 / ior;
 
-    // Calculate incidence vector (opposite to view vector) and its dot product with the mesh normal
+    // Calculate incidence vector (opposite to view vector) and its dot product with the mesh normal.
+    // `N` is the shading normal, including any normal map, so surface detail bends the refracted ray.
+    // Unlike the geometric normal, a normal-mapped one can face away from the viewer; clamp so the
+    // ray still enters the surface instead of refracting backwards.
     let I = -V;
-    let NdotI = dot(N, I);
+    let NdotI = min(dot(N, I), 0.0);
 
-    // Calculate refracted direction using Snell's law
+    // Calculate refracted direction using Snell's law, treating total internal reflection (only
+    // possible for an `ior` below 1) as grazing refraction rather than producing NaNs
     let k = 1.0 - eta * eta * (1.0 - NdotI * NdotI);
-    let T = eta * I - (eta * NdotI + sqrt(k)) * N;
+    let T = eta * I - (eta * NdotI + sqrt(max(k, 0.0))) * N;
 
     // Calculate the exit position of the refracted ray, by propagating refacted direction through thickness
     let exit_position = world_position.xyz + T * thickness;
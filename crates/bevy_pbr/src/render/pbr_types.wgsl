@@ -24,6 +24,13 @@ struct StandardMaterial {
     max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     deferred_lighting_pass_id: u32,
+    emissive_uv_speed: vec2<f32>,
+    fog_intensity: f32,
+    reflectance_env_scale: f32,
+    emissive_intensity: f32,
+    world_uv_scale: f32,
+    specular_color: vec4<f32>,
+    glossiness: f32,
 };
 
 // !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
@@ -44,6 +51,14 @@ const STANDARD_MATERIAL_FLAGS_SPECULAR_TRANSMISSION_TEXTURE_BIT: u32 = 1024u;
 const STANDARD_MATERIAL_FLAGS_THICKNESS_TEXTURE_BIT: u32          = 2048u;
 const STANDARD_MATERIAL_FLAGS_DIFFUSE_TRANSMISSION_TEXTURE_BIT: u32 = 4096u;
 const STANDARD_MATERIAL_FLAGS_ATTENUATION_ENABLED_BIT: u32        = 8192u;
+const STANDARD_MATERIAL_FLAGS_OCCLUDE_EMISSIVE_BIT: u32           = 16384u;
+const STANDARD_MATERIAL_FLAGS_SPECULAR_GLOSSINESS_BIT: u32        = 32768u;
+const STANDARD_MATERIAL_FLAGS_SPECULAR_GLOSSINESS_TEXTURE_BIT: u32 = 65536u;
+const STANDARD_MATERIAL_FLAGS_UNCLAMPED_ROUGHNESS_BIT: u32        = 131072u;
+const STANDARD_MATERIAL_FLAGS_SPECULAR_ANTIALIASING_BIT: u32      = 262144u;
+const STANDARD_MATERIAL_FLAGS_NO_ENV_DIFFUSE_BIT: u32             = 524288u;
+const STANDARD_MATERIAL_FLAGS_NO_ENV_SPECULAR_BIT: u32            = 1048576u;
+const STANDARD_MATERIAL_FLAGS_NO_SPECULAR_BIT: u32                = 2097152u;
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_RESERVED_BITS: u32       = 3758096384u; // (0b111u32 << 29)
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE: u32              = 0u;          // (0u32 << 29)
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK: u32                = 536870912u;  // (1u32 << 29)
@@ -77,6 +92,12 @@ fn standard_material_new() -> StandardMaterial {
     material.max_parallax_layer_count = 16.0;
     material.max_relief_mapping_search_steps = 5u;
     material.deferred_lighting_pass_id = 1u;
+    material.fog_intensity = 1.0;
+    material.reflectance_env_scale = 1.0;
+    material.emissive_intensity = 1.0;
+    material.world_uv_scale = 1.0;
+    material.specular_color = vec4<f32>(0.04, 0.04, 0.04, 1.0);
+    material.glossiness = 0.5;
     // scale 1, translation 0, rotation 0
     material.uv_transform = mat3x2<f32>(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
 
@@ -102,6 +123,12 @@ struct PbrInput {
     lightmap_light: vec3<f32>,
     is_orthographic: bool,
     flags: u32,
+#ifdef STANDARD_MATERIAL_PARALLAX_SELF_SHADOW
+    // The parallax mapped uv and the tangent frame (T, B, N) of the surface, used to self-shadow
+    // the depth map
+    parallax_uv: vec2<f32>,
+    parallax_tangent_frame: mat3x3<f32>,
+#endif
 };
 
 // Creates a PbrInput with default values
@@ -7,19 +7,25 @@
 }
 
 // Do the lookup, using HW 2x2 PCF and comparison
-fn sample_shadow_map_hardware(light_local: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+//
+// `uv_rect` is the extent (min xy, max zw) of the shadow map within its layer: cascades smaller
+// than the layer share it with other cascades, and the lookup is clamped so the 2x2 footprint
+// never reads the texels of a neighboring cascade.
+fn sample_shadow_map_hardware(light_local: vec2<f32>, depth: f32, array_index: i32, uv_rect: vec4<f32>) -> f32 {
+    let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
+    let uv = clamp(light_local, uv_rect.xy + 0.5 / shadow_map_size, uv_rect.zw - 0.5 / shadow_map_size);
 #ifdef NO_ARRAY_TEXTURES_SUPPORT
     return textureSampleCompare(
         view_bindings::directional_shadow_textures,
         view_bindings::directional_shadow_textures_sampler,
-        light_local,
+        uv,
         depth,
     );
 #else
     return textureSampleCompareLevel(
         view_bindings::directional_shadow_textures,
         view_bindings::directional_shadow_textures_sampler,
-        light_local,
+        uv,
         array_index,
         depth,
     );
@@ -27,7 +33,7 @@ fn sample_shadow_map_hardware(light_local: vec2<f32>, depth: f32, array_index: i
 }
 
 // https://web.archive.org/web/20230210095515/http://the-witness.net/news/2013/09/shadow-mapping-summary-part-1
-fn sample_shadow_map_castano_thirteen(light_local: vec2<f32>, depth: f32, array_index: i32) -> f32 {
+fn sample_shadow_map_castano_thirteen(light_local: vec2<f32>, depth: f32, array_index: i32, uv_rect: vec4<f32>) -> f32 {
     let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
     let inv_shadow_map_size = 1.0 / shadow_map_size;
 
@@ -38,6 +44,56 @@ fn sample_shadow_map_castano_thirteen(light_local: vec2<f32>, depth: f32, array_
     base_uv -= 0.5;
     base_uv *= inv_shadow_map_size;
 
+#ifdef SHADOW_FILTER_QUALITY_LOW
+    // 3x3 kernel, 4 samples
+    let uw0 = (3.0 - 2.0 * s);
+    let uw1 = (1.0 + 2.0 * s);
+
+    let u0 = (2.0 - s) / uw0 - 1.0;
+    let u1 = s / uw1 + 1.0;
+
+    let vw0 = (3.0 - 2.0 * t);
+    let vw1 = (1.0 + 2.0 * t);
+
+    let v0 = (2.0 - t) / vw0 - 1.0;
+    let v1 = t / vw1 + 1.0;
+
+    var sum = 0.0;
+
+    sum += uw0 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u0, v0) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw1 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u1, v0) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw0 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u0, v1) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw1 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u1, v1) * inv_shadow_map_size), depth, array_index, uv_rect);
+
+    return sum * (1.0 / 16.0);
+#else ifdef SHADOW_FILTER_QUALITY_HIGH
+    // 7x7 kernel, 16 samples
+    let uw = vec4(5.0 * s - 6.0, 11.0 * s - 28.0, -(11.0 * s + 17.0), -(5.0 * s + 1.0));
+    let u = vec4(
+        (4.0 * s - 5.0) / uw.x - 3.0,
+        (4.0 * s - 16.0) / uw.y - 1.0,
+        -(7.0 * s + 5.0) / uw.z + 1.0,
+        -s / uw.w + 3.0,
+    );
+
+    let vw = vec4(5.0 * t - 6.0, 11.0 * t - 28.0, -(11.0 * t + 17.0), -(5.0 * t + 1.0));
+    let v = vec4(
+        (4.0 * t - 5.0) / vw.x - 3.0,
+        (4.0 * t - 16.0) / vw.y - 1.0,
+        -(7.0 * t + 5.0) / vw.z + 1.0,
+        -t / vw.w + 3.0,
+    );
+
+    var sum = 0.0;
+    for (var y = 0; y < 4; y += 1) {
+        for (var x = 0; x < 4; x += 1) {
+            sum += uw[x] * vw[y] * sample_shadow_map_hardware(base_uv + (vec2(u[x], v[y]) * inv_shadow_map_size), depth, array_index, uv_rect);
+        }
+    }
+
+    return sum * (1.0 / 2704.0);
+#else
+    // 5x5 kernel, 9 samples
     let uw0 = (4.0 - 3.0 * s);
     let uw1 = 7.0;
     let uw2 = (1.0 + 3.0 * s);
@@ -56,29 +112,38 @@ fn sample_shadow_map_castano_thirteen(light_local: vec2<f32>, depth: f32, array_
 
     var sum = 0.0;
 
-    sum += uw0 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u0, v0) * inv_shadow_map_size), depth, array_index);
-    sum += uw1 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u1, v0) * inv_shadow_map_size), depth, array_index);
-    sum += uw2 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u2, v0) * inv_shadow_map_size), depth, array_index);
+    sum += uw0 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u0, v0) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw1 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u1, v0) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw2 * vw0 * sample_shadow_map_hardware(base_uv + (vec2(u2, v0) * inv_shadow_map_size), depth, array_index, uv_rect);
 
-    sum += uw0 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u0, v1) * inv_shadow_map_size), depth, array_index);
-    sum += uw1 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u1, v1) * inv_shadow_map_size), depth, array_index);
-    sum += uw2 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u2, v1) * inv_shadow_map_size), depth, array_index);
+    sum += uw0 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u0, v1) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw1 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u1, v1) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw2 * vw1 * sample_shadow_map_hardware(base_uv + (vec2(u2, v1) * inv_shadow_map_size), depth, array_index, uv_rect);
 
-    sum += uw0 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u0, v2) * inv_shadow_map_size), depth, array_index);
-    sum += uw1 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u1, v2) * inv_shadow_map_size), depth, array_index);
-    sum += uw2 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u2, v2) * inv_shadow_map_size), depth, array_index);
+    sum += uw0 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u0, v2) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw1 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u1, v2) * inv_shadow_map_size), depth, array_index, uv_rect);
+    sum += uw2 * vw2 * sample_shadow_map_hardware(base_uv + (vec2(u2, v2) * inv_shadow_map_size), depth, array_index, uv_rect);
 
     return sum * (1.0 / 144.0);
+#endif
 }
 
 fn map(min1: f32, max1: f32, min2: f32, max2: f32, value: f32) -> f32 {
     return min2 + (value - min1) * (max2 - min2) / (max1 - min1);
 }
 
-fn sample_shadow_map_jimenez_fourteen(light_local: vec2<f32>, depth: f32, array_index: i32, texel_size: f32) -> f32 {
+fn sample_shadow_map_jimenez_fourteen(light_local: vec2<f32>, depth: f32, array_index: i32, texel_size: f32, uv_rect: vec4<f32>) -> f32 {
     let shadow_map_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
 
-    let random_angle = 2.0 * PI * interleaved_gradient_noise(light_local * shadow_map_size, view_bindings::globals.frame_count);
+#ifdef SHADOW_FILTER_JITTER
+    // Change the rotation every frame, for TAA to average out the noise. The sequence restarts
+    // on a `ShadowMapRefresh`.
+    let frame = view_bindings::lights.shadow_jitter_frame;
+#else
+    // Keep the noise pattern stable when nothing resolves it over time.
+    let frame = 0u;
+#endif
+    let random_angle = 2.0 * PI * interleaved_gradient_noise(light_local * shadow_map_size, frame);
     let m = vec2(sin(random_angle), cos(random_angle));
     let rotation_matrix = mat2x2(
         m.y, -m.x,
@@ -100,24 +165,42 @@ fn sample_shadow_map_jimenez_fourteen(light_local: vec2<f32>, depth: f32, array_
     let sample_offset8 = (rotation_matrix * utils::SPIRAL_OFFSET_7_) * uv_offset_scale;
 
     var sum = 0.0;
-    sum += sample_shadow_map_hardware(light_local + sample_offset1, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset2, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset3, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset4, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset5, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset6, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset7, depth, array_index);
-    sum += sample_shadow_map_hardware(light_local + sample_offset8, depth, array_index);
+#ifdef SHADOW_FILTER_QUALITY_LOW
+    // Every other offset of the spiral, still covering all four quadrants
+    sum += sample_shadow_map_hardware(light_local + sample_offset1, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset3, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset5, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset7, depth, array_index, uv_rect);
+    return sum / 4.0;
+#else ifdef SHADOW_FILTER_QUALITY_HIGH
+    // The same spiral as the SPIRAL_OFFSET_*_ constants (rotating by 135 degrees and shrinking
+    // linearly with each sample) extended to 16 samples
+    for (var i = 0u; i < 16u; i += 1u) {
+        let spiral_angle = f32(i + 1u) * 0.75 * PI;
+        let spiral_offset = vec2(cos(spiral_angle), sin(spiral_angle)) * (1.0 - f32(i) / 16.0);
+        sum += sample_shadow_map_hardware(light_local + (rotation_matrix * spiral_offset) * uv_offset_scale, depth, array_index, uv_rect);
+    }
+    return sum / 16.0;
+#else
+    sum += sample_shadow_map_hardware(light_local + sample_offset1, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset2, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset3, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset4, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset5, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset6, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset7, depth, array_index, uv_rect);
+    sum += sample_shadow_map_hardware(light_local + sample_offset8, depth, array_index, uv_rect);
     return sum / 8.0;
+#endif
 }
 
-fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel_size: f32) -> f32 {
+fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel_size: f32, uv_rect: vec4<f32>) -> f32 {
 #ifdef SHADOW_FILTER_METHOD_CASTANO_13
-    return sample_shadow_map_castano_thirteen(light_local, depth, array_index);
+    return sample_shadow_map_castano_thirteen(light_local, depth, array_index, uv_rect);
 #else ifdef SHADOW_FILTER_METHOD_JIMENEZ_14
-    return sample_shadow_map_jimenez_fourteen(light_local, depth, array_index, texel_size);
+    return sample_shadow_map_jimenez_fourteen(light_local, depth, array_index, texel_size, uv_rect);
 #else ifdef SHADOW_FILTER_METHOD_HARDWARE_2X2
-    return sample_shadow_map_hardware(light_local, depth, array_index);
+    return sample_shadow_map_hardware(light_local, depth, array_index, uv_rect);
 #else
     // This needs a default return value to avoid shader compilation errors if it's compiled with no SHADOW_FILTER_METHOD_* defined.
     // (eg. if the normal prepass is enabled it ends up compiling this due to the normal prepass depending on pbr_functions, which depends on shadows)
@@ -126,3 +209,109 @@ fn sample_shadow_map(light_local: vec2<f32>, depth: f32, array_index: i32, texel
     return 0.0;
 #endif
 }
+
+// Do the lookup in a point light cubemap, using HW 2x2 PCF and comparison
+fn sample_shadow_cubemap_hardware(light_local: vec3<f32>, depth: f32, light_id: u32) -> f32 {
+#ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
+    return textureSampleCompare(
+        view_bindings::point_shadow_textures,
+        view_bindings::point_shadow_textures_sampler,
+        light_local,
+        depth,
+    );
+#else
+    return textureSampleCompareLevel(
+        view_bindings::point_shadow_textures,
+        view_bindings::point_shadow_textures_sampler,
+        light_local,
+        i32(light_id),
+        depth,
+    );
+#endif
+}
+
+// Percentage-closer filtering in cube space: takes a 3x3 grid of hardware 2x2
+// PCF samples on the plane perpendicular to the lookup direction, spaced one
+// texel apart at the distance of the fragment.
+fn sample_shadow_cubemap_pcf(light_local: vec3<f32>, depth: f32, light_id: u32) -> f32 {
+    // A cubemap face spans [-1, 1] at one world unit along its major axis, so a
+    // texel grows proportionally with the distance along that axis.
+    let abs_light_local = abs(light_local);
+    let major_axis_magnitude = max(abs_light_local.x, max(abs_light_local.y, abs_light_local.z));
+    let face_size = f32(textureDimensions(view_bindings::point_shadow_textures).x);
+    let texel_size = 2.0 * major_axis_magnitude / face_size;
+
+    // Build an orthonormal basis around the lookup direction.
+    let direction = normalize(light_local);
+    var up = vec3(0.0, 1.0, 0.0);
+    if abs(direction.y) > 0.99 {
+        up = vec3(1.0, 0.0, 0.0);
+    }
+    let right = normalize(cross(up, direction)) * texel_size;
+    up = cross(direction, normalize(right)) * texel_size;
+
+    var sum = 0.0;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let offset = f32(x) * right + f32(y) * up;
+            sum += sample_shadow_cubemap_hardware(light_local + offset, depth, light_id);
+        }
+    }
+    return sum / 9.0;
+}
+
+fn sample_shadow_cubemap(light_local: vec3<f32>, depth: f32, light_id: u32) -> f32 {
+#ifdef POINT_LIGHT_SHADOW_FILTER_PCF
+    return sample_shadow_cubemap_pcf(light_local, depth, light_id);
+#else
+    return sample_shadow_cubemap_hardware(light_local, depth, light_id);
+#endif
+}
+
+// Samples a dual-paraboloid shadow map at the normalized device coordinates the
+// shadow pass rendered to. The map is stored in face `layer % 6` of cubemap
+// `layer / 6`; see `PARABOLOID_FACES` in `render/light.rs`.
+fn sample_shadow_paraboloid_hardware(paraboloid_coords: vec2<f32>, depth: f32, layer: u32) -> f32 {
+    // Keep the 2x2 footprint inside the face so that it doesn't filter across
+    // the edge into an unrelated map.
+    let face_size = f32(textureDimensions(view_bindings::point_shadow_textures).x);
+    let coords = clamp(paraboloid_coords, vec2(-1.0 + 1.0 / face_size), vec2(1.0 - 1.0 / face_size));
+
+    // Face coordinates run top to bottom, unlike NDC. Build the lookup vector
+    // that the cubemap face selection rules map back onto (s, t) of the face.
+    let s = coords.x;
+    let t = -coords.y;
+    var direction: vec3<f32>;
+    switch layer % 6u {
+        case 0u: { direction = vec3(1.0, -t, -s); }
+        case 1u: { direction = vec3(-1.0, -t, s); }
+        case 2u: { direction = vec3(s, 1.0, t); }
+        case 3u: { direction = vec3(s, -1.0, -t); }
+        case 4u: { direction = vec3(s, -t, 1.0); }
+        default: { direction = vec3(-s, -t, -1.0); }
+    }
+    return sample_shadow_cubemap_hardware(direction, depth, layer / 6u);
+}
+
+// Percentage-closer filtering of a dual-paraboloid shadow map: a 3x3 grid of
+// hardware 2x2 PCF samples spaced one texel apart.
+fn sample_shadow_paraboloid_pcf(paraboloid_coords: vec2<f32>, depth: f32, layer: u32) -> f32 {
+    let texel_size = 2.0 / f32(textureDimensions(view_bindings::point_shadow_textures).x);
+
+    var sum = 0.0;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let offset = vec2(f32(x), f32(y)) * texel_size;
+            sum += sample_shadow_paraboloid_hardware(paraboloid_coords + offset, depth, layer);
+        }
+    }
+    return sum / 9.0;
+}
+
+fn sample_shadow_paraboloid(paraboloid_coords: vec2<f32>, depth: f32, layer: u32) -> f32 {
+#ifdef POINT_LIGHT_SHADOW_FILTER_PCF
+    return sample_shadow_paraboloid_pcf(paraboloid_coords, depth, layer);
+#else
+    return sample_shadow_paraboloid_hardware(paraboloid_coords, depth, layer);
+#endif
+}
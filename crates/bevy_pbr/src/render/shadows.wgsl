@@ -4,12 +4,20 @@
     mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
     mesh_view_bindings as view_bindings,
     utils::hsv2rgb,
-    shadow_sampling::sample_shadow_map
+    shadow_sampling::{sample_shadow_cubemap, sample_shadow_map, sample_shadow_paraboloid},
 }
 
 const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
 
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
+#ifdef DUAL_PARABOLOID_POINT_SHADOWS
+    return fetch_point_shadow_dual_paraboloid(light_id, frag_position, surface_normal);
+#else
+    return fetch_point_shadow_cubemap(light_id, frag_position, surface_normal);
+#endif
+}
+
+fn fetch_point_shadow_cubemap(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = &view_bindings::point_lights.data[light_id];
 
     // because the shadow maps align with the axes and the frustum planes are at 45 degrees
@@ -37,18 +45,45 @@ fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: v
     let zw = -major_axis_magnitude * (*light).light_custom_data.xy + (*light).light_custom_data.zw;
     let depth = zw.x / zw.y;
 
-    // Do the lookup, using HW PCF and comparison. Cubemaps assume a left-handed coordinate space,
-    // so we have to flip the z-axis when sampling.
-    // NOTE: Due to the non-uniform control flow above, we must use the Level variant of
-    // textureSampleCompare to avoid undefined behavior due to some of the fragments in
-    // a quad (2x2 fragments) being processed not being sampled, and this messing with
-    // mip-mapping functionality. The shadow maps have no mipmaps so Level just samples
-    // from LOD 0.
-#ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
-    return textureSampleCompare(view_bindings::point_shadow_textures, view_bindings::point_shadow_textures_sampler, frag_ls * flip_z, depth);
-#else
-    return textureSampleCompareLevel(view_bindings::point_shadow_textures, view_bindings::point_shadow_textures_sampler, frag_ls * flip_z, i32(light_id), depth);
-#endif
+    // Do the lookup, using the point light shadow filtering method of the view.
+    // Cubemaps assume a left-handed coordinate space, so we have to flip the z-axis
+    // when sampling.
+    // NOTE: Due to the non-uniform control flow above, the samplers use the Level
+    // variant of textureSampleCompare where available to avoid undefined behavior due
+    // to some of the fragments in a quad (2x2 fragments) being processed not being
+    // sampled, and this messing with mip-mapping functionality. The shadow maps have
+    // no mipmaps so Level just samples from LOD 0.
+    return sample_shadow_cubemap(frag_ls * flip_z, depth, light_id);
+}
+
+// NOTE: The projection must match `dual_paraboloid_clip_position` in `prepass.wgsl`.
+fn fetch_point_shadow_dual_paraboloid(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
+    let light = &view_bindings::point_lights.data[light_id];
+
+    let surface_to_light = (*light).position_radius.xyz - frag_position.xyz;
+    let distance_to_light = length(surface_to_light);
+
+    // As for cubemaps, the normal bias is scaled by the texel size at 1 world unit from the light.
+    let normal_offset = (*light).shadow_normal_bias * distance_to_light * surface_normal.xyz;
+    let depth_offset = (*light).shadow_depth_bias * normalize(surface_to_light.xyz);
+    let frag_ls = frag_position.xyz + normal_offset + depth_offset - (*light).position_radius.xyz;
+    let distance = length(frag_ls);
+
+    // Move into the view space of the hemisphere's shadow pass. The -Z hemisphere is rendered
+    // with an unrotated view, the +Z one with a view turned half a turn around Y.
+    var direction = frag_ls / distance;
+    var hemisphere = 0u;
+    if direction.z > 0.0 {
+        hemisphere = 1u;
+        direction = vec3(-direction.x, direction.y, -direction.z);
+    }
+    let paraboloid_coords = direction.xy / (1.0 - direction.z);
+
+    // light_custom_data.z holds the near plane of the point light projection, and the shadow
+    // pass writes reverse-z depth as near / distance.
+    let depth = (*light).light_custom_data.z / distance;
+
+    return sample_shadow_paraboloid(paraboloid_coords, depth, light_id * 2u + hemisphere);
 }
 
 fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
@@ -91,7 +126,8 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
 
     // divide xy by perspective matrix "f" and by -projected.z (projected.z is -projection matrix's w)
     // to get ndc coordinates
-    let f_div_minus_z = 1.0 / ((*light).spot_light_tan_angle * -projected_position.z);
+    // light_custom_data.w holds the tangent of the outer angle
+    let f_div_minus_z = 1.0 / ((*light).light_custom_data.w * -projected_position.z);
     let shadow_xy_ndc = projected_position.xy * f_div_minus_z;
     // convert to uv coordinates
     let shadow_uv = shadow_xy_ndc * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
@@ -101,7 +137,7 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
 
      // Number determined by trial and error that gave nice results.
      let texel_size = 0.0134277345;
-    return sample_shadow_map(shadow_uv, depth, i32(light_id) + view_bindings::lights.spot_light_shadowmap_offset, texel_size);
+    return sample_shadow_map(shadow_uv, depth, i32(light_id) + view_bindings::lights.spot_light_shadowmap_offset, texel_size, vec4(0.0, 0.0, 1.0, 1.0));
 }
 
 fn get_cascade_index(light_id: u32, view_z: f32) -> u32 {
@@ -138,12 +174,13 @@ fn sample_directional_cascade(light_id: u32, cascade_index: u32, frag_position:
     // compute texture coordinates for shadow lookup, compensating for the Y-flip difference
     // between the NDC and texture coordinates
     let flip_correction = vec2<f32>(0.5, -0.5);
-    let light_local = offset_position_ndc.xy * flip_correction + vec2<f32>(0.5, 0.5);
+    let uv_min = (*cascade).uv_offset;
+    let uv_max = uv_min + vec2((*cascade).uv_scale);
+    let light_local = mix(uv_min, uv_max, offset_position_ndc.xy * flip_correction + vec2<f32>(0.5, 0.5));
 
     let depth = offset_position_ndc.z;
 
-    let array_index = i32((*light).depth_texture_base_index + cascade_index);
-    return sample_shadow_map(light_local, depth, array_index, (*cascade).texel_size);
+    return sample_shadow_map(light_local, depth, i32((*cascade).array_index), (*cascade).texel_size, vec4(uv_min, uv_max));
 }
 
 fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>, view_z: f32) -> f32 {
@@ -166,9 +203,44 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
             shadow = mix(shadow, next_shadow, (-view_z - next_near_bound) / (this_far_bound - next_near_bound));
         }
     }
+
+    // Fade the shadow out toward the far bound of the last cascade, if requested.
+    let fade_distance = (*light).shadow_fade_distance;
+    if (fade_distance > 0.0) {
+        let max_distance = (*light).cascades[(*light).num_cascades - 1u].far_bound;
+        shadow = mix(shadow, 1.0, saturate((-view_z - (max_distance - fade_distance)) / fade_distance));
+    }
     return shadow;
 }
 
+// Returns the intensity multiplier of the cascade covering `view_z`, blended with the next
+// cascade across their overlap in the same way as the shadow itself. Fragments beyond the last
+// cascade use its multiplier.
+fn directional_cascade_intensity(light_id: u32, view_z: f32) -> f32 {
+    let light = &view_bindings::lights.directional_lights[light_id];
+    if ((*light).num_cascades == 0u) {
+        return 1.0;
+    }
+
+    let cascade_index = get_cascade_index(light_id, view_z);
+    if (cascade_index >= (*light).num_cascades) {
+        return (*light).cascades[(*light).num_cascades - 1u].intensity;
+    }
+
+    var intensity = (*light).cascades[cascade_index].intensity;
+
+    let next_cascade_index = cascade_index + 1u;
+    if (next_cascade_index < (*light).num_cascades) {
+        let this_far_bound = (*light).cascades[cascade_index].far_bound;
+        let next_near_bound = (1.0 - (*light).cascades_overlap_proportion) * this_far_bound;
+        if (-view_z >= next_near_bound) {
+            let next_intensity = (*light).cascades[next_cascade_index].intensity;
+            intensity = mix(intensity, next_intensity, (-view_z - next_near_bound) / (this_far_bound - next_near_bound));
+        }
+    }
+    return intensity;
+}
+
 fn cascade_debug_visualization(
     output_color: vec3<f32>,
     light_id: u32,
@@ -20,6 +20,9 @@
 @group(0) @binding(3) var ambient_occlusion: texture_storage_2d<r16float, write>;
 @group(0) @binding(4) var depth_differences: texture_storage_2d<r32uint, write>;
 @group(0) @binding(5) var<uniform> globals: Globals;
+#ifdef SSAO_BAKED
+@group(0) @binding(6) var baked_ambient_occlusion: texture_2d<f32>;
+#endif
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var<uniform> view: View;
 
@@ -101,6 +104,25 @@ fn gtao(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let uv = (vec2<f32>(pixel_coordinates) + 0.5) / view.viewport.zw;
 
     var pixel_depth = calculate_neighboring_depth_differences(pixel_coordinates);
+
+    // Static pixels have a normal alpha of 0, see `ScreenSpaceAmbientOcclusionStatic`
+    let is_static = textureLoad(normals, pixel_coordinates, 0).a < 0.5;
+#ifdef SSAO_BAKE_STATIC
+    // Mark everything that isn't static geometry as not baked
+    if !is_static {
+        textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(-1.0, 0.0, 0.0, 0.0));
+        return;
+    }
+#endif
+#ifdef SSAO_BAKED
+    // Reuse the baked visibility of static pixels, only the rest is computed live
+    let baked_visibility = textureLoad(baked_ambient_occlusion, pixel_coordinates, 0).r;
+    if is_static && baked_visibility >= 0.0 {
+        textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(baked_visibility, 0.0, 0.0, 0.0));
+        return;
+    }
+#endif
+
     pixel_depth += 0.00001; // Avoid depth precision issues
 
     let pixel_position = reconstruct_view_space_position(pixel_depth, uv);
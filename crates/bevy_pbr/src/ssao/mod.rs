@@ -1,24 +1,29 @@
 use crate::NodePbr;
-use bevy_app::{App, Plugin};
-use bevy_asset::{load_internal_asset, Handle};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetEvent, Handle};
 use bevy_core_pipeline::{
     core_3d::graph::{Core3d, Node3d},
     prelude::Camera3d,
     prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
 };
 use bevy_ecs::{
+    change_detection::DetectChanges,
+    event::EventReader,
     prelude::{Bundle, Component, Entity},
-    query::{Has, QueryItem, With},
+    query::{Added, Changed, Has, Or, QueryItem, With},
     reflect::ReflectComponent,
+    removal_detection::RemovedComponents,
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut, Resource},
-    world::{FromWorld, World},
+    world::{FromWorld, Ref, World},
 };
+use bevy_math::UVec2;
 use bevy_reflect::Reflect;
 use bevy_render::{
-    camera::{ExtractedCamera, TemporalJitter},
+    camera::{ExtractedCamera, Projection, TemporalJitter},
     extract_component::ExtractComponent,
     globals::{GlobalsBuffer, GlobalsUniform},
+    mesh::Mesh,
     prelude::Camera,
     render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
     render_resource::{
@@ -29,12 +34,16 @@ use bevy_render::{
     },
     renderer::{RenderAdapter, RenderContext, RenderDevice, RenderQueue},
     texture::{CachedTexture, TextureCache},
-    view::{Msaa, ViewUniform, ViewUniformOffset, ViewUniforms},
+    view::{
+        InheritedVisibility, Msaa, ViewUniform, ViewUniformOffset, ViewUniforms, VisibilitySystems,
+    },
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_transform::{components::GlobalTransform, TransformSystem};
 use bevy_utils::{
     prelude::default,
     tracing::{error, warn},
+    EntityHashMap, HashSet,
 };
 use std::mem;
 
@@ -68,7 +77,15 @@ impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
             Shader::from_wgsl
         );
 
-        app.register_type::<ScreenSpaceAmbientOcclusionSettings>();
+        app.register_type::<ScreenSpaceAmbientOcclusionSettings>()
+            .register_type::<ScreenSpaceAmbientOcclusionBake>()
+            .register_type::<ScreenSpaceAmbientOcclusionStatic>()
+            .add_systems(
+                PostUpdate,
+                invalidate_ssao_bakes
+                    .after(TransformSystem::TransformPropagate)
+                    .after(VisibilitySystems::VisibilityPropagate),
+            );
     }
 
     fn finish(&self, app: &mut App) {
@@ -101,12 +118,15 @@ impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
         render_app
             .init_resource::<SsaoPipelines>()
             .init_resource::<SpecializedComputePipelines<SsaoPipelines>>()
+            .init_resource::<SsaoBakeCache>()
             .add_systems(ExtractSchedule, extract_ssao_settings)
             .add_systems(
                 Render,
                 (
                     prepare_ssao_pipelines.in_set(RenderSet::Prepare),
-                    prepare_ssao_textures.in_set(RenderSet::PrepareResources),
+                    prepare_ssao_textures
+                        .in_set(RenderSet::PrepareResources)
+                        .after(prepare_ssao_pipelines),
                     prepare_ssao_bind_groups.in_set(RenderSet::PrepareBindGroups),
                 ),
             )
@@ -159,6 +179,50 @@ pub struct ScreenSpaceAmbientOcclusionSettings {
     pub quality_level: ScreenSpaceAmbientOcclusionQualityLevel,
 }
 
+/// Component that bakes the screen space ambient occlusion of the static geometry seen by a 3d
+/// camera once and reuses it on subsequent frames, instead of recomputing it every frame.
+///
+/// Only meshes marked with [`ScreenSpaceAmbientOcclusionStatic`] are rendered into the bake. Every
+/// frame, pixels covered by static geometry reuse their baked occlusion, and live SSAO is computed
+/// for all other pixels, so dynamic meshes receive ambient occlusion from everything around them.
+/// Dynamic meshes don't darken the baked static surfaces around them though.
+///
+/// The bake is regenerated whenever it is invalidated. This happens automatically when the camera
+/// moves, its projection or [`ScreenSpaceAmbientOcclusionSettings`] change, and when static mesh
+/// entities are added, removed, moved, hidden or have their mesh asset modified. Call
+/// [`ScreenSpaceAmbientOcclusionBake::invalidate`] to force a rebake for any other change.
+///
+/// Since screen space ambient occlusion depends on the view, the bake only saves work while the
+/// camera stays still: a camera that moves every frame rebakes every frame, which costs more than
+/// plain [`ScreenSpaceAmbientOcclusionSettings`].
+///
+/// With [`TemporalJitter`], the bake keeps the subpixel offset of the frame it was rendered in
+/// rather than following the jitter, so temporal anti-aliasing can't refine it and static edges
+/// may look slightly softer. The bake also doesn't vary its noise over time like live SSAO does.
+///
+/// Requires [`ScreenSpaceAmbientOcclusionSettings`] on the same camera.
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct ScreenSpaceAmbientOcclusionBake {
+    generation: u32,
+}
+
+/// Marks a mesh entity as static geometry for [`ScreenSpaceAmbientOcclusionBake`].
+///
+/// Adding, removing, moving or hiding marked entities, or modifying their mesh asset, invalidates
+/// the baked ambient occlusion of all cameras. Unmarked entities aren't baked and always get live
+/// ambient occlusion instead.
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct ScreenSpaceAmbientOcclusionStatic;
+
+impl ScreenSpaceAmbientOcclusionBake {
+    /// Marks the baked ambient occlusion as stale, causing it to be recomputed next frame.
+    pub fn invalidate(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
 #[derive(Reflect, PartialEq, Eq, Hash, Clone, Copy, Default)]
 pub enum ScreenSpaceAmbientOcclusionQualityLevel {
     Low,
@@ -198,114 +262,203 @@ impl ViewNode for SsaoNode {
         &'static SsaoPipelineId,
         &'static SsaoBindGroups,
         &'static ViewUniformOffset,
+        Option<(
+            &'static SsaoBakeTexture,
+            &'static SsaoBakePipelineId,
+            &'static SsaoBakeBindGroups,
+        )>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, pipeline_id, bind_groups, view_uniform_offset): QueryItem<Self::ViewQuery>,
+        (camera, pipeline_id, bind_groups, view_uniform_offset, bake): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipelines = world.resource::<SsaoPipelines>();
         let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(camera_size) = camera.physical_viewport_size else {
+            return Ok(());
+        };
+
+        let Some(bake) = bake else {
+            let (
+                Some(preprocess_depth_pipeline),
+                Some(gtao_pipeline),
+                Some(spatial_denoise_pipeline),
+            ) = (
+                pipeline_cache.get_compute_pipeline(pipelines.preprocess_depth_pipeline),
+                pipeline_cache.get_compute_pipeline(pipeline_id.0),
+                pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_pipeline),
+            )
+            else {
+                return Ok(());
+            };
+
+            render_context.command_encoder().push_debug_group("ssao");
+            run_ssao_passes(
+                render_context,
+                camera_size,
+                &bind_groups.common_bind_group,
+                view_uniform_offset,
+                (
+                    preprocess_depth_pipeline,
+                    &bind_groups.preprocess_depth_bind_group,
+                ),
+                (gtao_pipeline, &bind_groups.gtao_bind_group),
+                (
+                    spatial_denoise_pipeline,
+                    &bind_groups.spatial_denoise_bind_group,
+                ),
+            );
+            render_context.command_encoder().pop_debug_group();
+            return Ok(());
+        };
+
+        let (bake_texture, bake_pipeline_id, bake_bind_groups) = bake;
         let (
-            Some(camera_size),
             Some(preprocess_depth_pipeline),
-            Some(spatial_denoise_pipeline),
             Some(gtao_pipeline),
+            Some(spatial_denoise_pipeline),
+            Some(preprocess_static_depth_pipeline),
+            Some(gtao_static_pipeline),
+            Some(spatial_denoise_static_pipeline),
         ) = (
-            camera.physical_viewport_size,
             pipeline_cache.get_compute_pipeline(pipelines.preprocess_depth_pipeline),
-            pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_pipeline),
             pipeline_cache.get_compute_pipeline(pipeline_id.0),
+            pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_baked_pipeline),
+            pipeline_cache.get_compute_pipeline(pipelines.preprocess_static_depth_pipeline),
+            pipeline_cache.get_compute_pipeline(bake_pipeline_id.0),
+            pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_static_pipeline),
         )
         else {
             return Ok(());
         };
 
-        render_context.command_encoder().push_debug_group("ssao");
-
-        {
-            let mut preprocess_depth_pass =
-                render_context
-                    .command_encoder()
-                    .begin_compute_pass(&ComputePassDescriptor {
-                        label: Some("ssao_preprocess_depth_pass"),
-                        timestamp_writes: None,
-                    });
-            preprocess_depth_pass.set_pipeline(preprocess_depth_pipeline);
-            preprocess_depth_pass.set_bind_group(0, &bind_groups.preprocess_depth_bind_group, &[]);
-            preprocess_depth_pass.set_bind_group(
-                1,
-                &bind_groups.common_bind_group,
-                &[view_uniform_offset.offset],
-            );
-            preprocess_depth_pass.dispatch_workgroups(
-                div_ceil(camera_size.x, 16),
-                div_ceil(camera_size.y, 16),
-                1,
-            );
-        }
-
-        {
-            let mut gtao_pass =
-                render_context
-                    .command_encoder()
-                    .begin_compute_pass(&ComputePassDescriptor {
-                        label: Some("ssao_gtao_pass"),
-                        timestamp_writes: None,
-                    });
-            gtao_pass.set_pipeline(gtao_pipeline);
-            gtao_pass.set_bind_group(0, &bind_groups.gtao_bind_group, &[]);
-            gtao_pass.set_bind_group(
-                1,
+        if bake_texture.render {
+            // Bake the ambient occlusion of static geometry only
+            render_context
+                .command_encoder()
+                .push_debug_group("ssao_bake");
+            run_ssao_passes(
+                render_context,
+                camera_size,
                 &bind_groups.common_bind_group,
-                &[view_uniform_offset.offset],
-            );
-            gtao_pass.dispatch_workgroups(
-                div_ceil(camera_size.x, 8),
-                div_ceil(camera_size.y, 8),
-                1,
-            );
-        }
-
-        {
-            let mut spatial_denoise_pass =
-                render_context
-                    .command_encoder()
-                    .begin_compute_pass(&ComputePassDescriptor {
-                        label: Some("ssao_spatial_denoise_pass"),
-                        timestamp_writes: None,
-                    });
-            spatial_denoise_pass.set_pipeline(spatial_denoise_pipeline);
-            spatial_denoise_pass.set_bind_group(0, &bind_groups.spatial_denoise_bind_group, &[]);
-            spatial_denoise_pass.set_bind_group(
-                1,
-                &bind_groups.common_bind_group,
-                &[view_uniform_offset.offset],
-            );
-            spatial_denoise_pass.dispatch_workgroups(
-                div_ceil(camera_size.x, 8),
-                div_ceil(camera_size.y, 8),
-                1,
+                view_uniform_offset,
+                (
+                    preprocess_static_depth_pipeline,
+                    &bake_bind_groups.preprocess_depth_bind_group,
+                ),
+                (gtao_static_pipeline, &bake_bind_groups.gtao_bind_group),
+                (
+                    spatial_denoise_static_pipeline,
+                    &bake_bind_groups.spatial_denoise_bind_group,
+                ),
             );
+            render_context.command_encoder().pop_debug_group();
         }
 
+        // Composite the bake with live ambient occlusion for everything else
+        render_context.command_encoder().push_debug_group("ssao");
+        run_ssao_passes(
+            render_context,
+            camera_size,
+            &bind_groups.common_bind_group,
+            view_uniform_offset,
+            (
+                preprocess_depth_pipeline,
+                &bind_groups.preprocess_depth_bind_group,
+            ),
+            (gtao_pipeline, &bind_groups.gtao_bind_group),
+            (
+                spatial_denoise_pipeline,
+                &bind_groups.spatial_denoise_bind_group,
+            ),
+        );
         render_context.command_encoder().pop_debug_group();
+
         Ok(())
     }
 }
 
+/// Runs the preprocess depth, GTAO and spatial denoise passes with the given pipelines and bind
+/// groups, in that order.
+fn run_ssao_passes(
+    render_context: &mut RenderContext,
+    camera_size: UVec2,
+    common_bind_group: &BindGroup,
+    view_uniform_offset: &ViewUniformOffset,
+    (preprocess_depth_pipeline, preprocess_depth_bind_group): (&ComputePipeline, &BindGroup),
+    (gtao_pipeline, gtao_bind_group): (&ComputePipeline, &BindGroup),
+    (spatial_denoise_pipeline, spatial_denoise_bind_group): (&ComputePipeline, &BindGroup),
+) {
+    {
+        let mut preprocess_depth_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("ssao_preprocess_depth_pass"),
+                    timestamp_writes: None,
+                });
+        preprocess_depth_pass.set_pipeline(preprocess_depth_pipeline);
+        preprocess_depth_pass.set_bind_group(0, preprocess_depth_bind_group, &[]);
+        preprocess_depth_pass.set_bind_group(1, common_bind_group, &[view_uniform_offset.offset]);
+        preprocess_depth_pass.dispatch_workgroups(
+            div_ceil(camera_size.x, 16),
+            div_ceil(camera_size.y, 16),
+            1,
+        );
+    }
+
+    {
+        let mut gtao_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("ssao_gtao_pass"),
+                    timestamp_writes: None,
+                });
+        gtao_pass.set_pipeline(gtao_pipeline);
+        gtao_pass.set_bind_group(0, gtao_bind_group, &[]);
+        gtao_pass.set_bind_group(1, common_bind_group, &[view_uniform_offset.offset]);
+        gtao_pass.dispatch_workgroups(div_ceil(camera_size.x, 8), div_ceil(camera_size.y, 8), 1);
+    }
+
+    {
+        let mut spatial_denoise_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("ssao_spatial_denoise_pass"),
+                    timestamp_writes: None,
+                });
+        spatial_denoise_pass.set_pipeline(spatial_denoise_pipeline);
+        spatial_denoise_pass.set_bind_group(0, spatial_denoise_bind_group, &[]);
+        spatial_denoise_pass.set_bind_group(1, common_bind_group, &[view_uniform_offset.offset]);
+        spatial_denoise_pass.dispatch_workgroups(
+            div_ceil(camera_size.x, 8),
+            div_ceil(camera_size.y, 8),
+            1,
+        );
+    }
+}
+
 #[derive(Resource)]
 struct SsaoPipelines {
     preprocess_depth_pipeline: CachedComputePipelineId,
     spatial_denoise_pipeline: CachedComputePipelineId,
+    preprocess_static_depth_pipeline: CachedComputePipelineId,
+    spatial_denoise_static_pipeline: CachedComputePipelineId,
+    spatial_denoise_baked_pipeline: CachedComputePipelineId,
 
     common_bind_group_layout: BindGroupLayout,
     preprocess_depth_bind_group_layout: BindGroupLayout,
     gtao_bind_group_layout: BindGroupLayout,
     spatial_denoise_bind_group_layout: BindGroupLayout,
+    preprocess_static_depth_bind_group_layout: BindGroupLayout,
+    gtao_baked_bind_group_layout: BindGroupLayout,
+    spatial_denoise_baked_bind_group_layout: BindGroupLayout,
 
     hilbert_index_lut: TextureView,
     point_clamp_sampler: Sampler,
@@ -401,6 +554,52 @@ impl FromWorld for SsaoPipelines {
             ),
         );
 
+        let preprocess_static_depth_bind_group_layout = render_device.create_bind_group_layout(
+            "ssao_preprocess_static_depth_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_depth_2d(),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),
+            ),
+        );
+
+        let gtao_baked_bind_group_layout = render_device.create_bind_group_layout(
+            "ssao_gtao_baked_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Uint),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R32Uint, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<GlobalsUniform>(false),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),
+            ),
+        );
+
+        let spatial_denoise_baked_bind_group_layout = render_device.create_bind_group_layout(
+            "ssao_spatial_denoise_baked_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Uint),
+                    texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),
+            ),
+        );
+
         let preprocess_depth_pipeline =
             pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
                 label: Some("ssao_preprocess_depth_pipeline".into()),
@@ -427,14 +626,59 @@ impl FromWorld for SsaoPipelines {
                 entry_point: "spatial_denoise".into(),
             });
 
+        let preprocess_static_depth_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssao_preprocess_static_depth_pipeline".into()),
+                layout: vec![
+                    preprocess_static_depth_bind_group_layout.clone(),
+                    common_bind_group_layout.clone(),
+                ],
+                push_constant_ranges: vec![],
+                shader: PREPROCESS_DEPTH_SHADER_HANDLE,
+                shader_defs: vec!["SSAO_BAKE_STATIC".into()],
+                entry_point: "preprocess_depth".into(),
+            });
+
+        let spatial_denoise_static_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssao_spatial_denoise_static_pipeline".into()),
+                layout: vec![
+                    spatial_denoise_bind_group_layout.clone(),
+                    common_bind_group_layout.clone(),
+                ],
+                push_constant_ranges: vec![],
+                shader: SPATIAL_DENOISE_SHADER_HANDLE,
+                shader_defs: vec!["SSAO_BAKE_STATIC".into()],
+                entry_point: "spatial_denoise".into(),
+            });
+
+        let spatial_denoise_baked_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssao_spatial_denoise_baked_pipeline".into()),
+                layout: vec![
+                    spatial_denoise_baked_bind_group_layout.clone(),
+                    common_bind_group_layout.clone(),
+                ],
+                push_constant_ranges: vec![],
+                shader: SPATIAL_DENOISE_SHADER_HANDLE,
+                shader_defs: vec!["SSAO_BAKED".into()],
+                entry_point: "spatial_denoise".into(),
+            });
+
         Self {
             preprocess_depth_pipeline,
             spatial_denoise_pipeline,
+            preprocess_static_depth_pipeline,
+            spatial_denoise_static_pipeline,
+            spatial_denoise_baked_pipeline,
 
             common_bind_group_layout,
             preprocess_depth_bind_group_layout,
             gtao_bind_group_layout,
             spatial_denoise_bind_group_layout,
+            preprocess_static_depth_bind_group_layout,
+            gtao_baked_bind_group_layout,
+            spatial_denoise_baked_bind_group_layout,
 
             hilbert_index_lut,
             point_clamp_sampler,
@@ -446,6 +690,16 @@ impl FromWorld for SsaoPipelines {
 struct SsaoPipelineKey {
     ssao_settings: ScreenSpaceAmbientOcclusionSettings,
     temporal_jitter: bool,
+    bake_pass: Option<SsaoBakePass>,
+}
+
+/// The GTAO pass variants used by views with a [`ScreenSpaceAmbientOcclusionBake`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum SsaoBakePass {
+    /// Renders the ambient occlusion of static geometry into the bake.
+    Static,
+    /// Reuses the bake for static pixels and computes the others live.
+    Baked,
 }
 
 impl SpecializedComputePipeline for SsaoPipelines {
@@ -466,10 +720,22 @@ impl SpecializedComputePipeline for SsaoPipelines {
             shader_defs.push("TEMPORAL_JITTER".into());
         }
 
+        let gtao_bind_group_layout = match key.bake_pass {
+            None => &self.gtao_bind_group_layout,
+            Some(SsaoBakePass::Static) => {
+                shader_defs.push("SSAO_BAKE_STATIC".into());
+                &self.gtao_bind_group_layout
+            }
+            Some(SsaoBakePass::Baked) => {
+                shader_defs.push("SSAO_BAKED".into());
+                &self.gtao_baked_bind_group_layout
+            }
+        };
+
         ComputePipelineDescriptor {
             label: Some("ssao_gtao_pipeline".into()),
             layout: vec![
-                self.gtao_bind_group_layout.clone(),
+                gtao_bind_group_layout.clone(),
                 self.common_bind_group_layout.clone(),
             ],
             push_constant_ranges: vec![],
@@ -480,17 +746,73 @@ impl SpecializedComputePipeline for SsaoPipelines {
     }
 }
 
+fn invalidate_ssao_bakes(
+    mut cameras: Query<(
+        &mut ScreenSpaceAmbientOcclusionBake,
+        Ref<GlobalTransform>,
+        Option<Ref<Projection>>,
+        Ref<ScreenSpaceAmbientOcclusionSettings>,
+    )>,
+    static_meshes: Query<&Handle<Mesh>, With<ScreenSpaceAmbientOcclusionStatic>>,
+    changed_static_meshes: Query<
+        (),
+        (
+            With<Handle<Mesh>>,
+            With<ScreenSpaceAmbientOcclusionStatic>,
+            Or<(
+                Added<ScreenSpaceAmbientOcclusionStatic>,
+                Changed<GlobalTransform>,
+                Changed<Handle<Mesh>>,
+                Changed<InheritedVisibility>,
+            )>,
+        ),
+    >,
+    // Despawning an entity removes its marker too
+    mut removed_static_meshes: RemovedComponents<ScreenSpaceAmbientOcclusionStatic>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+) {
+    // Both readers are always fully drained so their events aren't seen again next frame
+    let mut geometry_changed = !changed_static_meshes.is_empty();
+    geometry_changed |= removed_static_meshes.read().count() > 0;
+    let modified_meshes: HashSet<_> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    geometry_changed |= !modified_meshes.is_empty()
+        && static_meshes
+            .iter()
+            .any(|mesh| modified_meshes.contains(&mesh.id()));
+
+    for (mut bake, transform, projection, settings) in &mut cameras {
+        if geometry_changed
+            || transform.is_changed()
+            || projection.is_some_and(|projection| projection.is_changed())
+            || settings.is_changed()
+        {
+            bake.invalidate();
+        }
+    }
+}
+
 fn extract_ssao_settings(
     mut commands: Commands,
     cameras: Extract<
         Query<
-            (Entity, &Camera, &ScreenSpaceAmbientOcclusionSettings),
+            (
+                Entity,
+                &Camera,
+                &ScreenSpaceAmbientOcclusionSettings,
+                Option<&ScreenSpaceAmbientOcclusionBake>,
+            ),
             (With<Camera3d>, With<DepthPrepass>, With<NormalPrepass>),
         >,
     >,
     msaa: Extract<Res<Msaa>>,
 ) {
-    for (entity, camera, ssao_settings) in &cameras {
+    for (entity, camera, ssao_settings, bake) in &cameras {
         if **msaa != Msaa::Off {
             error!(
                 "SSAO is being used which requires Msaa::Off, but Msaa is currently set to Msaa::{:?}",
@@ -500,7 +822,11 @@ fn extract_ssao_settings(
         }
 
         if camera.is_active {
-            commands.get_or_spawn(entity).insert(ssao_settings.clone());
+            let mut entity_commands = commands.get_or_spawn(entity);
+            entity_commands.insert(ssao_settings.clone());
+            if let Some(bake) = bake {
+                entity_commands.insert(bake.clone());
+            }
         }
     }
 }
@@ -513,13 +839,50 @@ pub struct ScreenSpaceAmbientOcclusionTextures {
     depth_differences_texture: CachedTexture,
 }
 
+/// Baked ambient occlusion of a view using [`ScreenSpaceAmbientOcclusionBake`].
+struct SsaoBake {
+    texture: CachedTexture,
+    size: Extent3d,
+    /// The bake generation the texture holds, if it has been rendered yet.
+    generation: Option<u32>,
+}
+
+/// Persistent storage for baked ambient occlusion, since [`TextureCache`] textures may be handed
+/// to another view once a frame is over.
+#[derive(Resource, Default)]
+struct SsaoBakeCache(EntityHashMap<Entity, SsaoBake>);
+
+/// The baked ambient occlusion of a view, and whether the SSAO node renders it this frame.
+#[derive(Component)]
+struct SsaoBakeTexture {
+    texture: CachedTexture,
+    render: bool,
+}
+
 fn prepare_ssao_textures(
     mut commands: Commands,
     mut texture_cache: ResMut<TextureCache>,
+    mut bake_cache: ResMut<SsaoBakeCache>,
     render_device: Res<RenderDevice>,
-    views: Query<(Entity, &ExtractedCamera), With<ScreenSpaceAmbientOcclusionSettings>>,
+    pipeline_cache: Res<PipelineCache>,
+    pipelines: Res<SsaoPipelines>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedCamera,
+            &SsaoPipelineId,
+            Option<(&ScreenSpaceAmbientOcclusionBake, &SsaoBakePipelineId)>,
+        ),
+        With<ScreenSpaceAmbientOcclusionSettings>,
+    >,
 ) {
-    for (entity, camera) in &views {
+    bake_cache.0.retain(|entity, _| {
+        views
+            .get(*entity)
+            .is_ok_and(|(_, _, _, bake)| bake.is_some())
+    });
+
+    for (entity, camera, pipeline_id, bake) in &views {
         let Some(physical_viewport_size) = camera.physical_viewport_size else {
             continue;
         };
@@ -557,19 +920,66 @@ fn prepare_ssao_textures(
             },
         );
 
-        let ssao_texture = texture_cache.get(
-            &render_device,
-            TextureDescriptor {
-                label: Some("ssao_texture"),
-                size,
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format: TextureFormat::R16Float,
-                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-        );
+        let ssao_texture_descriptor = TextureDescriptor {
+            label: Some("ssao_texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::R16Float,
+            usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+
+        if let Some((bake, bake_pipeline_id)) = bake {
+            let bake_texture_descriptor = TextureDescriptor {
+                label: Some("ssao_bake_texture"),
+                ..ssao_texture_descriptor.clone()
+            };
+            let cached = bake_cache.0.entry(entity).or_insert_with(|| {
+                let texture = render_device.create_texture(&bake_texture_descriptor);
+                SsaoBake {
+                    texture: CachedTexture {
+                        default_view: texture.create_view(&TextureViewDescriptor::default()),
+                        texture,
+                    },
+                    size,
+                    generation: None,
+                }
+            });
+
+            if cached.size != size {
+                let texture = render_device.create_texture(&bake_texture_descriptor);
+                cached.texture = CachedTexture {
+                    default_view: texture.create_view(&TextureViewDescriptor::default()),
+                    texture,
+                };
+                cached.size = size;
+                cached.generation = None;
+            }
+
+            let render = cached.generation != Some(bake.generation)
+                && [
+                    pipelines.preprocess_depth_pipeline,
+                    pipelines.spatial_denoise_baked_pipeline,
+                    pipelines.preprocess_static_depth_pipeline,
+                    pipelines.spatial_denoise_static_pipeline,
+                    pipeline_id.0,
+                    bake_pipeline_id.0,
+                ]
+                .into_iter()
+                .all(|id| pipeline_cache.get_compute_pipeline(id).is_some());
+            if render {
+                cached.generation = Some(bake.generation);
+            }
+
+            commands.entity(entity).insert(SsaoBakeTexture {
+                texture: cached.texture.clone(),
+                render,
+            });
+        }
+
+        let ssao_texture = texture_cache.get(&render_device, ssao_texture_descriptor);
 
         let depth_differences_texture = texture_cache.get(
             &render_device,
@@ -599,6 +1009,10 @@ fn prepare_ssao_textures(
 #[derive(Component)]
 struct SsaoPipelineId(CachedComputePipelineId);
 
+/// The GTAO pipeline rendering the bake of a view with a [`ScreenSpaceAmbientOcclusionBake`].
+#[derive(Component)]
+struct SsaoBakePipelineId(CachedComputePipelineId);
+
 fn prepare_ssao_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
@@ -608,19 +1022,39 @@ fn prepare_ssao_pipelines(
         Entity,
         &ScreenSpaceAmbientOcclusionSettings,
         Has<TemporalJitter>,
+        Has<ScreenSpaceAmbientOcclusionBake>,
     )>,
 ) {
-    for (entity, ssao_settings, temporal_jitter) in &views {
+    for (entity, ssao_settings, temporal_jitter, bake) in &views {
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
             SsaoPipelineKey {
                 ssao_settings: ssao_settings.clone(),
                 temporal_jitter,
+                bake_pass: bake.then_some(SsaoBakePass::Baked),
             },
         );
 
         commands.entity(entity).insert(SsaoPipelineId(pipeline_id));
+
+        if bake {
+            // The bake is reused across frames, so varying its noise per frame would only freeze
+            // the noise of one frame.
+            let bake_pipeline_id = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                SsaoPipelineKey {
+                    ssao_settings: ssao_settings.clone(),
+                    temporal_jitter: false,
+                    bake_pass: Some(SsaoBakePass::Static),
+                },
+            );
+
+            commands
+                .entity(entity)
+                .insert(SsaoBakePipelineId(bake_pipeline_id));
+        }
     }
 }
 
@@ -632,6 +1066,14 @@ struct SsaoBindGroups {
     spatial_denoise_bind_group: BindGroup,
 }
 
+/// The bind groups rendering the bake of a view with a [`ScreenSpaceAmbientOcclusionBake`].
+#[derive(Component)]
+struct SsaoBakeBindGroups {
+    preprocess_depth_bind_group: BindGroup,
+    gtao_bind_group: BindGroup,
+    spatial_denoise_bind_group: BindGroup,
+}
+
 fn prepare_ssao_bind_groups(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
@@ -642,6 +1084,7 @@ fn prepare_ssao_bind_groups(
         Entity,
         &ScreenSpaceAmbientOcclusionTextures,
         &ViewPrepassTextures,
+        Option<&SsaoBakeTexture>,
     )>,
 ) {
     let (Some(view_uniforms), Some(globals_uniforms)) = (
@@ -651,7 +1094,7 @@ fn prepare_ssao_bind_groups(
         return;
     };
 
-    for (entity, ssao_textures, prepass_textures) in &views {
+    for (entity, ssao_textures, prepass_textures, bake_texture) in &views {
         let common_bind_group = render_device.create_bind_group(
             "ssao_common_bind_group",
             &pipelines.common_bind_group_layout,
@@ -685,30 +1128,101 @@ fn prepare_ssao_bind_groups(
             )),
         );
 
-        let gtao_bind_group = render_device.create_bind_group(
-            "ssao_gtao_bind_group",
-            &pipelines.gtao_bind_group_layout,
-            &BindGroupEntries::sequential((
-                &ssao_textures.preprocessed_depth_texture.default_view,
-                prepass_textures.normal_view().unwrap(),
-                &pipelines.hilbert_index_lut,
-                &ssao_textures.ssao_noisy_texture.default_view,
-                &ssao_textures.depth_differences_texture.default_view,
-                globals_uniforms.clone(),
-            )),
-        );
+        let (gtao_bind_group, spatial_denoise_bind_group) = match bake_texture {
+            None => (
+                render_device.create_bind_group(
+                    "ssao_gtao_bind_group",
+                    &pipelines.gtao_bind_group_layout,
+                    &BindGroupEntries::sequential((
+                        &ssao_textures.preprocessed_depth_texture.default_view,
+                        prepass_textures.normal_view().unwrap(),
+                        &pipelines.hilbert_index_lut,
+                        &ssao_textures.ssao_noisy_texture.default_view,
+                        &ssao_textures.depth_differences_texture.default_view,
+                        globals_uniforms.clone(),
+                    )),
+                ),
+                render_device.create_bind_group(
+                    "ssao_spatial_denoise_bind_group",
+                    &pipelines.spatial_denoise_bind_group_layout,
+                    &BindGroupEntries::sequential((
+                        &ssao_textures.ssao_noisy_texture.default_view,
+                        &ssao_textures.depth_differences_texture.default_view,
+                        &ssao_textures
+                            .screen_space_ambient_occlusion_texture
+                            .default_view,
+                    )),
+                ),
+            ),
+            Some(bake_texture) => {
+                let bake_bind_groups = SsaoBakeBindGroups {
+                    preprocess_depth_bind_group: render_device.create_bind_group(
+                        "ssao_preprocess_static_depth_bind_group",
+                        &pipelines.preprocess_static_depth_bind_group_layout,
+                        &BindGroupEntries::sequential((
+                            prepass_textures.depth_view().unwrap(),
+                            &create_depth_view(0),
+                            &create_depth_view(1),
+                            &create_depth_view(2),
+                            &create_depth_view(3),
+                            &create_depth_view(4),
+                            prepass_textures.normal_view().unwrap(),
+                        )),
+                    ),
+                    gtao_bind_group: render_device.create_bind_group(
+                        "ssao_gtao_static_bind_group",
+                        &pipelines.gtao_bind_group_layout,
+                        &BindGroupEntries::sequential((
+                            &ssao_textures.preprocessed_depth_texture.default_view,
+                            prepass_textures.normal_view().unwrap(),
+                            &pipelines.hilbert_index_lut,
+                            &ssao_textures.ssao_noisy_texture.default_view,
+                            &ssao_textures.depth_differences_texture.default_view,
+                            globals_uniforms.clone(),
+                        )),
+                    ),
+                    spatial_denoise_bind_group: render_device.create_bind_group(
+                        "ssao_spatial_denoise_static_bind_group",
+                        &pipelines.spatial_denoise_bind_group_layout,
+                        &BindGroupEntries::sequential((
+                            &ssao_textures.ssao_noisy_texture.default_view,
+                            &ssao_textures.depth_differences_texture.default_view,
+                            &bake_texture.texture.default_view,
+                        )),
+                    ),
+                };
+                commands.entity(entity).insert(bake_bind_groups);
 
-        let spatial_denoise_bind_group = render_device.create_bind_group(
-            "ssao_spatial_denoise_bind_group",
-            &pipelines.spatial_denoise_bind_group_layout,
-            &BindGroupEntries::sequential((
-                &ssao_textures.ssao_noisy_texture.default_view,
-                &ssao_textures.depth_differences_texture.default_view,
-                &ssao_textures
-                    .screen_space_ambient_occlusion_texture
-                    .default_view,
-            )),
-        );
+                (
+                    render_device.create_bind_group(
+                        "ssao_gtao_baked_bind_group",
+                        &pipelines.gtao_baked_bind_group_layout,
+                        &BindGroupEntries::sequential((
+                            &ssao_textures.preprocessed_depth_texture.default_view,
+                            prepass_textures.normal_view().unwrap(),
+                            &pipelines.hilbert_index_lut,
+                            &ssao_textures.ssao_noisy_texture.default_view,
+                            &ssao_textures.depth_differences_texture.default_view,
+                            globals_uniforms.clone(),
+                            &bake_texture.texture.default_view,
+                        )),
+                    ),
+                    render_device.create_bind_group(
+                        "ssao_spatial_denoise_baked_bind_group",
+                        &pipelines.spatial_denoise_baked_bind_group_layout,
+                        &BindGroupEntries::sequential((
+                            &ssao_textures.ssao_noisy_texture.default_view,
+                            &ssao_textures.depth_differences_texture.default_view,
+                            &ssao_textures
+                                .screen_space_ambient_occlusion_texture
+                                .default_view,
+                            prepass_textures.normal_view().unwrap(),
+                            &bake_texture.texture.default_view,
+                        )),
+                    ),
+                )
+            }
+        };
 
         commands.entity(entity).insert(SsaoBindGroups {
             common_bind_group,
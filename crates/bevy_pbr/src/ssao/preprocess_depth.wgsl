@@ -13,6 +13,9 @@
 @group(0) @binding(3) var preprocessed_depth_mip2: texture_storage_2d<r16float, write>;
 @group(0) @binding(4) var preprocessed_depth_mip3: texture_storage_2d<r16float, write>;
 @group(0) @binding(5) var preprocessed_depth_mip4: texture_storage_2d<r16float, write>;
+#ifdef SSAO_BAKE_STATIC
+@group(0) @binding(6) var normals: texture_2d<f32>;
+#endif
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var<uniform> view: View;
 
@@ -50,7 +53,13 @@ fn preprocess_depth(@builtin(global_invocation_id) global_id: vec3<u32>, @builti
     let pixel_coordinates2 = pixel_coordinates0 + vec2<i32>(0i, 1i);
     let pixel_coordinates3 = pixel_coordinates0 + vec2<i32>(1i, 1i);
     let depths_uv = vec2<f32>(pixel_coordinates0) / view.viewport.zw;
+#ifdef SSAO_BAKE_STATIC
+    // Only static geometry is baked, so treat dynamic pixels (normal alpha of 1) as empty space
+    let is_dynamic = textureGather(3, normals, point_clamp_sampler, depths_uv, vec2<i32>(1i, 1i)) > vec4<f32>(0.5);
+    let depths = select(textureGather(0, input_depth, point_clamp_sampler, depths_uv, vec2<i32>(1i, 1i)), vec4<f32>(0.0), is_dynamic);
+#else
     let depths = textureGather(0, input_depth, point_clamp_sampler, depths_uv, vec2<i32>(1i, 1i));
+#endif
     textureStore(preprocessed_depth_mip0, pixel_coordinates0, vec4<f32>(depths.w, 0.0, 0.0, 0.0));
     textureStore(preprocessed_depth_mip0, pixel_coordinates1, vec4<f32>(depths.z, 0.0, 0.0, 0.0));
     textureStore(preprocessed_depth_mip0, pixel_coordinates2, vec4<f32>(depths.x, 0.0, 0.0, 0.0));
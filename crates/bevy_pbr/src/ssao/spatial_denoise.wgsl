@@ -14,6 +14,10 @@
 @group(0) @binding(0) var ambient_occlusion_noisy: texture_2d<f32>;
 @group(0) @binding(1) var depth_differences: texture_2d<u32>;
 @group(0) @binding(2) var ambient_occlusion: texture_storage_2d<r16float, write>;
+#ifdef SSAO_BAKED
+@group(0) @binding(3) var normals: texture_2d<f32>;
+@group(0) @binding(4) var baked_ambient_occlusion: texture_2d<f32>;
+#endif
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var<uniform> view: View;
 
@@ -23,6 +27,15 @@ fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let pixel_coordinates = vec2<i32>(global_id.xy);
     let uv = vec2<f32>(pixel_coordinates) / view.viewport.zw;
 
+#ifdef SSAO_BAKED
+    // Static pixels keep their baked (and already denoised) visibility
+    let baked_visibility = textureLoad(baked_ambient_occlusion, pixel_coordinates, 0).r;
+    if textureLoad(normals, pixel_coordinates, 0).a < 0.5 && baked_visibility >= 0.0 {
+        textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(baked_visibility, 0.0, 0.0, 0.0));
+        return;
+    }
+#endif
+
     let edges0 = textureGather(0, depth_differences, point_clamp_sampler, uv);
     let edges1 = textureGather(0, depth_differences, point_clamp_sampler, uv, vec2<i32>(2i, 0i));
     let edges2 = textureGather(0, depth_differences, point_clamp_sampler, uv, vec2<i32>(1i, 2i));
@@ -39,14 +52,14 @@ fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
     center_edges *= vec4<f32>(left_edges.y, right_edges.x, top_edges.w, bottom_edges.z);
 
     let center_weight = 1.2;
-    let left_weight = center_edges.x;
-    let right_weight = center_edges.y;
-    let top_weight = center_edges.z;
-    let bottom_weight = center_edges.w;
-    let top_left_weight = 0.425 * (top_weight * top_edges.x + left_weight * left_edges.z);
-    let top_right_weight = 0.425 * (top_weight * top_edges.y + right_weight * right_edges.z);
-    let bottom_left_weight = 0.425 * (bottom_weight * bottom_edges.x + left_weight * left_edges.w);
-    let bottom_right_weight = 0.425 * (bottom_weight * bottom_edges.y + right_weight * right_edges.w);
+    var left_weight = center_edges.x;
+    var right_weight = center_edges.y;
+    var top_weight = center_edges.z;
+    var bottom_weight = center_edges.w;
+    var top_left_weight = 0.425 * (top_weight * top_edges.x + left_weight * left_edges.z);
+    var top_right_weight = 0.425 * (top_weight * top_edges.y + right_weight * right_edges.z);
+    var bottom_left_weight = 0.425 * (bottom_weight * bottom_edges.x + left_weight * left_edges.w);
+    var bottom_right_weight = 0.425 * (bottom_weight * bottom_edges.y + right_weight * right_edges.w);
 
     let center_visibility = visibility0.y;
     let left_visibility = visibility0.x;
@@ -58,6 +71,23 @@ fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let bottom_left_visibility = visibility2.w;
     let bottom_right_visibility = visibility3.w;
 
+#ifdef SSAO_BAKE_STATIC
+    // Pixels that aren't baked hold a visibility of -1: keep them marked, and leave them out of
+    // the blur of their neighbors
+    if center_visibility < 0.0 {
+        textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(-1.0, 0.0, 0.0, 0.0));
+        return;
+    }
+    left_weight *= step(0.0, left_visibility);
+    right_weight *= step(0.0, right_visibility);
+    top_weight *= step(0.0, top_visibility);
+    bottom_weight *= step(0.0, bottom_visibility);
+    top_left_weight *= step(0.0, top_left_visibility);
+    top_right_weight *= step(0.0, top_right_visibility);
+    bottom_left_weight *= step(0.0, bottom_left_visibility);
+    bottom_right_weight *= step(0.0, bottom_right_visibility);
+#endif
+
     var sum = center_visibility;
     sum += left_visibility * left_weight;
     sum += right_visibility * right_weight;
@@ -81,6 +81,10 @@ impl ColorAttachment {
 }
 
 /// A wrapper for a [`TextureView`] that is used as a depth-only [`RenderPassDepthStencilAttachment`].
+///
+/// Clones share whether the attachment has been cleared yet, so passes rendering into different
+/// regions of the same view only clear it once.
+#[derive(Clone)]
 pub struct DepthAttachment {
     pub view: TextureView,
     clear_value: Option<f32>,
@@ -23,6 +23,7 @@ fn main() {
         .insert_resource(AmbientLight {
             color: LegacyColor::WHITE,
             brightness: 1.0 / 5.0f32,
+            ..default()
         })
         .add_plugins((
             DefaultPlugins.set(
@@ -327,6 +327,7 @@ fn setup(
             diffuse_map: asset_server.load("environment_maps/pisa_diffuse_rgb9e5_zstd.ktx2"),
             specular_map: asset_server.load("environment_maps/pisa_specular_rgb9e5_zstd.ktx2"),
             intensity: 150.0,
+            rotation: Quat::IDENTITY,
         },
         FogSettings {
             color: LegacyColor::rgba_u8(43, 44, 47, 255),
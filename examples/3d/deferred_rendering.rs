@@ -18,7 +18,10 @@ fn main() {
     App::new()
         .insert_resource(Msaa::Off)
         .insert_resource(DefaultOpaqueRendererMethod::deferred())
-        .insert_resource(DirectionalLightShadowMap { size: 4096 })
+        .insert_resource(DirectionalLightShadowMap {
+            size: 4096,
+            ..default()
+        })
         .add_plugins(DefaultPlugins)
         .insert_resource(Normal(None))
         .insert_resource(Pause(true))
@@ -59,6 +62,7 @@ fn setup(
             diffuse_map: asset_server.load("environment_maps/pisa_diffuse_rgb9e5_zstd.ktx2"),
             specular_map: asset_server.load("environment_maps/pisa_specular_rgb9e5_zstd.ktx2"),
             intensity: 2000.0,
+            rotation: Quat::IDENTITY,
         },
         DepthPrepass,
         MotionVectorPrepass,
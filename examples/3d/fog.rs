@@ -186,7 +186,9 @@ fn update_system(
         } else if let FogFalloff::ExponentialSquared { density } = fog.falloff {
             fog.falloff = FogFalloff::Exponential { density };
         } else {
-            fog.falloff = FogFalloff::Exponential { density: 0.07 };
+            fog.falloff = FogFalloff::Exponential {
+                density: Vec3::splat(0.07),
+            };
         };
     }
 
@@ -196,7 +198,9 @@ fn update_system(
         } else if let FogFalloff::ExponentialSquared { .. } = fog.falloff {
             // No change
         } else {
-            fog.falloff = FogFalloff::Exponential { density: 0.07 };
+            fog.falloff = FogFalloff::Exponential {
+                density: Vec3::splat(0.07),
+            };
         };
     }
 
@@ -230,9 +234,7 @@ fn update_system(
 
         if keycode.pressed(KeyCode::KeyA) {
             *density -= delta * 0.5 * *density;
-            if *density < 0.0 {
-                *density = 0.0;
-            }
+            *density = density.max(Vec3::ZERO);
         }
         if keycode.pressed(KeyCode::KeyS) {
             *density += delta * 0.5 * *density;
@@ -245,9 +247,7 @@ fn update_system(
 
         if keycode.pressed(KeyCode::KeyA) {
             *density -= delta * 0.5 * *density;
-            if *density < 0.0 {
-                *density = 0.0;
-            }
+            *density = density.max(Vec3::ZERO);
         }
         if keycode.pressed(KeyCode::KeyS) {
             *density += delta * 0.5 * *density;
@@ -150,6 +150,7 @@ fn main() {
         .insert_resource(AmbientLight {
             color: LegacyColor::WHITE,
             brightness: 0.0,
+            ..default()
         })
         .add_systems(Startup, setup)
         .add_systems(PreUpdate, create_cubes)
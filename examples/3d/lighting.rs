@@ -124,6 +124,7 @@ fn setup(
     commands.insert_resource(AmbientLight {
         color: LegacyColor::ORANGE_RED,
         brightness: 0.02,
+        ..default()
     });
 
     // red point light
@@ -90,6 +90,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(AmbientLight {
         color: LegacyColor::rgb_u8(210, 220, 240),
         brightness: 1.0,
+        ..default()
     });
 
     commands.insert_resource(Cubemap {
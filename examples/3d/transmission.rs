@@ -42,7 +42,10 @@ fn main() {
 
     app.add_plugins(DefaultPlugins)
         .insert_resource(ClearColor(LegacyColor::BLACK))
-        .insert_resource(PointLightShadowMap { size: 2048 })
+        .insert_resource(PointLightShadowMap {
+            size: 2048,
+            ..default()
+        })
         .insert_resource(AmbientLight {
             brightness: 0.0,
             ..default()
@@ -343,6 +346,7 @@ fn setup(
             intensity: 25.0,
             diffuse_map: asset_server.load("environment_maps/pisa_diffuse_rgb9e5_zstd.ktx2"),
             specular_map: asset_server.load("environment_maps/pisa_specular_rgb9e5_zstd.ktx2"),
+            rotation: Quat::IDENTITY,
         },
         BloomSettings::default(),
     ));
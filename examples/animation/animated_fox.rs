@@ -10,6 +10,7 @@ fn main() {
         .insert_resource(AmbientLight {
             color: LegacyColor::WHITE,
             brightness: 2000.,
+            ..default()
         })
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, setup)
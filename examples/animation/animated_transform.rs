@@ -11,6 +11,7 @@ fn main() {
         .insert_resource(AmbientLight {
             color: LegacyColor::WHITE,
             brightness: 150.0,
+            ..default()
         })
         .add_systems(Startup, setup)
         .run();